@@ -0,0 +1,441 @@
+//! AI providers reached over HTTP
+//!
+//! Two request formats cover the hosted APIs and most self-hosted servers:
+//! Anthropic's Messages API and OpenAI's Chat Completions API. Ollama, llama.cpp
+//! and LM Studio all speak the latter on localhost, without a key. The base URL,
+//! model and API key variable come from [`AiConfig`]; requests time out, and
+//! are retried with backoff when rate limited, overloaded or timed out.
+
+use anyhow::{Context, Result, bail};
+use serde_json::json;
+use std::time::Duration;
+
+use crate::config::{AiConfig, AiProvider};
+use crate::http::agent;
+
+/// Messages API version sent to Anthropic-compatible servers
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// Ollama's OpenAI-compatible API on its default port
+const OLLAMA_URL: &str = "http://localhost:11434/v1";
+
+/// llama.cpp's `llama-server` default (LM Studio uses port 1234)
+const LOCAL_URL: &str = "http://localhost:8080/v1";
+
+/// Tokens a reply may use (Anthropic requires a limit)
+const MAX_TOKENS: u32 = 4096;
+
+/// Longest wait before a retry, whatever the server asks for
+const MAX_RETRY_WAIT: Duration = Duration::from_secs(30);
+
+/// Request format an API speaks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiFormat {
+    /// `POST {base_url}/v1/messages`
+    Anthropic,
+    /// `POST {base_url}/chat/completions`
+    OpenAi,
+}
+
+/// Client for an AI provider's HTTP API
+#[derive(Debug, Clone)]
+pub struct ApiClient {
+    name: String,
+    format: ApiFormat,
+    base_url: String,
+    model: String,
+    api_key: Option<String>,
+    timeout: Duration,
+    max_retries: u32,
+    /// Suggestion when the server can't be reached
+    unreachable_hint: Option<&'static str>,
+}
+
+impl ApiClient {
+    /// Client for a server speaking `format`, without a key and with default timeout and retries
+    pub fn new(format: ApiFormat, base_url: &str, model: &str) -> Self {
+        let config = AiConfig::default();
+        Self {
+            name: base_url.to_string(),
+            format,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+            api_key: None,
+            timeout: Duration::from_secs(config.timeout_secs),
+            max_retries: config.max_retries,
+            unreachable_hint: None,
+        }
+    }
+
+    /// Client for the configured provider, with its API key from the environment
+    pub fn from_config(config: &AiConfig) -> Result<Self> {
+        let (format, base_url, model) = match config.provider {
+            AiProvider::Anthropic => (
+                ApiFormat::Anthropic,
+                "https://api.anthropic.com",
+                Some("claude-sonnet-4-5"),
+            ),
+            AiProvider::OpenAi => (
+                ApiFormat::OpenAi,
+                "https://api.openai.com/v1",
+                Some("gpt-4.1-mini"),
+            ),
+            // Ollama serves the OpenAI API too, but there's no model everyone has pulled
+            AiProvider::Ollama => (ApiFormat::OpenAi, OLLAMA_URL, None),
+            // llama.cpp answers with whatever model it loaded, whatever the request names
+            AiProvider::Local => (ApiFormat::OpenAi, LOCAL_URL, Some("local")),
+            provider => bail!("AI provider '{}' has no HTTP API", provider),
+        };
+        let base_url = config.base_url.as_deref().unwrap_or(base_url);
+
+        let Some(model) = config.model.as_deref().or(model) else {
+            let installed = ollama_models(base_url)
+                .ok()
+                .filter(|models| !models.is_empty())
+                .map(|models| format!(" (installed: {})", models.join(", ")))
+                .unwrap_or_default();
+            bail!(
+                "No model set for {}. Run 'hoards ai config set {} --model <name>'{}",
+                config.provider,
+                config.provider,
+                installed
+            );
+        };
+
+        let key_var = config
+            .api_key_env
+            .as_deref()
+            .or(config.provider.api_key_env());
+        let api_key = key_var
+            .and_then(|var| std::env::var(var).ok())
+            .filter(|key| !key.trim().is_empty());
+        // Hosted APIs need a key, local servers and a custom URL may not
+        let needs_key = config.provider.api_key_env().is_some() && config.base_url.is_none();
+        if api_key.is_none() && needs_key {
+            bail!(
+                "{} is not set. Export your API key, or name another variable with \"api_key_env\" in the AI config",
+                key_var.unwrap_or("The API key")
+            );
+        }
+
+        let mut client = Self::new(format, base_url, model);
+        client.name = config.provider.to_string();
+        client.api_key = api_key;
+        client.timeout = Duration::from_secs(config.timeout_secs);
+        client.max_retries = config.max_retries;
+        client.unreachable_hint = match config.provider {
+            AiProvider::Ollama => Some("is `ollama serve` running?"),
+            AiProvider::Local => Some("is the model server running?"),
+            _ => None,
+        };
+        Ok(client)
+    }
+
+    /// Model the client requests
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Server the client talks to
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Whether the server runs on this machine, so prompts never leave it
+    pub fn is_local(&self) -> bool {
+        is_loopback_url(&self.base_url)
+    }
+
+    /// Send a prompt and return the model's reply
+    pub fn complete(&self, prompt: &str) -> Result<String> {
+        let (url, body) = match self.format {
+            ApiFormat::Anthropic => (
+                format!("{}/v1/messages", self.base_url),
+                json!({
+                    "model": self.model,
+                    "max_tokens": MAX_TOKENS,
+                    "messages": [{"role": "user", "content": prompt}],
+                }),
+            ),
+            ApiFormat::OpenAi => (
+                format!("{}/chat/completions", self.base_url),
+                json!({
+                    "model": self.model,
+                    "messages": [{"role": "user", "content": prompt}],
+                }),
+            ),
+        };
+
+        let mut attempt = 0;
+        loop {
+            let mut request = agent()
+                .post(&url)
+                .header("User-Agent", concat!("hoards/", env!("CARGO_PKG_VERSION")));
+            match (self.format, &self.api_key) {
+                (ApiFormat::Anthropic, key) => {
+                    request = request.header("anthropic-version", ANTHROPIC_VERSION);
+                    if let Some(key) = key {
+                        request = request.header("x-api-key", key);
+                    }
+                }
+                (ApiFormat::OpenAi, Some(key)) => {
+                    request = request.header("Authorization", format!("Bearer {}", key));
+                }
+                (ApiFormat::OpenAi, None) => {}
+            }
+
+            let result = request
+                .config()
+                .http_status_as_error(false)
+                .timeout_global(Some(self.timeout))
+                .build()
+                .send_json(&body);
+
+            let retry_after = match result {
+                Ok(mut response) => {
+                    let retry_after = response
+                        .headers()
+                        .get("retry-after")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(|v| v.trim().parse::<u64>().ok())
+                        .map(Duration::from_secs);
+                    let status = response.status().as_u16();
+                    let text = response
+                        .body_mut()
+                        .read_to_string()
+                        .with_context(|| format!("Failed to read {} response", self.name))?;
+
+                    if (200..300).contains(&status) {
+                        return parse_reply(self.format, &text)
+                            .with_context(|| format!("Unexpected {} response", self.name));
+                    }
+                    // Rate limited or overloaded (Anthropic's 529) is worth another try
+                    let transient = status == 429 || status >= 500;
+                    if !transient || attempt >= self.max_retries {
+                        return Err(self.status_error(status, &text));
+                    }
+                    retry_after
+                }
+                Err(e) if attempt < self.max_retries && is_transient(&e) => None,
+                Err(e) => {
+                    return Err(e).with_context(|| match self.unreachable_hint {
+                        Some(hint) => format!("Failed to reach {} ({})", self.base_url, hint),
+                        None => format!("Failed to reach {}", self.base_url),
+                    });
+                }
+            };
+
+            attempt += 1;
+            let backoff = Duration::from_secs(1u64 << attempt.min(5));
+            std::thread::sleep(retry_after.unwrap_or(backoff).min(MAX_RETRY_WAIT));
+        }
+    }
+
+    /// Error for a failed request, with the server's message
+    fn status_error(&self, status: u16, body: &str) -> anyhow::Error {
+        let message = serde_json::from_str::<serde_json::Value>(body)
+            .ok()
+            .and_then(|v| v["error"]["message"].as_str().map(str::to_string))
+            .unwrap_or_else(|| body.trim().to_string());
+
+        match status {
+            401 | 403 if self.api_key.is_some() => {
+                anyhow::anyhow!("{} rejected the API key: {}", self.name, message)
+            }
+            401 | 403 => anyhow::anyhow!("{} requires an API key: {}", self.name, message),
+            404 => anyhow::anyhow!(
+                "{} API not found at {} (check \"base_url\" and \"model\"): {}",
+                self.name,
+                self.base_url,
+                message
+            ),
+            _ => anyhow::anyhow!("{} API error {}: {}", self.name, status, message),
+        }
+    }
+}
+
+/// Text of a successful reply
+fn parse_reply(format: ApiFormat, body: &str) -> Result<String> {
+    let json: serde_json::Value = serde_json::from_str(body)?;
+    let text = match format {
+        ApiFormat::Anthropic => json["content"]
+            .as_array()
+            .context("No content in reply")?
+            .iter()
+            .filter(|block| block["type"] == "text")
+            .filter_map(|block| block["text"].as_str())
+            .collect::<Vec<_>>()
+            .join(""),
+        ApiFormat::OpenAi => json["choices"][0]["message"]["content"]
+            .as_str()
+            .context("No message in reply")?
+            .to_string(),
+    };
+    Ok(text.trim().to_string())
+}
+
+/// Whether a URL points at this machine (`localhost` or a loopback address)
+pub fn is_loopback_url(url: &str) -> bool {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let host = match authority.strip_prefix('[') {
+        Some(v6) => v6.split(']').next().unwrap_or(v6),
+        None => authority.split(':').next().unwrap_or(authority),
+    };
+    host == "localhost"
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// Models pulled into the Ollama server whose OpenAI API is at `base_url`
+pub fn ollama_models(base_url: &str) -> Result<Vec<String>> {
+    let root = base_url.trim_end_matches('/').trim_end_matches("/v1");
+    let mut response = agent()
+        .get(&format!("{}/api/tags", root))
+        .config()
+        .timeout_global(Some(Duration::from_secs(5)))
+        .build()
+        .call()
+        .with_context(|| format!("Failed to reach Ollama at {}", root))?;
+    let json: serde_json::Value = response.body_mut().read_json()?;
+    Ok(json["models"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m["name"].as_str().map(str::to_string))
+        .collect())
+}
+
+/// Failures that may succeed on a retry
+fn is_transient(error: &ureq::Error) -> bool {
+    matches!(
+        error,
+        ureq::Error::Timeout(_) | ureq::Error::Io(_) | ureq::Error::ConnectionFailed
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock_server;
+
+    #[test]
+    fn test_anthropic_request() {
+        let (url, server) = mock_server(vec![
+            r#"HTTP/1.1 200 OK
+
+{"content": [{"type": "thinking", "thinking": "hmm"}, {"type": "text", "text": " [\"a\"] "}]}"#,
+        ]);
+        let mut client = ApiClient::new(ApiFormat::Anthropic, &format!("{}/", url), "model-x");
+        client.api_key = Some("secret".to_string());
+
+        assert_eq!(client.complete("Say hi").unwrap(), r#"["a"]"#);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("post /v1/messages "));
+        assert!(requests[0].contains("x-api-key: secret"));
+        assert!(requests[0].contains("anthropic-version: 2023-06-01"));
+        let body = requests[0].split_once("\r\n\n").unwrap().1;
+        let body: serde_json::Value = serde_json::from_str(body).unwrap();
+        assert_eq!(body["model"], "model-x");
+        assert_eq!(body["messages"][0]["content"], "Say hi");
+    }
+
+    #[test]
+    fn test_openai_request_retries() {
+        let (url, server) = mock_server(vec![
+            "HTTP/1.1 429 Too Many Requests\nretry-after: 0\n\n{\"error\": {\"message\": \"slow down\"}}",
+            r#"HTTP/1.1 200 OK
+
+{"choices": [{"message": {"role": "assistant", "content": "hello"}}]}"#,
+            "HTTP/1.1 401 Unauthorized\n\n{\"error\": {\"message\": \"bad key\"}}",
+        ]);
+        let client = ApiClient::new(ApiFormat::OpenAi, &url, "local-model");
+
+        assert_eq!(client.complete("Say hi").unwrap(), "hello");
+        let error = client.complete("Say hi").unwrap_err().to_string();
+        assert!(error.contains("requires an API key: bad key"), "{}", error);
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("post /chat/completions "));
+        assert!(!requests[0].contains("authorization"));
+        assert_eq!(requests.len(), 3);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = AiConfig {
+            provider: AiProvider::OpenAi,
+            base_url: Some("http://localhost:8080/v1".to_string()),
+            api_key_env: Some("HOARDS_TEST_UNSET_KEY".to_string()),
+            ..AiConfig::default()
+        };
+        // A custom server may not need a key
+        let client = ApiClient::from_config(&config).unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8080/v1");
+        assert_eq!(client.model(), "gpt-4.1-mini");
+
+        let config = AiConfig {
+            base_url: None,
+            ..config
+        };
+        let error = ApiClient::from_config(&config).unwrap_err().to_string();
+        assert!(error.starts_with("HOARDS_TEST_UNSET_KEY is not set"));
+
+        let config = AiConfig {
+            provider: AiProvider::Claude,
+            ..AiConfig::default()
+        };
+        assert!(ApiClient::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_local_providers() {
+        let config = AiConfig {
+            provider: AiProvider::Local,
+            api_key_env: Some("HOARDS_TEST_UNSET_KEY".to_string()),
+            ..AiConfig::default()
+        };
+        // Local servers need neither a key nor a model name
+        let client = ApiClient::from_config(&config).unwrap();
+        assert_eq!(client.base_url(), "http://localhost:8080/v1");
+        assert_eq!(client.model(), "local");
+        assert!(client.is_local());
+
+        let (url, server) = mock_server(vec![
+            r#"HTTP/1.1 200 OK
+
+{"models": [{"name": "llama3.2:latest"}, {"name": "qwen2.5-coder:7b"}]}"#,
+        ]);
+        let config = AiConfig {
+            provider: AiProvider::Ollama,
+            base_url: Some(format!("{}/v1", url)),
+            ..AiConfig::default()
+        };
+        // Ollama has no default model, the error lists what's installed
+        let error = ApiClient::from_config(&config).unwrap_err().to_string();
+        assert!(error.contains("--model"), "{}", error);
+        assert!(
+            error.contains("installed: llama3.2:latest, qwen2.5-coder:7b"),
+            "{}",
+            error
+        );
+        assert!(server.join().unwrap()[0].starts_with("get /api/tags "));
+
+        let config = AiConfig {
+            model: Some("llama3.2".to_string()),
+            base_url: None,
+            ..config
+        };
+        let client = ApiClient::from_config(&config).unwrap();
+        assert_eq!(client.base_url(), "http://localhost:11434/v1");
+        assert_eq!(client.model(), "llama3.2");
+
+        let remote = ApiClient::new(ApiFormat::OpenAi, "https://api.openai.com/v1", "m");
+        assert!(!remote.is_local());
+        assert!(ApiClient::new(ApiFormat::OpenAi, "http://[::1]:1234/v1", "m").is_local());
+        assert!(is_loopback_url("http://127.0.0.1:8080/v1"));
+        assert!(!is_loopback_url("http://gpu-box.lan:11434/v1"));
+    }
+}
@@ -1,18 +1,31 @@
 //! AI provider integration for smart features
 //!
-//! Provides functions to invoke configured AI CLI tools (claude, gemini, codex, opencode)
-//! and parse their responses for categorization, description generation, and bundle suggestions.
+//! Provides functions to invoke the configured AI provider, either a CLI tool (claude,
+//! gemini, codex, opencode) or an HTTP API (see [`api`]), and parse its responses for
+//! categorization, description generation, and bundle suggestions.
 //!
 //! Prompts are loaded from `~/.config/hoards/prompts/` and can be customized by the user.
 //! If a prompt file is missing, embedded defaults are used.
+//!
+//! Each task ([`AiTask`]) declares the JSON schema its response must match. A
+//! response that doesn't is sent back to the model once with the problems
+//! found; whatever still doesn't match is dropped and reported (see [`schema`]).
 
 use crate::config::{AiProvider, HoardConfig};
 use crate::models::{Bundle, Tool};
 use anyhow::{Context, Result, bail};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Command;
 
+pub mod api;
+pub mod schema;
+
+pub use api::{ApiClient, ApiFormat};
+
 // ==================== Embedded default prompts ====================
 
 const DEFAULT_CATEGORIZE_PROMPT: &str = r#"You are helping categorize CLI tools. Here are the existing categories: {{CATEGORIES}}
@@ -176,9 +189,13 @@ Modern replacement tools installed:
 Unused installed tools with high potential:
 {{UNUSED_TOOLS}}
 
+Installed tools that often exit with an error:
+{{FAILING_TOOLS}}
+
 Provide a brief (2-3 sentence) personalized insight about:
 1. The user's apparent workflow patterns
 2. Which specific unused tools would benefit them most based on their usage
+3. Why any frequently failing tools might be failing (broken install, configuration, or misuse)
 
 Respond with JSON:
 {"insight": "Your personalized analysis here"}
@@ -342,11 +359,22 @@ pub struct UnderutilizedTool {
     pub stars: Option<u64>,
 }
 
+/// An installed tool that often exits with an error (from the shell hook)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailingTool {
+    pub name: String,
+    pub runs: i64,
+    pub failures: i64,
+    pub last_failure: Option<String>,
+}
+
 /// Result of usage analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AnalysisResult {
     pub tips: Vec<AnalyzeTip>,
     pub underutilized: Vec<UnderutilizedTool>,
+    #[serde(default)]
+    pub failing: Vec<FailingTool>,
     pub ai_insight: Option<String>,
 }
 
@@ -404,6 +432,10 @@ pub fn invoke_ai(prompt: &str) -> Result<String> {
         bail!("No AI provider configured. Run 'hoards ai set <provider>' first.");
     }
 
+    if provider.uses_api() {
+        return ApiClient::from_config(&config.ai)?.complete(prompt);
+    }
+
     let cmd_name = provider
         .command()
         .context("Invalid AI provider configuration")?;
@@ -447,7 +479,11 @@ pub fn invoke_ai(prompt: &str) -> Result<String> {
                 .output()
                 .context("Failed to execute opencode")?
         }
-        AiProvider::None => unreachable!(),
+        AiProvider::Anthropic
+        | AiProvider::OpenAi
+        | AiProvider::Ollama
+        | AiProvider::Local
+        | AiProvider::None => unreachable!(),
     };
 
     if !output.status.success() {
@@ -459,6 +495,108 @@ pub fn invoke_ai(prompt: &str) -> Result<String> {
     Ok(response.trim().to_string())
 }
 
+// ==================== Tasks ====================
+
+/// Longest previous response quoted back to the model when asking for a fix
+const MAX_REPAIR_QUOTE: usize = 4000;
+
+/// A response checked against its task's schema
+#[derive(Debug, Clone)]
+pub struct Parsed<T> {
+    pub value: T,
+    /// Entries that didn't match the schema and were left out, with why
+    pub dropped: Vec<String>,
+}
+
+/// Something the AI is asked to do, and the shape of the answer
+pub struct AiTask<T> {
+    /// Short name for messages
+    pub name: &'static str,
+    schema: fn() -> Value,
+    convert: fn(Value) -> Result<T>,
+}
+
+impl<T> AiTask<T> {
+    /// JSON schema a response must match
+    pub fn schema(&self) -> Value {
+        (self.schema)()
+    }
+
+    /// Parse a response, keeping the entries that match the schema
+    pub fn parse(&self, response: &str) -> Result<Parsed<T>> {
+        let schema = self.schema();
+        let json = schema::find_json(response, &schema)?;
+        let (value, dropped) = schema::validate(json, &schema);
+        let Some(value) = value else {
+            bail!(
+                "AI response doesn't match the expected format: {}",
+                dropped.join("; ")
+            );
+        };
+        let value = (self.convert)(value)
+            .with_context(|| format!("Failed to read AI {} response", self.name))?;
+        Ok(Parsed { value, dropped })
+    }
+
+    /// Send a prompt and parse the response
+    ///
+    /// A response that doesn't match the schema is sent back once with the
+    /// problems found. If the second try isn't clean either, the better of the
+    /// two is returned with what was dropped.
+    pub fn invoke(&self, prompt: &str) -> Result<Parsed<T>> {
+        self.invoke_with(prompt, invoke_ai)
+    }
+
+    fn invoke_with(
+        &self,
+        prompt: &str,
+        send: impl Fn(&str) -> Result<String>,
+    ) -> Result<Parsed<T>> {
+        let response = send(prompt)?;
+        let first = self.parse(&response);
+        let problems = match &first {
+            Ok(parsed) if parsed.dropped.is_empty() => return first,
+            Ok(parsed) => parsed.dropped.clone(),
+            Err(e) => vec![format!("{:#}", e)],
+        };
+
+        let retry = send(&self.repair_prompt(prompt, &response, &problems))
+            .and_then(|response| self.parse(&response));
+        match (first, retry) {
+            (Ok(first), Ok(retry)) if first.dropped.len() < retry.dropped.len() => Ok(first),
+            (_, Ok(retry)) => Ok(retry),
+            (Ok(first), Err(_)) => Ok(first),
+            (Err(e), Err(_)) => Err(e),
+        }
+    }
+
+    /// The original prompt followed by the rejected response and what was wrong with it
+    fn repair_prompt(&self, prompt: &str, response: &str, problems: &[String]) -> String {
+        let quoted: String = response.chars().take(MAX_REPAIR_QUOTE).collect();
+        let problems: Vec<String> = problems.iter().map(|p| format!("- {}", p)).collect();
+        format!(
+            "{}\n\nYour previous response was:\n{}\n\nIt doesn't match the expected format:\n{}\n\n\
+             Respond again with only JSON matching this schema, nothing else:\n{}\n",
+            prompt.trim_end(),
+            quoted,
+            problems.join("\n"),
+            serde_json::to_string_pretty(&self.schema()).unwrap_or_default()
+        )
+    }
+}
+
+fn from_json<T: DeserializeOwned>(value: Value) -> Result<T> {
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Schema of a JSON object mapping tool names to non-empty strings
+fn string_map_schema() -> Value {
+    json!({
+        "type": "object",
+        "additionalProperties": {"type": "string", "minLength": 1}
+    })
+}
+
 // ==================== Categorize ====================
 
 /// Generate a prompt for categorizing tools
@@ -486,17 +624,12 @@ pub fn categorize_prompt(tools: &[Tool], existing_categories: &[String]) -> Stri
         .replace("{{TOOLS}}", &tool_list.join("\n"))
 }
 
-/// Parse categorization response from AI
-pub fn parse_categorize_response(
-    response: &str,
-) -> Result<std::collections::HashMap<String, String>> {
-    let json_str = extract_json_object(response)?;
-
-    let map: std::collections::HashMap<String, String> =
-        serde_json::from_str(&json_str).context("Failed to parse AI response as JSON")?;
-
-    Ok(map)
-}
+/// Assign categories to tools: tool name to category
+pub const CATEGORIZE: AiTask<HashMap<String, String>> = AiTask {
+    name: "categorize",
+    schema: string_map_schema,
+    convert: from_json,
+};
 
 // ==================== Describe ====================
 
@@ -508,26 +641,22 @@ pub fn describe_prompt(tools: &[Tool]) -> String {
     template.replace("{{TOOLS}}", &tool_list.join("\n"))
 }
 
-/// Parse description response from AI
-pub fn parse_describe_response(
-    response: &str,
-) -> Result<std::collections::HashMap<String, String>> {
-    let json_str = extract_json_object(response)?;
-
-    let map: std::collections::HashMap<String, String> =
-        serde_json::from_str(&json_str).context("Failed to parse AI response as JSON")?;
-
-    Ok(map)
-}
+/// Describe tools: tool name to description
+pub const DESCRIBE: AiTask<HashMap<String, String>> = AiTask {
+    name: "describe",
+    schema: string_map_schema,
+    convert: from_json,
+};
 
 // ==================== Suggest Bundle ====================
 
 /// Bundle suggestion from AI
-#[derive(Debug)]
+#[derive(Debug, Deserialize)]
 pub struct BundleSuggestion {
     pub name: String,
     pub description: String,
     pub tools: Vec<String>,
+    #[serde(default)]
     pub reasoning: Option<String>,
 }
 
@@ -585,31 +714,30 @@ pub fn suggest_bundle_prompt(
         .replace("{{TOOLS}}", &tool_list.join("\n"))
 }
 
-/// Parse bundle suggestion response from AI
-pub fn parse_bundle_response(response: &str) -> Result<Vec<BundleSuggestion>> {
-    let json_str = extract_json_array(response)?;
-
-    #[derive(serde::Deserialize)]
-    struct RawSuggestion {
-        name: String,
-        description: String,
-        tools: Vec<String>,
-        reasoning: Option<String>,
-    }
-
-    let raw: Vec<RawSuggestion> =
-        serde_json::from_str(&json_str).context("Failed to parse AI response as JSON")?;
-
-    Ok(raw
-        .into_iter()
-        .map(|r| BundleSuggestion {
-            name: r.name,
-            description: r.description,
-            tools: r.tools,
-            reasoning: r.reasoning,
+/// Suggest bundles of related tools
+pub const SUGGEST_BUNDLES: AiTask<Vec<BundleSuggestion>> = AiTask {
+    name: "bundle suggestion",
+    schema: || {
+        json!({
+            "type": "array",
+            "items": {
+                "type": "object",
+                "required": ["name", "description", "tools"],
+                "properties": {
+                    "name": {"type": "string", "minLength": 1},
+                    "description": {"type": "string"},
+                    "tools": {
+                        "type": "array",
+                        "minItems": 1,
+                        "items": {"type": "string", "minLength": 1}
+                    },
+                    "reasoning": {"type": "string"}
+                }
+            }
         })
-        .collect())
-}
+    },
+    convert: from_json,
+};
 
 // ==================== Extract ====================
 
@@ -675,61 +803,6 @@ pub fn parse_github_url(url: &str) -> Result<(String, String)> {
     bail!("Invalid GitHub URL format: {}", url)
 }
 
-/// Fetch README content from GitHub using gh CLI
-pub fn fetch_readme(owner: &str, repo: &str) -> Result<String> {
-    let output = Command::new("gh")
-        .args(["api", &format!("repos/{}/{}/readme", owner, repo)])
-        .output()
-        .context("Failed to run gh api")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("Failed to fetch README: {}", stderr);
-    }
-
-    #[derive(serde::Deserialize)]
-    struct ReadmeResponse {
-        content: String,
-        encoding: String,
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let readme: ReadmeResponse =
-        serde_json::from_str(&stdout).context("Failed to parse README response")?;
-
-    if readme.encoding != "base64" {
-        bail!("Unexpected README encoding: {}", readme.encoding);
-    }
-
-    // Decode base64 content
-    use base64::{Engine as _, engine::general_purpose};
-    let decoded = general_purpose::STANDARD
-        .decode(readme.content.replace('\n', ""))
-        .context("Failed to decode README content")?;
-
-    String::from_utf8(decoded).context("README is not valid UTF-8")
-}
-
-/// Fetch the latest commit SHA for a repo (used for cache versioning)
-pub fn fetch_repo_version(owner: &str, repo: &str) -> Result<String> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{}/{}/commits/HEAD", owner, repo),
-            "--jq",
-            ".sha",
-        ])
-        .output()
-        .context("Failed to run gh api")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("Failed to fetch repo version: {}", stderr);
-    }
-
-    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
-}
-
 /// Generate extraction prompt
 pub fn extract_prompt(readme: &str) -> String {
     // Truncate README if too long (keep first ~8000 chars to leave room for prompt)
@@ -743,23 +816,25 @@ pub fn extract_prompt(readme: &str) -> String {
     template.replace("{{README}}", &readme_truncated)
 }
 
-/// Parse extraction response from AI
-pub fn parse_extract_response(response: &str) -> Result<ExtractedTool> {
-    let json_str = extract_json_object(response)?;
-
-    let tool: ExtractedTool =
-        serde_json::from_str(&json_str).context("Failed to parse AI extraction response")?;
-
-    // Validate required fields
-    if tool.name.is_empty() {
-        bail!("Extracted tool has no name");
-    }
-    if tool.description.is_empty() {
-        bail!("Extracted tool has no description");
-    }
-
-    Ok(tool)
-}
+/// Read a tool's details from its README
+pub const EXTRACT: AiTask<ExtractedTool> = AiTask {
+    name: "extraction",
+    schema: || {
+        json!({
+            "type": "object",
+            "required": ["name", "source", "description", "category"],
+            "properties": {
+                "name": {"type": "string", "minLength": 1},
+                "binary": {"type": "string", "minLength": 1},
+                "source": {"type": "string", "minLength": 1},
+                "install_command": {"type": "string"},
+                "description": {"type": "string", "minLength": 1},
+                "category": {"type": "string", "minLength": 1}
+            }
+        })
+    },
+    convert: from_json,
+};
 
 // ==================== Cheatsheet ====================
 
@@ -906,19 +981,44 @@ pub fn discovery_prompt(query: &str, installed_tools: &[String]) -> String {
         .replace("{{INSTALLED_TOOLS}}", &installed_list)
 }
 
-/// Parse discovery response from AI
-pub fn parse_discovery_response(response: &str) -> Result<DiscoveryResponse> {
-    let json_str = extract_json_object(response)?;
-    let discovery: DiscoveryResponse =
-        serde_json::from_str(&json_str).context("Failed to parse discovery response")?;
-    Ok(discovery)
-}
+/// Recommend tools for what the user is working on
+pub const DISCOVER: AiTask<DiscoveryResponse> = AiTask {
+    name: "discovery",
+    schema: || {
+        json!({
+            "type": "object",
+            "required": ["summary", "tools"],
+            "properties": {
+                "summary": {"type": "string"},
+                "tools": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "required": ["name", "description", "category", "reason", "source", "install_cmd"],
+                        "properties": {
+                            "name": {"type": "string", "minLength": 1},
+                            "binary": {"type": "string"},
+                            "description": {"type": "string"},
+                            "category": {"type": "string", "enum": ["essential", "recommended"]},
+                            "reason": {"type": "string"},
+                            "source": {"type": "string", "minLength": 1},
+                            "install_cmd": {"type": "string", "minLength": 1},
+                            "github": {"type": "string"}
+                        }
+                    }
+                }
+            }
+        })
+    },
+    convert: from_json,
+};
 
 /// Generate an analyze prompt from usage data
 pub fn analyze_prompt(
     traditional_usage: &[(String, i64)],
     modern_tools: &[String],
     unused_tools: &[String],
+    failing_tools: &[FailingTool],
 ) -> String {
     let template = load_prompt("analyze", DEFAULT_ANALYZE_PROMPT);
 
@@ -944,25 +1044,35 @@ pub fn analyze_prompt(
         unused_tools.join(", ")
     };
 
+    let failing_str = if failing_tools.is_empty() {
+        "None".to_string()
+    } else {
+        failing_tools
+            .iter()
+            .map(|t| format!("{} ({} of {} runs failed)", t.name, t.failures, t.runs))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
     template
         .replace("{{TRADITIONAL_USAGE}}", &traditional_str)
         .replace("{{MODERN_TOOLS}}", &modern_str)
         .replace("{{UNUSED_TOOLS}}", &unused_str)
+        .replace("{{FAILING_TOOLS}}", &failing_str)
 }
 
-/// Parse analyze insight response from AI
-pub fn parse_analyze_response(response: &str) -> Result<String> {
-    let json_str = extract_json_object(response)?;
-
-    #[derive(serde::Deserialize)]
-    struct AnalyzeInsight {
-        insight: String,
-    }
-
-    let insight: AnalyzeInsight =
-        serde_json::from_str(&json_str).context("Failed to parse analyze response")?;
-    Ok(insight.insight)
-}
+/// Personal insight on usage analysis
+pub const ANALYZE: AiTask<String> = AiTask {
+    name: "analysis",
+    schema: || {
+        json!({
+            "type": "object",
+            "required": ["insight"],
+            "properties": {"insight": {"type": "string", "minLength": 1}}
+        })
+    },
+    convert: |value| from_json(value["insight"].clone()),
+};
 
 /// Build prompt for migration benefit descriptions
 pub fn migrate_prompt(tools: &[(String, String, String, String, String)]) -> String {
@@ -982,32 +1092,61 @@ pub fn migrate_prompt(tools: &[(String, String, String, String, String)]) -> Str
     prompt_template.replace("{{TOOLS}}", &tools_str)
 }
 
-/// Parse migration benefits response from AI
-pub fn parse_migrate_response(response: &str) -> Result<std::collections::HashMap<String, String>> {
-    let json_str = extract_json_object(response)?;
-
-    #[derive(serde::Deserialize)]
-    struct MigrateBenefits {
-        benefits: std::collections::HashMap<String, String>,
-    }
-
-    let result: MigrateBenefits =
-        serde_json::from_str(&json_str).context("Failed to parse migrate response")?;
-    Ok(result.benefits)
-}
+/// Why each migration is worth it: tool name to benefit
+pub const MIGRATE: AiTask<HashMap<String, String>> = AiTask {
+    name: "migration",
+    schema: || {
+        json!({
+            "type": "object",
+            "required": ["benefits"],
+            "properties": {"benefits": string_map_schema()}
+        })
+    },
+    convert: |value| from_json(value["benefits"].clone()),
+};
 
 /// Check if a binary is installed on the system
 pub fn is_binary_installed(binary: &str) -> bool {
     which::which(binary).is_ok()
 }
 
-/// Parse cheatsheet response from AI
-pub fn parse_cheatsheet_response(response: &str) -> Result<Cheatsheet> {
-    let json_str = extract_json_object(response)?;
-    let cheatsheet: Cheatsheet =
-        serde_json::from_str(&json_str).context("Failed to parse AI cheatsheet response")?;
-    Ok(cheatsheet)
-}
+/// Cheatsheet for a tool or bundle from `--help` output
+pub const CHEATSHEET: AiTask<Cheatsheet> = AiTask {
+    name: "cheatsheet",
+    schema: || {
+        json!({
+            "type": "object",
+            "required": ["title", "sections"],
+            "properties": {
+                "title": {"type": "string", "minLength": 1},
+                "sections": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "required": ["name", "commands"],
+                        "properties": {
+                            "name": {"type": "string", "minLength": 1},
+                            "commands": {
+                                "type": "array",
+                                "minItems": 1,
+                                "items": {
+                                    "type": "object",
+                                    "required": ["cmd", "desc"],
+                                    "properties": {
+                                        "cmd": {"type": "string", "minLength": 1},
+                                        "desc": {"type": "string"}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    },
+    convert: from_json,
+};
 
 /// Get --help output for a tool
 pub fn get_help_output(binary: &str) -> Result<String> {
@@ -1100,63 +1239,10 @@ pub fn format_cheatsheet(cheatsheet: &Cheatsheet) -> String {
     output.join("\n")
 }
 
-// ==================== JSON extraction helpers ====================
-
-/// Extract a JSON object from a response that might contain extra text
-fn extract_json_object(response: &str) -> Result<String> {
-    let start = response
-        .find('{')
-        .context("No JSON object found in response")?;
-    let end = response
-        .rfind('}')
-        .context("No closing brace found in response")?;
-
-    if end <= start {
-        bail!("Invalid JSON structure in response");
-    }
-
-    Ok(response[start..=end].to_string())
-}
-
-/// Extract a JSON array from a response that might contain extra text
-fn extract_json_array(response: &str) -> Result<String> {
-    let start = response
-        .find('[')
-        .context("No JSON array found in response")?;
-    let end = response
-        .rfind(']')
-        .context("No closing bracket found in response")?;
-
-    if end <= start {
-        bail!("Invalid JSON structure in response");
-    }
-
-    Ok(response[start..=end].to_string())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_extract_json_object() {
-        let response = r#"Here's the categorization:
-{"ripgrep": "search", "bat": "files"}
-Done!"#;
-        let json = extract_json_object(response).unwrap();
-        assert_eq!(json, r#"{"ripgrep": "search", "bat": "files"}"#);
-    }
-
-    #[test]
-    fn test_extract_json_array() {
-        let response = r#"Here are my suggestions:
-[{"name": "test", "description": "desc", "tools": ["a", "b"]}]
-"#;
-        let json = extract_json_array(response).unwrap();
-        assert!(json.starts_with('['));
-        assert!(json.ends_with(']'));
-    }
-
     #[test]
     fn test_default_prompts_have_placeholders() {
         assert!(DEFAULT_CATEGORIZE_PROMPT.contains("{{CATEGORIES}}"));
@@ -1214,7 +1300,7 @@ Done!"#;
         let response = r#"Here's the extracted info:
 {"name": "ripgrep", "binary": "rg", "source": "cargo", "install_command": "cargo install ripgrep", "description": "Fast regex search", "category": "search"}
 "#;
-        let tool = parse_extract_response(response).unwrap();
+        let tool = EXTRACT.parse(response).unwrap().value;
         assert_eq!(tool.name, "ripgrep");
         assert_eq!(tool.binary, Some("rg".to_string()));
         assert_eq!(tool.source, "cargo");
@@ -1225,12 +1311,70 @@ Done!"#;
     fn test_parse_extract_response_minimal() {
         let response =
             r#"{"name": "foo", "source": "pip", "description": "A tool", "category": "misc"}"#;
-        let tool = parse_extract_response(response).unwrap();
+        let tool = EXTRACT.parse(response).unwrap().value;
         assert_eq!(tool.name, "foo");
         assert_eq!(tool.binary, None);
         assert_eq!(tool.install_command, None);
     }
 
+    #[test]
+    fn test_task_parse_keeps_valid_entries() {
+        let parsed = CATEGORIZE
+            .parse(
+                r#"```json
+{"ripgrep": "search", "bat": 3, "fd": ""}
+```"#,
+            )
+            .unwrap();
+        assert_eq!(parsed.value.len(), 1);
+        assert_eq!(parsed.value["ripgrep"], "search");
+        assert_eq!(
+            parsed.dropped,
+            ["bat: expected string, got integer", "fd: must not be empty"]
+        );
+
+        let response = r#"{"name": "foo", "source": "pip", "description": "", "category": "misc"}"#;
+        let error = EXTRACT.parse(response).unwrap_err().to_string();
+        assert!(
+            error.contains("description: must not be empty"),
+            "{}",
+            error
+        );
+
+        let parsed = ANALYZE.parse(r#"{"insight": "Use fd"}"#).unwrap();
+        assert_eq!(parsed.value, "Use fd");
+    }
+
+    #[test]
+    fn test_task_invoke_repairs_once() {
+        use std::cell::RefCell;
+
+        let prompts = RefCell::new(Vec::new());
+        let replies = RefCell::new(vec![
+            r#"{"benefits": {"fd": "Faster"}}"#,
+            "Sorry, here you go: {\"benefits\": [\"fd\"]}",
+        ]);
+        let send = |prompt: &str| {
+            prompts.borrow_mut().push(prompt.to_string());
+            Ok(replies.borrow_mut().pop().unwrap().to_string())
+        };
+
+        let parsed = MIGRATE.invoke_with("Explain the migrations", send).unwrap();
+        assert_eq!(parsed.value["fd"], "Faster");
+        assert!(parsed.dropped.is_empty());
+
+        let prompts = prompts.into_inner();
+        assert_eq!(prompts.len(), 2);
+        assert!(prompts[1].starts_with("Explain the migrations\n\nYour previous response was:"));
+        assert!(prompts[1].contains("benefits: expected object, got array"));
+        assert!(prompts[1].contains("\"additionalProperties\""));
+
+        // Still broken after the retry: the first error is reported
+        let send = |_: &str| Ok("no idea".to_string());
+        let error = MIGRATE.invoke_with("Explain", send).unwrap_err();
+        assert_eq!(error.to_string(), "No JSON object found in response");
+    }
+
     #[test]
     fn test_extract_prompt_truncates_long_readme() {
         let long_readme = "x".repeat(10000);
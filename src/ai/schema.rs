@@ -0,0 +1,284 @@
+//! Checking AI responses against the JSON schema of their task
+//!
+//! Models don't always follow the requested format, so a response is checked
+//! field by field rather than deserialized in one go. Entries that don't match
+//! are dropped and reported, and the rest is kept: one bad tool in a batch of
+//! twenty shouldn't lose the other nineteen.
+//!
+//! Schemas use a subset of JSON Schema: `type` (a name or a list of names),
+//! `properties`, `required`, `additionalProperties`, `items`, `minItems`,
+//! `minLength` and `enum`. A property that isn't required may be `null`.
+
+use anyhow::{Result, bail};
+use serde_json::{Map, Value};
+
+/// Locate the JSON value in a response, which may wrap it in prose or a code fence
+pub fn find_json(response: &str, schema: &Value) -> Result<Value> {
+    let trimmed = response.trim();
+    if let Ok(value) = serde_json::from_str(trimmed) {
+        return Ok(value);
+    }
+
+    let (open, close, kind) = if allows(schema, "array") && !allows(schema, "object") {
+        ('[', ']', "array")
+    } else {
+        ('{', '}', "object")
+    };
+    let (Some(start), Some(end)) = (trimmed.find(open), trimmed.rfind(close)) else {
+        bail!("No JSON {} found in response", kind);
+    };
+    if end <= start {
+        bail!("No JSON {} found in response", kind);
+    }
+    match serde_json::from_str(&trimmed[start..=end]) {
+        Ok(value) => Ok(value),
+        Err(e) => bail!("Response is not valid JSON: {}", e),
+    }
+}
+
+/// Check `value` against `schema`, keeping what matches
+///
+/// Returns the value without the entries that didn't match, or `None` when
+/// nothing usable is left, and a description of each problem.
+pub fn validate(value: Value, schema: &Value) -> (Option<Value>, Vec<String>) {
+    let mut issues = Vec::new();
+    let value = check(value, schema, "", &mut issues);
+    (value, issues)
+}
+
+fn check(value: Value, schema: &Value, path: &str, issues: &mut Vec<String>) -> Option<Value> {
+    let mut issue = |message: String| {
+        let at = if path.is_empty() { "response" } else { path };
+        issues.push(format!("{}: {}", at, message));
+    };
+
+    let kind = type_name(&value);
+    let type_matches = match &schema["type"] {
+        Value::Null => true,
+        _ => allows(schema, kind) || (kind == "integer" && allows(schema, "number")),
+    };
+    if !type_matches {
+        issue(format!("expected {}, got {}", expected(schema), kind));
+        return None;
+    }
+
+    if let Some(options) = schema["enum"].as_array()
+        && !options.contains(&value)
+    {
+        let options: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+        issue(format!(
+            "expected one of {}, got {}",
+            options.join(", "),
+            value
+        ));
+        return None;
+    }
+
+    match value {
+        Value::String(s) => {
+            let min = schema["minLength"].as_u64().unwrap_or(0) as usize;
+            if s.trim().chars().count() < min {
+                issue("must not be empty".to_string());
+                return None;
+            }
+            Some(Value::String(s))
+        }
+        Value::Array(items) => {
+            let item_schema = &schema["items"];
+            let kept: Vec<Value> = items
+                .into_iter()
+                .enumerate()
+                .filter_map(|(i, item)| {
+                    check(item, item_schema, &format!("{}[{}]", path, i), issues)
+                })
+                .collect();
+            let min = schema["minItems"].as_u64().unwrap_or(0) as usize;
+            if kept.len() < min {
+                let at = if path.is_empty() { "response" } else { path };
+                issues.push(format!("{}: needs at least {} valid item(s)", at, min));
+                return None;
+            }
+            Some(Value::Array(kept))
+        }
+        Value::Object(mut map) => {
+            let required: Vec<&str> = schema["required"]
+                .as_array()
+                .map(|r| r.iter().filter_map(Value::as_str).collect())
+                .unwrap_or_default();
+            let mut kept = Map::new();
+            let mut complete = true;
+
+            if let Some(properties) = schema["properties"].as_object() {
+                for (name, property) in properties {
+                    let is_required = required.contains(&name.as_str());
+                    let field_path = join(path, name);
+                    match map.remove(name) {
+                        Some(Value::Null) if !is_required => {
+                            kept.insert(name.clone(), Value::Null);
+                        }
+                        Some(field) => match check(field, property, &field_path, issues) {
+                            Some(field) => {
+                                kept.insert(name.clone(), field);
+                            }
+                            None => complete &= !is_required,
+                        },
+                        None if is_required => {
+                            issues.push(format!("{}: missing", field_path));
+                            complete = false;
+                        }
+                        None => {}
+                    }
+                }
+            }
+
+            // Keys beyond the declared properties, checked when the schema says how
+            let extra = &schema["additionalProperties"];
+            for (name, field) in map {
+                if extra.is_object() {
+                    if let Some(field) = check(field, extra, &join(path, &name), issues) {
+                        kept.insert(name, field);
+                    }
+                } else {
+                    kept.insert(name, field);
+                }
+            }
+
+            complete.then_some(Value::Object(kept))
+        }
+        value => Some(value),
+    }
+}
+
+/// Whether the schema's `type` names `kind`
+fn allows(schema: &Value, kind: &str) -> bool {
+    match &schema["type"] {
+        Value::String(t) => t == kind,
+        Value::Array(types) => types.iter().any(|t| t == kind),
+        _ => false,
+    }
+}
+
+/// The schema's `type`, as read in a message
+fn expected(schema: &Value) -> String {
+    match &schema["type"] {
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" or "),
+        t => t.as_str().unwrap_or("any").to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "array",
+            "minItems": 1,
+            "items": {
+                "type": "object",
+                "required": ["name", "tools"],
+                "properties": {
+                    "name": {"type": "string", "minLength": 1},
+                    "tools": {"type": "array", "items": {"type": "string"}},
+                    "kind": {"type": "string", "enum": ["essential", "recommended"]},
+                    "note": {"type": "string"}
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_validate_keeps_valid_entries() {
+        let response = json!([
+            {"name": "a", "tools": ["x", 3, "y"], "kind": "essential", "note": null},
+            {"name": "", "tools": []},
+            {"tools": ["z"]},
+            {"name": "b", "tools": [], "kind": "optional", "extra": true},
+            "not an object"
+        ]);
+        let (value, issues) = validate(response, &schema());
+
+        assert_eq!(
+            value.unwrap(),
+            json!([
+                {"name": "a", "tools": ["x", "y"], "kind": "essential", "note": null},
+                {"name": "b", "tools": [], "extra": true}
+            ])
+        );
+        assert_eq!(
+            issues,
+            [
+                "[0].tools[1]: expected string, got integer",
+                "[1].name: must not be empty",
+                "[2].name: missing",
+                "[3].kind: expected one of \"essential\", \"recommended\", got \"optional\"",
+                "[4]: expected object, got string",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_nothing_usable() {
+        let (value, issues) = validate(json!([{"name": 1, "tools": []}]), &schema());
+        assert!(value.is_none());
+        assert_eq!(
+            issues.last().unwrap(),
+            "response: needs at least 1 valid item(s)"
+        );
+
+        let (value, issues) = validate(json!({"name": "a"}), &schema());
+        assert!(value.is_none());
+        assert_eq!(issues, ["response: expected array, got object"]);
+
+        // Map-like objects check every value
+        let map = json!({"type": "object", "additionalProperties": {"type": "string"}});
+        let (value, issues) = validate(json!({"rg": "search", "fd": ["files"]}), &map);
+        assert_eq!(value.unwrap(), json!({"rg": "search"}));
+        assert_eq!(issues, ["fd: expected string, got array"]);
+    }
+
+    #[test]
+    fn test_find_json() {
+        let object = json!({"type": "object"});
+        let fenced = "Sure!\n```json\n{\"a\": [1, 2]}\n```\nDone.";
+        assert_eq!(find_json(fenced, &object).unwrap(), json!({"a": [1, 2]}));
+
+        let array = json!({"type": "array"});
+        assert_eq!(
+            find_json("Here: [{\"a\": 1}] ok", &array).unwrap(),
+            json!([{"a": 1}])
+        );
+        assert!(find_json("no json here", &object).is_err());
+        assert!(
+            find_json("{\"a\": }", &object)
+                .unwrap_err()
+                .to_string()
+                .starts_with("Response is not valid JSON")
+        );
+    }
+}
@@ -0,0 +1,623 @@
+//! Shell alias resolution for usage attribution
+//!
+//! Reads the user's bash/zsh aliases, fish abbreviations, aliases and wrapper
+//! functions, so `ll` (`alias ll='eza -l'`) or `g` (`abbr g git`) counts as the
+//! tool it runs. Only definitions written in config files are found: this never
+//! starts a shell to ask it.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+
+use crate::cmdline::invocations_with;
+
+/// Block keywords closed by `end` in fish
+const FISH_BLOCKS: &[&str] = &["function", "if", "for", "while", "switch", "begin"];
+
+/// `function` options taking a value
+const FISH_FUNCTION_OPTIONS_WITH_ARG: &[&str] = &[
+    "-d",
+    "--description",
+    "-e",
+    "--on-event",
+    "-v",
+    "--on-variable",
+    "-j",
+    "--on-job-exit",
+    "-p",
+    "--on-process-exit",
+    "-s",
+    "--on-signal",
+    "-V",
+    "--inherit-variable",
+];
+
+/// Alias names mapped to the command line they expand to
+#[derive(Debug, Clone, Default)]
+pub struct Aliases {
+    expansions: HashMap<String, String>,
+}
+
+impl Aliases {
+    /// Load aliases from the user's shell config files
+    pub fn load() -> Self {
+        let Some(home) = dirs::home_dir() else {
+            return Self::default();
+        };
+        Self::from_sources(&sources(&home))
+    }
+
+    /// Load aliases through a cache file, reparsing only when a config file changed
+    ///
+    /// The shell hook runs after every command, and reading every rc file and
+    /// fish function each time would cost more than the rest of the hook. With
+    /// the cache it only checks modification times.
+    pub fn load_cached(cache: &Path) -> Self {
+        let Some(home) = dirs::home_dir() else {
+            return Self::default();
+        };
+        Self::load_cached_from(&home, cache)
+    }
+
+    fn load_cached_from(home: &Path, cache: &Path) -> Self {
+        let sources = sources(home);
+        let stamps: Vec<(PathBuf, Option<SystemTime>)> = sources
+            .iter()
+            .map(|(path, _)| {
+                (
+                    path.clone(),
+                    fs::metadata(path).and_then(|m| m.modified()).ok(),
+                )
+            })
+            .collect();
+
+        if let Ok(content) = fs::read(cache)
+            && let Ok(cached) = serde_json::from_slice::<AliasCache>(&content)
+            && cached.stamps == stamps
+        {
+            return Self {
+                expansions: cached.expansions,
+            };
+        }
+
+        let aliases = Self::from_sources(&sources);
+        let cached = AliasCache {
+            stamps,
+            expansions: aliases.expansions.clone(),
+        };
+        // Written aside and renamed, so a concurrent hook never reads half a file
+        if let Ok(json) = serde_json::to_vec(&cached) {
+            let tmp = cache.with_extension(format!("tmp-{}", std::process::id()));
+            if fs::write(&tmp, json).is_ok() && fs::rename(&tmp, cache).is_err() {
+                fs::remove_file(&tmp).ok();
+            }
+        }
+        aliases
+    }
+
+    fn from_sources(sources: &[(PathBuf, Syntax)]) -> Self {
+        let mut aliases = Self::default();
+        for (path, syntax) in sources {
+            let Ok(content) = fs::read_to_string(path) else {
+                continue;
+            };
+            match syntax {
+                Syntax::Posix => aliases.parse_posix(&content),
+                Syntax::FishVariables => aliases.parse_fish_variables(&content),
+                Syntax::Fish => aliases.parse_fish(&content),
+            }
+        }
+        aliases
+    }
+
+    /// The command line an alias expands to
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.expansions.get(name).map(String::as_str)
+    }
+
+    /// The program an alias ends up running (`bat` for `alias cat='bat --paging=never'`)
+    pub fn resolve<'a>(&'a self, name: &'a str) -> Option<&'a str> {
+        self.get(name)?;
+        invocations_with(name, self).first().map(|i| i.program)
+    }
+
+    /// Define an alias, replacing any earlier definition
+    pub fn insert(&mut self, name: impl Into<String>, expansion: impl Into<String>) {
+        let (name, expansion) = (name.into(), expansion.into());
+        if !name.is_empty() && !expansion.trim().is_empty() {
+            self.expansions.insert(name, expansion);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.expansions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.expansions.is_empty()
+    }
+
+    /// bash/zsh: `alias ll='ls -l' la='ls -a'`
+    fn parse_posix(&mut self, content: &str) {
+        for words in content.lines().flat_map(statements) {
+            let Some(("alias", args)) = words.split_first().map(|(w, rest)| (w.as_str(), rest))
+            else {
+                continue;
+            };
+            // zsh suffix (`-s`) and global (`-g`) aliases don't name commands
+            if args.iter().any(|a| a == "-s" || a == "-g") {
+                continue;
+            }
+            for arg in args.iter().filter(|a| !a.starts_with('-')) {
+                if let Some((name, expansion)) = arg.split_once('=') {
+                    self.insert(name, expansion);
+                }
+            }
+        }
+    }
+
+    /// fish: `alias`, `abbr` and functions wrapping a single command
+    fn parse_fish(&mut self, content: &str) {
+        let mut statements = content.lines().flat_map(statements);
+        while let Some(words) = statements.next() {
+            let Some((first, args)) = words.split_first() else {
+                continue;
+            };
+            match first.as_str() {
+                "alias" => self.parse_fish_alias(args),
+                "abbr" => self.parse_fish_abbr(args),
+                "function" => {
+                    let mut body = Vec::new();
+                    let mut depth = 1;
+                    for words in statements.by_ref() {
+                        match words.first().map(String::as_str) {
+                            Some("end") => depth -= 1,
+                            Some(word) if FISH_BLOCKS.contains(&word) => depth += 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                        body.push(words);
+                    }
+                    self.parse_fish_function(args, &body);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// `alias ll 'eza -l'` or `alias ll='eza -l'`
+    fn parse_fish_alias(&mut self, args: &[String]) {
+        let args: Vec<&String> = args.iter().filter(|a| !a.starts_with('-')).collect();
+        match args.as_slice() {
+            [definition] => {
+                if let Some((name, expansion)) = definition.split_once('=') {
+                    self.insert(name, expansion);
+                }
+            }
+            [name, rest @ ..] => self.insert(name.as_str(), join(rest)),
+            [] => {}
+        }
+    }
+
+    /// `abbr -a gco git checkout`; abbreviations that aren't plain command
+    /// replacements (regexes, functions, anywhere, erase/list) are skipped
+    fn parse_fish_abbr(&mut self, args: &[String]) {
+        let mut words = Vec::new();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-e" | "--erase" | "-l" | "--list" | "-s" | "--show" | "-q" | "--query" | "-r"
+                | "--rename" | "--regex" | "-f" | "--function" | "-c" | "--command" => {
+                    return;
+                }
+                "-p" | "--position" => {
+                    if iter.next().is_some_and(|p| p == "anywhere") {
+                        return;
+                    }
+                }
+                "--position=anywhere" => return,
+                _ if arg.starts_with('-') && words.is_empty() => {}
+                _ => words.push(arg),
+            }
+        }
+
+        if let [name, rest @ ..] = words.as_slice() {
+            self.insert(name.as_str(), join(rest));
+        }
+    }
+
+    /// `function ll --wraps 'eza -l'`, or a function whose body is one command
+    fn parse_fish_function(&mut self, args: &[String], body: &[Vec<String>]) {
+        let mut name = None;
+        let mut wraps = None;
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "-w" | "--wraps" => wraps = iter.next().cloned(),
+                _ if arg.starts_with("--wraps=") => {
+                    wraps = arg.strip_prefix("--wraps=").map(str::to_string);
+                }
+                _ if FISH_FUNCTION_OPTIONS_WITH_ARG.contains(&arg.as_str()) => {
+                    iter.next();
+                }
+                _ if arg.starts_with('-') => {}
+                _ if name.is_none() => name = Some(arg),
+                _ => {}
+            }
+        }
+
+        let Some(name) = name else {
+            return;
+        };
+        let expansion = wraps.or_else(|| match body {
+            [command] => Some(quote_words(command)),
+            _ => None,
+        });
+        if let Some(expansion) = expansion {
+            self.insert(name.as_str(), expansion);
+        }
+    }
+
+    /// `SETUVAR _fish_abbr_gco:git\x20checkout` lines from `fish_variables`
+    fn parse_fish_variables(&mut self, content: &str) {
+        for line in content.lines() {
+            let Some(rest) = line.strip_prefix("SETUVAR ") else {
+                continue;
+            };
+            let rest = rest.strip_prefix("--export ").unwrap_or(rest);
+            if let Some((name, value)) = rest
+                .strip_prefix("_fish_abbr_")
+                .and_then(|var| var.split_once(':'))
+            {
+                self.insert(unescape_fish_name(name), unescape_fish_value(value));
+            }
+        }
+    }
+}
+
+/// How a config file defines aliases
+#[derive(Debug, Clone, Copy)]
+enum Syntax {
+    Posix,
+    FishVariables,
+    Fish,
+}
+
+/// Parsed aliases with the modification times of the files they came from
+#[derive(Serialize, Deserialize)]
+struct AliasCache {
+    stamps: Vec<(PathBuf, Option<SystemTime>)>,
+    expansions: HashMap<String, String>,
+}
+
+/// Config files that may define aliases, in the order they're read
+fn sources(home: &Path) -> Vec<(PathBuf, Syntax)> {
+    let zdotdir = std::env::var_os("ZDOTDIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| home.to_path_buf());
+    let mut sources: Vec<(PathBuf, Syntax)> = [
+        home.join(".bashrc"),
+        home.join(".bash_aliases"),
+        home.join(".aliases"),
+        zdotdir.join(".zshrc"),
+        zdotdir.join(".zsh_aliases"),
+    ]
+    .into_iter()
+    .map(|path| (path, Syntax::Posix))
+    .collect();
+
+    let fish = home.join(".config/fish");
+    // Abbreviations added interactively before fish 3.6 live in universal variables
+    sources.push((fish.join("fish_variables"), Syntax::FishVariables));
+    sources.extend(
+        [fish.join("config.fish"), fish.join("aliases.fish")]
+            .into_iter()
+            .chain(fish_scripts(&fish.join("conf.d")))
+            .chain(fish_scripts(&fish.join("functions")))
+            .map(|path| (path, Syntax::Fish)),
+    );
+    sources
+}
+
+/// `*.fish` files in a directory
+fn fish_scripts(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut scripts: Vec<PathBuf> = read_dir
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "fish"))
+        .collect();
+    scripts.sort();
+    scripts
+}
+
+/// Split one line of shell script into its statements, as unquoted words
+///
+/// Statements end at `;`, `&` and `|`; an unquoted `#` starts a comment.
+fn statements(line: &str) -> Vec<Vec<String>> {
+    let mut statements = Vec::new();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                for c in chars.by_ref() {
+                    if c == '\'' {
+                        break;
+                    }
+                    word.push(c);
+                }
+            }
+            '"' => {
+                in_word = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if chars.peek().is_some_and(|n| matches!(n, '"' | '\\' | '$')) => {
+                            word.extend(chars.next());
+                        }
+                        _ => word.push(c),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                word.extend(chars.next());
+            }
+            '#' if !in_word => break,
+            ';' | '&' | '|' | ' ' | '\t' => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+                if c != ' ' && c != '\t' && !words.is_empty() {
+                    statements.push(std::mem::take(&mut words));
+                }
+            }
+            _ => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+
+    if in_word {
+        words.push(word);
+    }
+    if !words.is_empty() {
+        statements.push(words);
+    }
+    statements
+}
+
+/// Arguments of `alias`/`abbr`, which the shell joins into one command line
+fn join(words: &[&String]) -> String {
+    words
+        .iter()
+        .map(|w| w.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Rebuild a command line from unquoted words, quoting where needed
+fn quote_words(words: &[String]) -> String {
+    words
+        .iter()
+        .map(|w| {
+            if w.is_empty() || w.contains(|c: char| c.is_whitespace() || "'\"\\;&|#".contains(c)) {
+                format!("'{}'", w.replace('\'', "'\\''"))
+            } else {
+                w.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Variable names in `fish_variables` escape other characters as `_XX_` (hex)
+fn unescape_fish_name(name: &str) -> String {
+    let mut out = Vec::new();
+    let bytes = name.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'_'
+            && bytes.get(i + 3) == Some(&b'_')
+            && let Some(byte) = name
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            out.push(byte);
+            i += 4;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// Values in `fish_variables` use backslash escapes (`\x20` for a space)
+fn unescape_fish_value(value: &str) -> String {
+    let mut out = Vec::new();
+    let bytes = value.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'\\' {
+            out.push(bytes[i]);
+            i += 1;
+            continue;
+        }
+        match bytes.get(i + 1) {
+            Some(b'x') => {
+                match value
+                    .get(i + 2..i + 4)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(byte) => {
+                        out.push(byte);
+                        i += 4;
+                    }
+                    None => {
+                        out.push(b'\\');
+                        i += 1;
+                    }
+                }
+            }
+            Some(&escaped) => {
+                out.push(escaped);
+                i += 2;
+            }
+            None => {
+                out.push(b'\\');
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_posix() {
+        let mut aliases = Aliases::default();
+        aliases.parse_posix(
+            r#"
+# alias commented='out'
+alias ll='eza -l' la="eza -la"
+alias gco='git checkout'; alias k=kubectl  # trailing comment
+alias -g G='| grep'
+alias it='echo it'\''s'
+export PATH=$PATH:~/bin
+"#,
+        );
+
+        assert_eq!(aliases.get("ll"), Some("eza -l"));
+        assert_eq!(aliases.get("la"), Some("eza -la"));
+        assert_eq!(aliases.get("gco"), Some("git checkout"));
+        assert_eq!(aliases.get("k"), Some("kubectl"));
+        assert_eq!(aliases.get("it"), Some("echo it's"));
+        assert_eq!(aliases.get("G"), None);
+        assert_eq!(aliases.get("commented"), None);
+        assert_eq!(aliases.len(), 5);
+    }
+
+    #[test]
+    fn test_parse_fish() {
+        let mut aliases = Aliases::default();
+        aliases.parse_fish(
+            r#"
+if status is-interactive
+    abbr -a g git
+    abbr --add --global gco git checkout
+    abbr -a --position anywhere L '| less'
+    abbr -a --regex '^\d+$' numbers --function handler
+end
+alias cat 'bat --paging=never'
+alias ls='eza'
+
+function ll --description 'long list'
+    eza -l $argv
+end
+
+function k --wraps kubectl
+    set -l ctx (kubectl config current-context)
+    kubectl $argv
+end
+
+function deploy
+    cargo build --release
+    scp target/release/app server:
+end
+
+function serve; python3 -m http.server $argv; end
+"#,
+        );
+
+        assert_eq!(aliases.get("g"), Some("git"));
+        assert_eq!(aliases.get("gco"), Some("git checkout"));
+        assert_eq!(aliases.get("cat"), Some("bat --paging=never"));
+        assert_eq!(aliases.get("ls"), Some("eza"));
+        assert_eq!(aliases.get("ll"), Some("eza -l $argv"));
+        assert_eq!(aliases.get("k"), Some("kubectl"));
+        assert_eq!(aliases.get("serve"), Some("python3 -m http.server $argv"));
+        // Several commands, an abbreviation expanding anywhere, a regex abbreviation
+        assert_eq!(aliases.get("deploy"), None);
+        assert_eq!(aliases.get("L"), None);
+        assert_eq!(aliases.get("numbers"), None);
+    }
+
+    #[test]
+    fn test_parse_fish_variables() {
+        let mut aliases = Aliases::default();
+        aliases.parse_fish_variables(
+            "# This file contains fish universal variable definitions.\n\
+             SETUVAR __fish_initialized:3400\n\
+             SETUVAR _fish_abbr_gco:git\\x20checkout\n\
+             SETUVAR _fish_abbr_git_2D_up:git\\x20pull\\x20\\x2d\\x2drebase\n",
+        );
+
+        assert_eq!(aliases.get("gco"), Some("git checkout"));
+        assert_eq!(aliases.get("git-up"), Some("git pull --rebase"));
+        assert_eq!(aliases.len(), 2);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let mut aliases = Aliases::default();
+        aliases.insert("cat", "bat --paging=never");
+        aliases.insert("l", "ll -a");
+        aliases.insert("ll", "eza -l");
+        aliases.insert("rg", "rg --smart-case");
+
+        assert_eq!(aliases.resolve("cat"), Some("bat"));
+        assert_eq!(aliases.resolve("l"), Some("eza"));
+        // An alias isn't expanded inside its own expansion
+        assert_eq!(aliases.resolve("rg"), Some("rg"));
+        assert_eq!(aliases.resolve("fd"), None);
+    }
+
+    #[test]
+    fn test_load_cached() {
+        let home = tempfile::tempdir().unwrap();
+        let cache = home.path().join("aliases.cache");
+        let bashrc = home.path().join(".bashrc");
+        fs::write(&bashrc, "alias ll='eza -l'\n").unwrap();
+
+        let aliases = Aliases::load_cached_from(home.path(), &cache);
+        assert_eq!(aliases.get("ll"), Some("eza -l"));
+        assert!(cache.exists());
+
+        // Served from the cache while the files are unchanged
+        let mut cached: AliasCache = serde_json::from_slice(&fs::read(&cache).unwrap()).unwrap();
+        cached
+            .expansions
+            .insert("k".to_string(), "kubectl".to_string());
+        fs::write(&cache, serde_json::to_vec(&cached).unwrap()).unwrap();
+        assert_eq!(
+            Aliases::load_cached_from(home.path(), &cache).get("k"),
+            Some("kubectl")
+        );
+
+        // A new fish function is picked up
+        let functions = home.path().join(".config/fish/functions");
+        fs::create_dir_all(&functions).unwrap();
+        fs::write(functions.join("g.fish"), "function g; git $argv; end\n").unwrap();
+        let aliases = Aliases::load_cached_from(home.path(), &cache);
+        assert_eq!(aliases.get("g"), Some("git $argv"));
+        assert_eq!(aliases.get("k"), None);
+    }
+}
@@ -174,14 +174,19 @@ pub enum Commands {
     ///
     /// Helps clean up your system:
     /// 1. Show unused installed tools
-    /// 2. Show orphaned database entries
-    /// 3. Fix health issues
-    /// 4. Optionally remove unused tools
+    /// 2. Remove orphaned database entries
+    /// 3. Report reclaimable package manager caches (cargo registry, pip/npm
+    ///    caches, brew downloads, apt autoremove, unused flatpak runtimes)
+    /// 4. Fix health issues
     Cleanup {
-        /// Skip confirmations
+        /// Skip confirmations for database changes
         #[arg(short, long)]
         force: bool,
 
+        /// Offer to purge package manager caches (asks for each, even with --force)
+        #[arg(long)]
+        purge_caches: bool,
+
         /// Only show what would be done
         #[arg(short, long)]
         dry_run: bool,
@@ -266,6 +271,10 @@ pub enum Commands {
         /// Show all available newer versions (not just latest)
         #[arg(short = 'a', long)]
         all_versions: bool,
+
+        /// Show release notes between the installed and latest version
+        #[arg(short, long)]
+        notes: bool,
     },
 
     // ============================================
@@ -488,6 +497,10 @@ pub enum DiscoverCommands {
     },
 
     /// Show trending tools by GitHub stars
+    #[command(after_help = "Examples:
+  hoards discover trending                    # Most starred
+  hoards discover trending --by velocity      # Most stars gained in 30 days
+  hoards discover trending --by velocity --days 7")]
     Trending {
         /// Filter by category
         #[arg(short, long)]
@@ -496,6 +509,14 @@ pub enum DiscoverCommands {
         /// Number of tools to show
         #[arg(short, long, default_value = "20")]
         limit: usize,
+
+        /// Rank by total stars or by stars gained (recorded on each `sync --github`)
+        #[arg(long, default_value = "stars", value_parser = ["stars", "velocity"])]
+        by: String,
+
+        /// Window for velocity ranking, in days
+        #[arg(long, default_value = "30")]
+        days: u32,
     },
 }
 
@@ -514,10 +535,33 @@ pub enum InsightsCommands {
         /// Number of top tools to show
         #[arg(short, long, default_value = "20")]
         limit: usize,
+
+        /// Break a tool's usage down by subcommand (see `usage config --subcommands`)
+        #[arg(long, requires = "tool")]
+        subcommands: bool,
+
+        /// Show which tools each git repository relies on (hook mode only)
+        #[arg(long, conflicts_with_all = ["tool", "subcommands"])]
+        by_project: bool,
+
+        /// Merge in usage imported from other machines (see `usage import`)
+        #[arg(long, conflicts_with_all = ["subcommands", "by_project"])]
+        all_hosts: bool,
+    },
+
+    /// Show tools used in a project (git repository or directory)
+    Project {
+        /// Project directory (defaults to the current directory)
+        #[arg(default_value = ".")]
+        path: std::path::PathBuf,
     },
 
     /// Find installed tools you never use
-    Unused,
+    Unused {
+        /// Only list tools unused on every machine (see `usage import`)
+        #[arg(long)]
+        all_hosts: bool,
+    },
 
     /// Check database health and find issues
     Health {
@@ -531,6 +575,17 @@ pub enum InsightsCommands {
 
     /// Show combined overview dashboard
     Overview,
+
+    /// Show disk usage per tool and per source
+    Disk {
+        /// Number of largest tools to show
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+
+        /// Only show tools with no recorded usage
+        #[arg(short, long)]
+        unused: bool,
+    },
 }
 
 // ============================================
@@ -576,12 +631,12 @@ pub enum AiCommands {
         count: usize,
     },
 
-    /// Extract tool info from GitHub repository README
+    /// Extract tool info from a repository README (GitHub, GitLab, Gitea/Forgejo)
     ///
     /// Uses AI to parse README and extract tool metadata (name, binary, source, description).
     /// Results are cached per repository version to avoid repeated API calls.
     Extract {
-        /// GitHub repository URLs (e.g., https://github.com/BurntSushi/ripgrep)
+        /// Repository URLs (e.g., https://github.com/BurntSushi/ripgrep, https://codeberg.org/owner/repo)
         #[arg(required = true)]
         urls: Vec<String>,
 
@@ -733,8 +788,20 @@ pub enum AiCommands {
 pub enum AiConfigCommands {
     /// Set the AI provider to use
     Set {
-        /// AI provider (claude, gemini, codex, opencode)
+        /// AI provider: a CLI (claude, gemini, codex, opencode) or an API (anthropic, openai)
         provider: String,
+
+        /// Model to request (API providers)
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// API base URL, for a proxy or compatible server (API providers)
+        #[arg(short, long)]
+        base_url: Option<String>,
+
+        /// Environment variable holding the API key (API providers)
+        #[arg(short = 'k', long)]
+        api_key_env: Option<String>,
     },
 
     /// Show current AI configuration
@@ -783,6 +850,39 @@ pub enum GhCommands {
         name: String,
     },
 
+    /// Pin a tool to a repository (sync and fetch won't search for it)
+    #[command(after_help = "Examples:
+  hoards gh link rg BurntSushi/ripgrep
+  hoards gh link bat https://github.com/sharkdp/bat
+  hoards gh link fdroidcl https://gitlab.com/fdroid/fdroidcl")]
+    Link {
+        /// Tool name
+        name: String,
+
+        /// Repository as owner/repo (GitHub) or a GitHub, GitLab or Gitea/Forgejo URL
+        repo: String,
+    },
+
+    /// Mark a tool as not on any forge, dropping the info matched for it
+    Unlink {
+        /// Tool name
+        name: String,
+    },
+
+    /// Add starred repositories that look like CLI tools as not-installed tools
+    #[command(after_help = "Examples:
+  hoards gh import-stars --dry-run
+  hoards gh import-stars --user BurntSushi")]
+    ImportStars {
+        /// Import this user's public stars instead of your own
+        #[arg(short, long)]
+        user: Option<String>,
+
+        /// Only show what would be imported (dry run)
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+
     /// Search GitHub for a tool
     Search {
         /// Search query
@@ -808,12 +908,14 @@ pub enum GhCommands {
 #[non_exhaustive]
 pub enum UsageCommands {
     /// Scan shell history and update usage counts
+    ///
+    /// Only entries added since the previous scan are counted.
     Scan {
         /// Only show what would be recorded (dry run)
         #[arg(short, long)]
         dry_run: bool,
 
-        /// Reset usage counts before scanning
+        /// Reset usage counts and re-import all history
         #[arg(long)]
         reset: bool,
     },
@@ -835,12 +937,24 @@ pub enum UsageCommands {
     Log {
         /// Command that was executed
         command: String,
+
+        /// Directory the command ran in (defaults to the current directory)
+        #[arg(long)]
+        cwd: Option<std::path::PathBuf>,
+
+        /// Exit status of the command
+        #[arg(long, allow_negative_numbers = true)]
+        status: Option<i32>,
+
+        /// Wall-clock duration of the command in milliseconds
+        #[arg(long)]
+        duration_ms: Option<u64>,
     },
 
     /// Show shell hook setup instructions
     Init {
         /// Shell type (auto-detected if omitted)
-        #[arg(value_parser = ["fish", "bash", "zsh"])]
+        #[arg(value_parser = ["fish", "bash", "zsh", "nushell", "elvish", "xonsh", "powershell"])]
         shell: Option<String>,
     },
 
@@ -849,6 +963,22 @@ pub enum UsageCommands {
         /// Set tracking mode
         #[arg(long, value_parser = ["scan", "hook"])]
         mode: Option<String>,
+
+        /// Set where scan mode reads history from
+        #[arg(long, value_parser = ["shell", "atuin", "mcfly"])]
+        history: Option<String>,
+
+        /// Track subcommands for these tools (comma-separated, e.g. git,kubectl; "none" to stop)
+        #[arg(long, value_delimiter = ',')]
+        subcommands: Option<Vec<String>>,
+
+        /// Never record these commands (comma-separated globs, e.g. pass,vault*; "none" to clear)
+        #[arg(long, value_delimiter = ',')]
+        ignore_commands: Option<Vec<String>>,
+
+        /// Never record commands run in these directories (comma-separated globs; "none" to clear)
+        #[arg(long, value_delimiter = ',')]
+        ignore_dirs: Option<Vec<String>>,
     },
 
     /// Reset all usage counters to zero
@@ -857,6 +987,52 @@ pub enum UsageCommands {
         #[arg(short, long)]
         force: bool,
     },
+
+    /// Write this machine's usage counts to a snapshot for other machines
+    Export {
+        /// Output file (prints to stdout if omitted)
+        #[arg(short, long)]
+        output: Option<std::path::PathBuf>,
+
+        /// Host name to tag the snapshot with (defaults to this machine's)
+        #[arg(long)]
+        host: Option<String>,
+    },
+
+    /// Import a usage snapshot exported on another machine
+    ///
+    /// Importing a newer snapshot from the same host replaces the previous one.
+    Import {
+        /// Snapshot file written by `usage export` ("-" for stdin)
+        file: std::path::PathBuf,
+
+        /// Replace the host's snapshot even if this one is older
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// List machines whose usage was imported
+    Hosts {
+        /// Forget the usage imported from a machine
+        #[arg(long)]
+        remove: Option<String>,
+    },
+
+    /// Forget recorded usage of a tool, or since a date
+    #[command(arg_required_else_help = true)]
+    Purge {
+        /// Only forget this tool
+        #[arg(long)]
+        tool: Option<String>,
+
+        /// Only forget usage from this day on (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Skip confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -0,0 +1,754 @@
+//! Shell command line tokenizing for usage tracking
+//!
+//! Splits a command line into the simple commands it runs (pipelines, `&&`,
+//! `||`, `;`, subshells and `$(...)` substitutions) and unwraps wrappers such as
+//! `sudo`, `env`, `timeout` or `xargs` to find every program actually invoked.
+//! This is a heuristic tokenizer, not a shell parser: it only needs to find
+//! command names, so anything it can't resolve (`$EDITOR`, functions) is skipped.
+//! Given the user's [`Aliases`], alias names are expanded like the shell would.
+
+use crate::aliases::Aliases;
+
+/// Shell builtins and common non-tools that never count as tool usage
+const SKIP: &[&str] = &[
+    "cd", "ls", "echo", "export", "set", "unset", "alias", "source", "if", "then", "else", "fi",
+    "for", "do", "done", "while", "case", "esac", "function", "return", "exit", "true", "false",
+    "test", "[", "[[", "pwd", "pushd", "popd", "dirs", "history", "clear", "end", "in", "local",
+    "read", "eval", ".",
+];
+
+/// Keywords that can precede a command within the same simple command
+const PREFIX_KEYWORDS: &[&str] = &[
+    "if", "then", "else", "elif", "do", "while", "until", "!", "{", "}", "and", "or", "not",
+    "begin",
+];
+
+/// A program that runs another program given as its arguments
+struct Wrapper {
+    name: &'static str,
+    /// Options that consume the following word
+    options_with_arg: &'static [&'static str],
+    /// Positional arguments between the options and the wrapped command
+    positional: usize,
+}
+
+const WRAPPERS: &[Wrapper] = &[
+    Wrapper {
+        name: "sudo",
+        options_with_arg: &["-u", "-g", "-p", "-C", "-D", "-h", "-r", "-t", "-U", "-T"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "doas",
+        options_with_arg: &["-u", "-C"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "env",
+        options_with_arg: &["-u", "-C", "--unset", "--chdir"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "nice",
+        options_with_arg: &["-n", "--adjustment"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "ionice",
+        options_with_arg: &["-c", "-n", "-p", "-P", "-u"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "stdbuf",
+        options_with_arg: &["-i", "-o", "-e"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "timeout",
+        options_with_arg: &["-s", "-k", "--signal", "--kill-after"],
+        positional: 1, // DURATION
+    },
+    Wrapper {
+        name: "xargs",
+        options_with_arg: &["-I", "-n", "-P", "-L", "-d", "-E", "-s", "-a"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "watch",
+        options_with_arg: &["-n", "--interval"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "time",
+        options_with_arg: &["-f", "-o", "--format", "--output"],
+        positional: 0,
+    },
+    Wrapper {
+        name: "nohup",
+        options_with_arg: &[],
+        positional: 0,
+    },
+    Wrapper {
+        name: "command",
+        options_with_arg: &[],
+        positional: 0,
+    },
+    Wrapper {
+        name: "builtin",
+        options_with_arg: &[],
+        positional: 0,
+    },
+    Wrapper {
+        name: "exec",
+        options_with_arg: &["-a"],
+        positional: 0,
+    },
+];
+
+/// Global options of multiplexer tools that take a separate argument, so the
+/// argument isn't mistaken for the subcommand (`git -C repo log`)
+const GLOBAL_OPTIONS_WITH_ARG: &[(&str, &[&str])] = &[
+    (
+        "git",
+        &["-C", "-c", "--git-dir", "--work-tree", "--namespace"],
+    ),
+    (
+        "docker",
+        &[
+            "-H",
+            "--host",
+            "-c",
+            "--context",
+            "--config",
+            "-l",
+            "--log-level",
+        ],
+    ),
+    ("podman", &["-c", "--connection", "--url", "--root"]),
+    (
+        "kubectl",
+        &[
+            "-n",
+            "--namespace",
+            "--context",
+            "--kubeconfig",
+            "--cluster",
+            "-s",
+            "--server",
+        ],
+    ),
+    (
+        "helm",
+        &["-n", "--namespace", "--kube-context", "--kubeconfig"],
+    ),
+    ("cargo", &["-Z", "-C", "--config"]),
+    ("gh", &["-R", "--repo"]),
+];
+
+/// Nesting limit for substitutions, so pathological input can't recurse forever
+const MAX_DEPTH: usize = 8;
+
+/// A program run by a command line, with its subcommand if it has one
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Invocation<'a> {
+    /// Program name without path prefix
+    pub program: &'a str,
+    /// First non-option argument when it looks like a subcommand (`rebase` in `git rebase -i`)
+    pub subcommand: Option<&'a str>,
+}
+
+/// Every program a command line invokes, in order, without path prefixes
+///
+/// `rg foo | fzf | xargs bat` gives `["rg", "fzf", "bat"]`, and
+/// `cd x && sudo -E make install` gives `["make"]`.
+pub fn invoked_commands(line: &str) -> Vec<&str> {
+    invocations(line).into_iter().map(|i| i.program).collect()
+}
+
+/// Like [`invoked_commands`], keeping each program's subcommand
+pub fn invocations(line: &str) -> Vec<Invocation<'_>> {
+    let mut walker = Walker::new(None);
+    walker.collect(line, 0);
+    walker.found
+}
+
+/// Like [`invocations`], expanding shell aliases, abbreviations and wrapper functions
+///
+/// With `alias gco='git checkout'`, `gco main` gives `git` with subcommand `checkout`.
+pub fn invocations_with<'a>(line: &'a str, aliases: &'a Aliases) -> Vec<Invocation<'a>> {
+    let mut walker = Walker::new(Some(aliases));
+    walker.collect(line, 0);
+    walker.found
+}
+
+/// Walks a command line, collecting the programs it runs
+struct Walker<'a> {
+    aliases: Option<&'a Aliases>,
+    /// Aliases being expanded; like shells, an alias is never expanded inside
+    /// its own expansion (`alias ls='ls --color'`)
+    expanding: Vec<&'a str>,
+    found: Vec<Invocation<'a>>,
+}
+
+impl<'a> Walker<'a> {
+    fn new(aliases: Option<&'a Aliases>) -> Self {
+        Self {
+            aliases,
+            expanding: Vec::new(),
+            found: Vec::new(),
+        }
+    }
+
+    fn collect(&mut self, line: &'a str, depth: usize) {
+        if depth > MAX_DEPTH {
+            return;
+        }
+
+        let (segments, nested) = split(line);
+        for words in &segments {
+            self.segment_command(words, depth);
+        }
+        for inner in nested {
+            self.collect(inner, depth + 1);
+        }
+    }
+
+    /// Replace an alias with its expansion, keeping the arguments it was given
+    fn expand(&mut self, name: &'a str, expansion: &'a str, args: &[&'a str], depth: usize) {
+        if depth >= MAX_DEPTH {
+            return;
+        }
+
+        let (mut segments, nested) = split(expansion);
+        // The arguments extend the last command of the expansion
+        if let Some(last) = segments.last_mut() {
+            last.extend_from_slice(args);
+        }
+
+        self.expanding.push(name);
+        for words in &segments {
+            self.segment_command(words, depth + 1);
+        }
+        for inner in nested {
+            self.collect(inner, depth + 1);
+        }
+        self.expanding.pop();
+    }
+
+    /// Find the program a simple command runs, unwrapping keywords and wrappers
+    fn segment_command(&mut self, words: &[&'a str], depth: usize) {
+        let mut i = 0;
+        // Shells only expand aliases in command position, not after `sudo` and co.
+        let mut wrapped = false;
+
+        while i < words.len() {
+            let word = unquote(words[i]);
+
+            if is_redirect_operator(word) {
+                i += 2;
+                continue;
+            }
+            // `name() { ... }` defines a function; its body may still run tools
+            if is_assignment(word) || is_redirect_with_target(word) || word.ends_with("()") {
+                i += 1;
+                continue;
+            }
+            if PREFIX_KEYWORDS.contains(&word) {
+                i += 1;
+                continue;
+            }
+
+            if let Some(wrapper) = WRAPPERS.iter().find(|w| w.name == word) {
+                // `command -v foo` looks a program up instead of running it
+                if word == "command" && matches!(words.get(i + 1), Some(&"-v" | &"-V")) {
+                    return;
+                }
+
+                i += 1;
+                while let Some(&arg) = words.get(i) {
+                    if arg == "--" {
+                        i += 1;
+                        break;
+                    }
+                    if arg.starts_with('-') && arg.len() > 1 {
+                        i += if wrapper.options_with_arg.contains(&arg) {
+                            2
+                        } else {
+                            1
+                        };
+                    } else if is_assignment(arg) {
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                i += wrapper.positional;
+                wrapped = true;
+
+                // `watch 'kubectl get pods'` hands a whole command line to sh -c
+                if word == "watch"
+                    && let [single] = &words[i.min(words.len())..]
+                    && unquote(single) != *single
+                {
+                    self.collect(unquote(single), depth + 1);
+                    return;
+                }
+                continue;
+            }
+
+            // Only unquoted words are aliases: `'ll'` runs a program called ll
+            if !wrapped
+                && word == words[i]
+                && !self.expanding.contains(&word)
+                && let Some(expansion) = self.aliases.and_then(|aliases| aliases.get(word))
+            {
+                self.expand(word, expansion, &words[i + 1..], depth);
+                return;
+            }
+
+            // Variables, functions and substitutions can't be resolved to a program
+            if word.starts_with('$') || word.contains(['(', '`', '=']) {
+                return;
+            }
+
+            // Remove path prefix (e.g., /usr/bin/git -> git)
+            let name = word.rsplit('/').next().unwrap_or(word);
+            if !name.is_empty() && !SKIP.contains(&name) {
+                self.found.push(Invocation {
+                    program: name,
+                    subcommand: subcommand(name, &words[i + 1..]),
+                });
+            }
+            return;
+        }
+    }
+}
+
+/// Split a line into simple commands (as word lists) plus the contents of
+/// any subshells and command substitutions, which are tokenized separately
+fn split(line: &str) -> (Vec<Vec<&str>>, Vec<&str>) {
+    let bytes = line.as_bytes();
+    let mut segments: Vec<Vec<&str>> = Vec::new();
+    let mut nested: Vec<&str> = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    let mut word_start: Option<usize> = None;
+    let mut i = 0;
+
+    macro_rules! end_word {
+        () => {
+            if let Some(start) = word_start.take()
+                && start < i
+            {
+                words.push(&line[start..i]);
+            }
+        };
+    }
+    macro_rules! end_segment {
+        () => {
+            end_word!();
+            if !words.is_empty() {
+                segments.push(std::mem::take(&mut words));
+            }
+        };
+    }
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                word_start.get_or_insert(i);
+                // Skip the escaped character whole, so slices stay on char boundaries
+                i += 1 + line[i + 1..].chars().next().map_or(0, char::len_utf8);
+            }
+            b'\'' => {
+                word_start.get_or_insert(i);
+                i = find_byte(bytes, i + 1, b'\'').map_or(bytes.len(), |end| end + 1);
+            }
+            b'"' => {
+                word_start.get_or_insert(i);
+                let end = closing_double_quote(bytes, i + 1);
+                // Substitutions still run inside double quotes
+                collect_substitutions(line, i + 1, end, &mut nested);
+                i = (end + 1).min(bytes.len());
+            }
+            b'`' => {
+                word_start.get_or_insert(i);
+                let end = find_byte(bytes, i + 1, b'`').unwrap_or(bytes.len());
+                nested.push(&line[i + 1..end]);
+                i = (end + 1).min(bytes.len());
+            }
+            b'$' if bytes.get(i + 1) == Some(&b'(') => {
+                word_start.get_or_insert(i);
+                let end = matching_paren(bytes, i + 1);
+                // $((...)) is arithmetic, not a command
+                if bytes.get(i + 2) != Some(&b'(') {
+                    nested.push(&line[(i + 2).min(end)..end]);
+                }
+                i = (end + 1).min(bytes.len());
+            }
+            b'(' => {
+                // Subshell, or fish-style command substitution mid-word
+                let end = matching_paren(bytes, i);
+                nested.push(&line[i + 1..end]);
+                i = (end + 1).min(bytes.len());
+            }
+            b')' => {
+                end_segment!();
+                i += 1;
+            }
+            b' ' | b'\t' => {
+                end_word!();
+                i += 1;
+            }
+            b'\n' | b';' => {
+                end_segment!();
+                i += 1;
+            }
+            // `>|` clobber redirect, not a pipe
+            b'|' if i > 0 && bytes[i - 1] == b'>' => i += 1,
+            b'|' => {
+                end_segment!();
+                // `||` and `|&`
+                i += if matches!(bytes.get(i + 1), Some(b'|' | b'&')) {
+                    2
+                } else {
+                    1
+                };
+            }
+            // `2>&1`, `>&2` and `&>file` are redirects, not background/and
+            b'&' if (i > 0 && matches!(bytes[i - 1], b'>' | b'<'))
+                || bytes.get(i + 1) == Some(&b'>') =>
+            {
+                word_start.get_or_insert(i);
+                i += 1;
+            }
+            b'&' => {
+                end_segment!();
+                i += if bytes.get(i + 1) == Some(&b'&') {
+                    2
+                } else {
+                    1
+                };
+            }
+            _ => {
+                word_start.get_or_insert(i);
+                i += 1;
+            }
+        }
+    }
+    end_segment!();
+
+    (segments, nested)
+}
+
+/// Find the first `$(...)` substitutions within `line[start..end]` (double-quoted text)
+fn collect_substitutions<'a>(line: &'a str, start: usize, end: usize, nested: &mut Vec<&'a str>) {
+    let bytes = line.as_bytes();
+    let mut i = start;
+    while i + 1 < end {
+        if bytes[i] == b'\\' {
+            i += 2;
+            continue;
+        }
+        if bytes[i] == b'$' && bytes[i + 1] == b'(' {
+            let close = matching_paren(bytes, i + 1).min(end);
+            if bytes.get(i + 2) != Some(&b'(') {
+                nested.push(&line[(i + 2).min(close)..close]);
+            }
+            i = close + 1;
+            continue;
+        }
+        i += 1;
+    }
+}
+
+fn find_byte(bytes: &[u8], from: usize, needle: u8) -> Option<usize> {
+    bytes
+        .get(from..)?
+        .iter()
+        .position(|&b| b == needle)
+        .map(|p| from + p)
+}
+
+/// Index of the closing `"` (honouring backslash escapes), or the end of input
+fn closing_double_quote(bytes: &[u8], from: usize) -> usize {
+    let mut i = from;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Index of the `)` matching the `(` at `open`, skipping quoted text, or the end of input
+fn matching_paren(bytes: &[u8], open: usize) -> usize {
+    let mut depth = 0;
+    let mut i = open;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            b'\'' => i = find_byte(bytes, i + 1, b'\'').unwrap_or(bytes.len()),
+            b'"' => i = closing_double_quote(bytes, i + 1),
+            b'(' => depth += 1,
+            b')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    bytes.len()
+}
+
+/// The subcommand among a program's arguments, skipping its global options
+fn subcommand<'a>(program: &str, args: &[&'a str]) -> Option<&'a str> {
+    let options_with_arg = GLOBAL_OPTIONS_WITH_ARG
+        .iter()
+        .find(|(name, _)| *name == program)
+        .map_or(&[][..], |(_, options)| *options);
+
+    let mut i = 0;
+    while let Some(&arg) = args.get(i) {
+        if arg == "--" || is_redirect_operator(arg) || is_redirect_with_target(arg) {
+            return None;
+        }
+        // Options, and toolchain overrides like `cargo +nightly build`
+        if arg.starts_with(['-', '+']) {
+            i += if options_with_arg.contains(&arg) {
+                2
+            } else {
+                1
+            };
+            continue;
+        }
+
+        let arg = unquote(arg);
+        return looks_like_subcommand(arg).then_some(arg);
+    }
+    None
+}
+
+/// Whether a word could be a subcommand name, rather than a path, URL or other argument
+pub fn looks_like_subcommand(word: &str) -> bool {
+    word.len() <= 32
+        && word.starts_with(|c: char| c.is_ascii_alphabetic())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':'))
+}
+
+/// Strip one level of matching surrounding quotes
+fn unquote(word: &str) -> &str {
+    for quote in ['\'', '"'] {
+        if word.len() >= 2
+            && let Some(inner) = word.strip_prefix(quote).and_then(|w| w.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    word
+}
+
+/// `NAME=value` environment assignment
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=').is_some_and(|(name, _)| {
+        !name.is_empty()
+            && !name.starts_with(|c: char| c.is_ascii_digit())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+/// A bare redirection operator whose target is the next word (`>`, `2>>`, `<`)
+fn is_redirect_operator(word: &str) -> bool {
+    let op = word.trim_start_matches(|c: char| c.is_ascii_digit());
+    !op.is_empty() && op.chars().all(|c| matches!(c, '<' | '>' | '&' | '|'))
+}
+
+/// A redirection with its target attached (`>out.txt`, `2>&1`, `&>log`)
+fn is_redirect_with_target(word: &str) -> bool {
+    let op = word.trim_start_matches(|c: char| c.is_ascii_digit());
+    op.starts_with(['<', '>']) || op.starts_with("&>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simple_command() {
+        assert_eq!(invoked_commands("git status"), ["git"]);
+        assert_eq!(invoked_commands("/usr/bin/rg pattern"), ["rg"]);
+        assert!(invoked_commands("").is_empty());
+        assert!(invoked_commands("cd /tmp").is_empty());
+    }
+
+    #[test]
+    fn test_pipelines_and_lists() {
+        assert_eq!(
+            invoked_commands("rg foo | fzf | xargs bat"),
+            ["rg", "fzf", "bat"]
+        );
+        assert_eq!(invoked_commands("cd x && make"), ["make"]);
+        assert_eq!(
+            invoked_commands("cargo fmt; cargo clippy || jq . x |& less"),
+            ["cargo", "cargo", "jq", "less"]
+        );
+        assert_eq!(invoked_commands("sleep 10 & htop"), ["sleep", "htop"]);
+    }
+
+    #[test]
+    fn test_redirects_are_not_separators() {
+        assert_eq!(invoked_commands("make 2>&1 | tee log"), ["make", "tee"]);
+        assert_eq!(invoked_commands("fd x &> out.txt"), ["fd"]);
+        assert_eq!(invoked_commands("> out.txt jq . in.json"), ["jq"]);
+    }
+
+    #[test]
+    fn test_subshells_and_substitutions() {
+        assert_eq!(invoked_commands("(cd src && rg todo)"), ["rg"]);
+        assert_eq!(invoked_commands("bat $(fd -e rs)"), ["bat", "fd"]);
+        assert_eq!(invoked_commands("echo \"$(git rev-parse HEAD)\""), ["git"]);
+        assert_eq!(invoked_commands("echo `date`"), ["date"]);
+        assert_eq!(invoked_commands("vim (fzf)"), ["vim", "fzf"]);
+        assert!(invoked_commands("echo $((1 + 2))").is_empty());
+    }
+
+    #[test]
+    fn test_wrappers() {
+        assert_eq!(invoked_commands("sudo -E apt update"), ["apt"]);
+        assert_eq!(invoked_commands("sudo -u postgres psql"), ["psql"]);
+        assert_eq!(invoked_commands("nice -n 10 cargo build"), ["cargo"]);
+        assert_eq!(invoked_commands("timeout 5 curl example.com"), ["curl"]);
+        assert_eq!(invoked_commands("timeout -s KILL 5s curl x"), ["curl"]);
+        assert_eq!(
+            invoked_commands("env A=b RUST_LOG=debug cargo run"),
+            ["cargo"]
+        );
+        assert_eq!(invoked_commands("RUST_LOG=debug cargo run"), ["cargo"]);
+        assert_eq!(
+            invoked_commands("fd -e md | xargs -I{} bat {}"),
+            ["fd", "bat"]
+        );
+        assert_eq!(invoked_commands("xargs -n 1 rg foo"), ["rg"]);
+        assert_eq!(invoked_commands("watch -n 2 kubectl get pods"), ["kubectl"]);
+        assert_eq!(
+            invoked_commands("watch 'docker ps | grep web'"),
+            ["docker", "grep"]
+        );
+        assert_eq!(invoked_commands("time nohup make"), ["make"]);
+        assert!(invoked_commands("command -v rg").is_empty());
+    }
+
+    #[test]
+    fn test_keywords() {
+        assert_eq!(
+            invoked_commands("for f in *.rs; do rg todo $f; done"),
+            ["rg"]
+        );
+        assert_eq!(
+            invoked_commands("if rg -q x file; then bat file; fi"),
+            ["rg", "bat"]
+        );
+        assert_eq!(invoked_commands("test -f x; and make"), ["make"]);
+        assert!(invoked_commands("if true").is_empty());
+    }
+
+    #[test]
+    fn test_unresolvable_commands() {
+        assert!(invoked_commands("$EDITOR file").is_empty());
+        assert_eq!(invoked_commands("mk() { make -j8; }"), ["make"]);
+        assert_eq!(invoked_commands("\"rg\" pattern"), ["rg"]);
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let subcommands = |line| {
+            invocations(line)
+                .into_iter()
+                .map(|i| (i.program, i.subcommand))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            subcommands("git rebase -i HEAD~3"),
+            [("git", Some("rebase"))]
+        );
+        assert_eq!(subcommands("git -C ~/src/app log"), [("git", Some("log"))]);
+        assert_eq!(
+            subcommands("kubectl -n prod logs -f web | rg error"),
+            [("kubectl", Some("logs")), ("rg", Some("error"))]
+        );
+        assert_eq!(
+            subcommands("cargo +nightly build"),
+            [("cargo", Some("build"))]
+        );
+        assert_eq!(
+            subcommands("sudo docker compose up"),
+            [("docker", Some("compose"))]
+        );
+        assert_eq!(subcommands("bat src/main.rs"), [("bat", None)]);
+        assert_eq!(subcommands("gh --version"), [("gh", None)]);
+        assert_eq!(subcommands("jq . > out.json"), [("jq", None)]);
+    }
+
+    #[test]
+    fn test_alias_expansion() {
+        let mut aliases = Aliases::default();
+        aliases.insert("g", "git");
+        aliases.insert("gco", "git checkout");
+        aliases.insert("ll", "eza -l");
+        aliases.insert("l", "ll -a");
+        aliases.insert("rg", "rg --smart-case");
+        aliases.insert("up", "git pull && cargo build");
+
+        let resolved = |line| {
+            invocations_with(line, &aliases)
+                .into_iter()
+                .map(|i| (i.program, i.subcommand))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(resolved("g push"), [("git", Some("push"))]);
+        assert_eq!(resolved("gco main"), [("git", Some("checkout"))]);
+        assert_eq!(
+            resolved("l src | rg foo"),
+            [("eza", Some("src")), ("rg", Some("foo"))]
+        );
+        assert_eq!(
+            resolved("up"),
+            [("git", Some("pull")), ("cargo", Some("build"))]
+        );
+        // Quoted words and arguments to wrappers are left alone, like the shell does
+        assert_eq!(resolved("'g' push"), [("g", Some("push"))]);
+        assert_eq!(resolved("sudo ll"), [("ll", None)]);
+        assert_eq!(invoked_commands("g push"), ["g"]);
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        for line in [
+            "echo 'unterminated",
+            "rg \"open",
+            "$(",
+            "(((",
+            ")",
+            "\\",
+            "é\\é | ü",
+            "`",
+            "a |",
+            "&&",
+            "$((",
+            "x >",
+            "\"$(",
+            "2>&",
+        ] {
+            let _ = invoked_commands(line);
+        }
+        assert_eq!(invoked_commands("rg 'a | b' | sört"), ["rg", "sört"]);
+    }
+}
@@ -13,30 +13,93 @@ use crate::commands::install::{
 use crate::{AiProvider, Database, HoardConfig};
 
 /// Set the AI provider
-pub fn cmd_ai_set(provider: &str) -> Result<()> {
+pub fn cmd_ai_set(
+    provider: &str,
+    model: Option<String>,
+    base_url: Option<String>,
+    api_key_env: Option<String>,
+) -> Result<()> {
     let ai_provider = AiProvider::from(provider);
 
     if ai_provider == AiProvider::None {
         println!(
-            "{} Unknown provider '{}'. Valid options: claude, gemini, codex, opencode",
+            "{} Unknown provider '{}'. Valid options: claude, gemini, codex, opencode, anthropic, openai, ollama, local",
             "!".yellow(),
             provider
         );
         return Ok(());
     }
 
-    // Check if the CLI tool is installed
-    if !ai_provider.is_installed() {
+    if ai_provider == AiProvider::Ollama && model.is_none() {
+        // Ollama has no default model, point at the ones already pulled
+        let url = base_url.as_deref().unwrap_or("http://localhost:11434/v1");
+        match crate::ai::api::ollama_models(url) {
+            Ok(models) if !models.is_empty() => {
+                println!(
+                    "{} Ollama needs a model, pass one with --model:",
+                    "!".yellow()
+                );
+                for name in models {
+                    println!("  {}", name);
+                }
+            }
+            Ok(_) => println!(
+                "{} No Ollama models installed. Pull one with 'ollama pull <model>', then pass it with --model",
+                "!".yellow()
+            ),
+            Err(e) => println!("{} {:#} (is `ollama serve` running?)", "!".yellow(), e),
+        }
+        return Ok(());
+    }
+
+    // A "local" provider pointed elsewhere still sends prompts off this machine
+    if matches!(ai_provider, AiProvider::Ollama | AiProvider::Local)
+        && let Some(url) = &base_url
+        && !crate::ai::api::is_loopback_url(url)
+    {
         println!(
-            "{} Warning: '{}' CLI not found in PATH",
+            "{} Warning: {} is not on this machine, prompts will be sent there",
             "!".yellow(),
-            ai_provider.command().unwrap_or("unknown")
+            url
         );
-        println!("  The provider will be saved, but AI features won't work until installed.");
+    }
+
+    if ai_provider.uses_api() {
+        // Warn if the API key isn't available
+        let key_var = api_key_env.as_deref().or(ai_provider.api_key_env());
+        if let Some(var) = key_var
+            && std::env::var(var).is_err()
+            && base_url.is_none()
+        {
+            println!("{} Warning: {} is not set", "!".yellow(), var);
+            println!("  The provider will be saved, but AI features won't work until it is.");
+        }
+    } else {
+        if model.is_some() || base_url.is_some() || api_key_env.is_some() {
+            println!(
+                "{} --model, --base-url and --api-key-env only apply to API providers",
+                "!".yellow()
+            );
+            return Ok(());
+        }
+
+        // Check if the CLI tool is installed
+        if !ai_provider.is_installed() {
+            println!(
+                "{} Warning: '{}' CLI not found in PATH",
+                "!".yellow(),
+                ai_provider.command().unwrap_or("unknown")
+            );
+            println!("  The provider will be saved, but AI features won't work until installed.");
+        }
     }
 
     let mut config = HoardConfig::load()?;
     config.set_ai_provider(ai_provider);
+    // Settings of the previous provider don't carry over
+    config.ai.model = model;
+    config.ai.base_url = base_url;
+    config.ai.api_key_env = api_key_env;
     config.save()?;
 
     println!("{} AI provider set to '{}'", "+".green(), ai_provider);
@@ -50,6 +113,8 @@ pub fn cmd_ai_set(provider: &str) -> Result<()> {
 
 /// Show current AI configuration
 pub fn cmd_ai_show() -> Result<()> {
+    use crate::ai::ApiClient;
+
     let config = HoardConfig::load()?;
 
     println!("{}", "AI Configuration".bold());
@@ -57,18 +122,55 @@ pub fn cmd_ai_show() -> Result<()> {
     println!();
 
     let provider = &config.ai.provider;
-    let status = if provider == &AiProvider::None {
-        "not configured".red().to_string()
-    } else if provider.is_installed() {
-        "installed".green().to_string()
+    if provider.uses_api() {
+        let key_var = config
+            .ai
+            .api_key_env
+            .as_deref()
+            .or(provider.api_key_env())
+            .unwrap_or_default();
+        let status = if key_var.is_empty() {
+            "no key needed".green().to_string()
+        } else if std::env::var(key_var).is_ok() {
+            format!("{} set", key_var).green().to_string()
+        } else {
+            format!("{} not set", key_var).yellow().to_string()
+        };
+        println!("Provider: {} [{}]", provider.to_string().cyan(), status);
+
+        match ApiClient::from_config(&config.ai) {
+            Ok(client) => {
+                println!("Model:    {}", client.model());
+                println!("Base URL: {}", client.base_url());
+                if client.is_local() {
+                    println!("Privacy:  {}", "prompts stay on this machine".green());
+                }
+            }
+            Err(_) => {
+                println!(
+                    "Model:    {}",
+                    config.ai.model.as_deref().unwrap_or("default")
+                );
+            }
+        }
+        println!(
+            "Timeout:  {}s, {} retries",
+            config.ai.timeout_secs, config.ai.max_retries
+        );
     } else {
-        "not installed".yellow().to_string()
-    };
+        let status = if provider == &AiProvider::None {
+            "not configured".red().to_string()
+        } else if provider.is_installed() {
+            "installed".green().to_string()
+        } else {
+            "not installed".yellow().to_string()
+        };
 
-    println!("Provider: {} [{}]", provider.to_string().cyan(), status);
+        println!("Provider: {} [{}]", provider.to_string().cyan(), status);
 
-    if let Some(cmd) = provider.command() {
-        println!("Command:  {}", cmd);
+        if let Some(cmd) = provider.command() {
+            println!("Command:  {}", cmd);
+        }
     }
 
     println!();
@@ -88,6 +190,10 @@ pub fn cmd_ai_test() -> Result<()> {
     }
 
     let provider = &config.ai.provider;
+    if provider.uses_api() {
+        return test_ai_api(&config);
+    }
+
     let cmd = match provider.command() {
         Some(c) => c,
         None => {
@@ -141,9 +247,60 @@ pub fn cmd_ai_test() -> Result<()> {
     Ok(())
 }
 
+/// Send a short prompt to an API provider and report the reply
+fn test_ai_api(config: &HoardConfig) -> Result<()> {
+    use crate::ai::ApiClient;
+
+    let client = match ApiClient::from_config(&config.ai) {
+        Ok(client) => client,
+        Err(e) => {
+            println!("{} {}", "!".red(), e);
+            return Ok(());
+        }
+    };
+
+    println!(
+        "{} Testing {} ({} at {})...",
+        ">".cyan(),
+        config.ai.provider,
+        client.model(),
+        client.base_url()
+    );
+
+    let started = std::time::Instant::now();
+    match client.complete("Reply with just the word: ok") {
+        Ok(reply) => println!(
+            "{} {} replied in {:.1}s: {}",
+            "+".green(),
+            client.model(),
+            started.elapsed().as_secs_f64(),
+            reply.chars().take(60).collect::<String>().dimmed()
+        ),
+        Err(e) => println!("{} {:#}", "!".red(), e),
+    }
+
+    Ok(())
+}
+
+/// Report the parts of an AI response that were left out for not matching the expected format
+fn print_dropped(dropped: &[String]) {
+    if dropped.is_empty() {
+        return;
+    }
+    println!(
+        "{} Ignored {} invalid entr{} in the AI response:",
+        "!".yellow(),
+        dropped.len(),
+        if dropped.len() == 1 { "y" } else { "ies" }
+    );
+    for issue in dropped {
+        println!("  - {}", issue.dimmed());
+    }
+}
+
 /// Categorize tools using AI
 pub fn cmd_ai_categorize(dry_run: bool) -> Result<()> {
-    use crate::ai::{categorize_prompt, invoke_ai, parse_categorize_response};
+    use crate::ai::{CATEGORIZE, categorize_prompt};
 
     let db = Database::open()?;
 
@@ -179,10 +336,9 @@ pub fn cmd_ai_categorize(dry_run: bool) -> Result<()> {
     let prompt = categorize_prompt(&uncategorized, &categories);
 
     println!("{} Asking AI to categorize...", ">".cyan());
-    let response = invoke_ai(&prompt)?;
-
-    // Parse response
-    let categorizations = parse_categorize_response(&response)?;
+    let parsed = CATEGORIZE.invoke(&prompt)?;
+    print_dropped(&parsed.dropped);
+    let categorizations = parsed.value;
 
     if categorizations.is_empty() {
         println!("{} AI returned no categorizations", "!".yellow());
@@ -228,7 +384,7 @@ pub fn cmd_ai_categorize(dry_run: bool) -> Result<()> {
 
 /// Suggest bundles using AI based on usage patterns
 pub fn cmd_ai_suggest_bundle(count: usize) -> Result<()> {
-    use crate::ai::{invoke_ai, parse_bundle_response, suggest_bundle_prompt};
+    use crate::ai::{SUGGEST_BUNDLES, suggest_bundle_prompt};
 
     let db = Database::open()?;
 
@@ -288,10 +444,9 @@ pub fn cmd_ai_suggest_bundle(count: usize) -> Result<()> {
 
     // Generate prompt and call AI
     let prompt = suggest_bundle_prompt(&tools, &bundles, &usage_data, count);
-    let response = invoke_ai(&prompt)?;
-
-    // Parse response
-    let suggestions = parse_bundle_response(&response)?;
+    let parsed = SUGGEST_BUNDLES.invoke(&prompt)?;
+    print_dropped(&parsed.dropped);
+    let suggestions = parsed.value;
 
     if suggestions.is_empty() {
         println!("{} AI returned no bundle suggestions", "!".yellow());
@@ -479,7 +634,7 @@ fn install_bundle_tools(db: &Database, suggestion: &crate::ai::BundleSuggestion)
 
 /// Generate descriptions for tools using AI
 pub fn cmd_ai_describe(dry_run: bool, limit: Option<usize>) -> Result<()> {
-    use crate::ai::{describe_prompt, invoke_ai, parse_describe_response};
+    use crate::ai::{DESCRIBE, describe_prompt};
 
     let db = Database::open()?;
 
@@ -518,10 +673,9 @@ pub fn cmd_ai_describe(dry_run: bool, limit: Option<usize>) -> Result<()> {
     let prompt = describe_prompt(&no_description);
 
     println!("{} Asking AI to generate descriptions...", ">".cyan());
-    let response = invoke_ai(&prompt)?;
-
-    // Parse response
-    let descriptions = parse_describe_response(&response)?;
+    let parsed = DESCRIBE.invoke(&prompt)?;
+    print_dropped(&parsed.dropped);
+    let descriptions = parsed.value;
 
     if descriptions.is_empty() {
         println!("{} AI returned no descriptions", "!".yellow());
@@ -562,7 +716,7 @@ pub fn cmd_ai_describe(dry_run: bool, limit: Option<usize>) -> Result<()> {
     Ok(())
 }
 
-/// Extract tool info from GitHub README using AI
+/// Extract tool info from a repository README (GitHub, GitLab or Gitea) using AI
 pub fn cmd_ai_extract(
     db: &Database,
     urls: Vec<String>,
@@ -570,11 +724,10 @@ pub fn cmd_ai_extract(
     dry_run: bool,
     delay_ms: u64,
 ) -> Result<()> {
-    use crate::ai::{
-        ExtractedTool, extract_prompt, fetch_readme, fetch_repo_version, invoke_ai,
-        parse_extract_response, parse_github_url,
-    };
+    use crate::ai::{EXTRACT, ExtractedTool, extract_prompt};
     use crate::db::CachedExtraction;
+    use crate::forge::RepoRef;
+    use crate::github::RepoMapping;
     use crate::{InstallSource, Tool};
     use dialoguer::Confirm;
     use std::thread;
@@ -593,7 +746,8 @@ pub fn cmd_ai_extract(
     );
     println!();
 
-    let mut extracted: Vec<(String, String, ExtractedTool)> = Vec::new();
+    let hosts = crate::forge::configured_hosts();
+    let mut extracted: Vec<(RepoRef, ExtractedTool)> = Vec::new();
     let mut errors: Vec<(String, String)> = Vec::new();
 
     for (i, url) in urls.iter().enumerate() {
@@ -603,18 +757,20 @@ pub fn cmd_ai_extract(
         }
 
         // Parse URL
-        let (owner, repo) = match parse_github_url(url) {
+        let repo_ref = match RepoRef::parse(url, &hosts) {
             Ok(parsed) => parsed,
             Err(e) => {
                 errors.push((url.clone(), e.to_string()));
                 continue;
             }
         };
+        let (owner, repo) = (repo_ref.owner.clone(), repo_ref.repo.clone());
+        let forge = crate::forge::client(repo_ref.forge, &repo_ref.host, db);
 
-        println!("{} {}/{}", ">".cyan(), owner, repo);
+        println!("{} {}", ">".cyan(), repo_ref);
 
         // Check cache first
-        let version = match fetch_repo_version(&owner, &repo) {
+        let version = match forge.head_commit(&owner, &repo) {
             Ok(v) => v,
             Err(e) => {
                 println!("  {} Failed to get version: {}", "!".red(), e);
@@ -633,12 +789,12 @@ pub fn cmd_ai_extract(
                 description: cached.description,
                 category: cached.category,
             };
-            extracted.push((owner, repo, tool));
+            extracted.push((repo_ref, tool));
             continue;
         }
 
         // Fetch README
-        let readme = match fetch_readme(&owner, &repo) {
+        let readme = match forge.readme(&owner, &repo) {
             Ok(r) => r,
             Err(e) => {
                 println!("  {} Failed to fetch README: {}", "!".red(), e);
@@ -651,19 +807,13 @@ pub fn cmd_ai_extract(
         let prompt = extract_prompt(&readme);
         println!("  {} Asking AI to extract...", ">".dimmed());
 
-        let response = match invoke_ai(&prompt) {
-            Ok(r) => r,
-            Err(e) => {
-                println!("  {} AI extraction failed: {}", "!".red(), e);
-                errors.push((url.clone(), e.to_string()));
-                continue;
+        let tool = match EXTRACT.invoke(&prompt) {
+            Ok(parsed) => {
+                print_dropped(&parsed.dropped);
+                parsed.value
             }
-        };
-
-        let tool = match parse_extract_response(&response) {
-            Ok(t) => t,
             Err(e) => {
-                println!("  {} Failed to parse response: {}", "!".red(), e);
+                println!("  {} AI extraction failed: {}", "!".red(), e);
                 errors.push((url.clone(), e.to_string()));
                 continue;
             }
@@ -687,7 +837,7 @@ pub fn cmd_ai_extract(
         }
 
         println!("  {} Extracted successfully", "+".green());
-        extracted.push((owner, repo, tool));
+        extracted.push((repo_ref, tool));
     }
 
     // Show results
@@ -696,9 +846,9 @@ pub fn cmd_ai_extract(
         println!("{}", "Extracted Tools:".bold());
         println!("{}", "=".repeat(50));
 
-        for (owner, repo, tool) in &extracted {
+        for (repo_ref, tool) in &extracted {
             println!();
-            println!("{} (from {}/{})", tool.name.cyan().bold(), owner, repo);
+            println!("{} (from {})", tool.name.cyan().bold(), repo_ref);
             if let Some(bin) = &tool.binary {
                 println!("  Binary:      {}", bin);
             }
@@ -738,8 +888,10 @@ pub fn cmd_ai_extract(
         };
 
         if should_add {
+            // Pin added tools to their repository so sync follows it instead of searching GitHub
+            let mut pins = RepoMapping::load()?;
             let mut added = 0;
-            for (_owner, _repo, ext) in &extracted {
+            for (repo_ref, ext) in &extracted {
                 // Check if tool already exists
                 if db.get_tool_by_name(&ext.name)?.is_some() {
                     println!("  {} {} already exists, skipping", "!".yellow(), ext.name);
@@ -758,9 +910,13 @@ pub fn cmd_ai_extract(
                     println!("  {} Failed to add {}: {}", "!".red(), ext.name, e);
                 } else {
                     println!("  {} Added {}", "+".green(), ext.name);
+                    pins.link(&ext.name, repo_ref);
                     added += 1;
                 }
             }
+            if added > 0 {
+                pins.save()?;
+            }
 
             println!();
             println!(
@@ -784,9 +940,7 @@ pub fn cmd_ai_extract(
 
 /// Generate a cheatsheet for a tool using AI
 pub fn cmd_ai_cheatsheet(tool_name: &str, refresh: bool) -> Result<()> {
-    use crate::ai::{
-        cheatsheet_prompt, format_cheatsheet, get_help_output, invoke_ai, parse_cheatsheet_response,
-    };
+    use crate::ai::{CHEATSHEET, cheatsheet_prompt, format_cheatsheet, get_help_output};
 
     let db = Database::open()?;
 
@@ -827,10 +981,9 @@ pub fn cmd_ai_cheatsheet(tool_name: &str, refresh: bool) -> Result<()> {
 
     // Generate prompt and call AI
     let prompt = cheatsheet_prompt(tool_name, &help_output);
-    let response = invoke_ai(&prompt)?;
-
-    // Parse response
-    let cheatsheet = parse_cheatsheet_response(&response)?;
+    let parsed = CHEATSHEET.invoke(&prompt)?;
+    print_dropped(&parsed.dropped);
+    let cheatsheet = parsed.value;
 
     // Cache the result with version info
     cache_cheatsheet(&db, tool_name, binary, &cheatsheet)?;
@@ -906,8 +1059,7 @@ pub fn invalidate_cheatsheet_cache(db: &Database, tool_name: &str) -> Result<()>
 /// Generate a workflow-oriented cheatsheet for all tools in a bundle
 pub fn cmd_ai_bundle_cheatsheet(bundle_name: &str, refresh: bool) -> Result<()> {
     use crate::ai::{
-        bundle_cheatsheet_prompt, format_cheatsheet, get_help_output, get_tool_version, invoke_ai,
-        parse_cheatsheet_response,
+        CHEATSHEET, bundle_cheatsheet_prompt, format_cheatsheet, get_help_output, get_tool_version,
     };
 
     let db = Database::open()?;
@@ -984,10 +1136,9 @@ pub fn cmd_ai_bundle_cheatsheet(bundle_name: &str, refresh: bool) -> Result<()>
 
     // Generate prompt and call AI
     let prompt = bundle_cheatsheet_prompt(bundle_name, &tools_help);
-    let response = invoke_ai(&prompt)?;
-
-    // Parse response
-    let cheatsheet = parse_cheatsheet_response(&response)?;
+    let parsed = CHEATSHEET.invoke(&prompt)?;
+    print_dropped(&parsed.dropped);
+    let cheatsheet = parsed.value;
 
     // Cache the result with version info
     cache_bundle_cheatsheet(&db, bundle_name, &tools_info, &cheatsheet)?;
@@ -1075,7 +1226,7 @@ pub fn cmd_ai_discover(
     no_stars: bool,
     dry_run: bool,
 ) -> Result<()> {
-    use crate::ai::{ToolRecommendation, discovery_prompt, invoke_ai, parse_discovery_response};
+    use crate::ai::{DISCOVER, ToolRecommendation, discovery_prompt};
     use crate::scanner::is_installed;
     use dialoguer::{MultiSelect, theme::ColorfulTheme};
     use indicatif::{ProgressBar, ProgressStyle};
@@ -1108,11 +1259,11 @@ pub fn cmd_ai_discover(
     spinner.set_message("Asking AI for recommendations...");
     spinner.enable_steady_tick(std::time::Duration::from_millis(80));
 
-    let response = invoke_ai(&prompt)?;
+    let parsed = DISCOVER.invoke(&prompt);
     spinner.finish_and_clear();
-
-    // Parse response
-    let mut discovery = parse_discovery_response(&response)?;
+    let parsed = parsed?;
+    print_dropped(&parsed.dropped);
+    let mut discovery = parsed.value;
 
     // Limit results
     if discovery.tools.len() > limit {
@@ -1259,12 +1410,10 @@ pub fn cmd_ai_discover(
 
 /// Install a tool discovered via AI, using proper extraction when possible
 fn install_discovered_tool(db: &Database, tool: &crate::ai::ToolRecommendation) -> Result<()> {
-    use crate::ai::{
-        ExtractedTool, extract_prompt, fetch_readme, fetch_repo_version, invoke_ai,
-        parse_extract_response, parse_github_url,
-    };
+    use crate::ai::{EXTRACT, ExtractedTool, extract_prompt, parse_github_url};
     use crate::commands::install::get_safe_install_command;
     use crate::db::CachedExtraction;
+    use crate::github::GitHubClient;
     use crate::models::{InstallSource, Tool};
     use indicatif::{ProgressBar, ProgressStyle};
 
@@ -1276,7 +1425,8 @@ fn install_discovered_tool(db: &Database, tool: &crate::ai::ToolRecommendation)
         match parse_github_url(&github_url) {
             Ok((owner, repo)) => {
                 // Check cache first
-                let version = fetch_repo_version(&owner, &repo).unwrap_or_default();
+                let client = GitHubClient::load().with_cache(db);
+                let version = client.head_commit(&owner, &repo).unwrap_or_default();
 
                 if let Ok(Some(cached)) = db.get_cached_extraction(&owner, &repo, &version) {
                     println!("  {} Using cached extraction", "+".green());
@@ -1299,11 +1449,11 @@ fn install_discovered_tool(db: &Database, tool: &crate::ai::ToolRecommendation)
                     spinner.enable_steady_tick(std::time::Duration::from_millis(80));
                     spinner.set_message("Fetching README from GitHub...");
 
-                    match fetch_readme(&owner, &repo) {
+                    match client.readme(&owner, &repo) {
                         Ok(readme) => {
                             spinner.set_message("Extracting tool info with AI...");
                             let prompt = extract_prompt(&readme);
-                            match invoke_ai(&prompt).and_then(|r| parse_extract_response(&r)) {
+                            match EXTRACT.invoke(&prompt).map(|parsed| parsed.value) {
                                 Ok(ext) => {
                                     spinner.finish_and_clear();
                                     // Cache it
@@ -1511,74 +1661,13 @@ fn fetch_github_stars(repo: &str) -> Result<u64> {
 
 // ==================== AI Analyze ====================
 
-/// Detect shell aliases from config files
-///
-/// Returns a map of alias name -> target command
-fn detect_shell_aliases() -> std::collections::HashMap<String, String> {
-    use std::collections::HashMap;
-    use std::fs;
-
-    let mut aliases: HashMap<String, String> = HashMap::new();
-
-    // Check common shell config files
-    let home = match dirs::home_dir() {
-        Some(h) => h,
-        None => return aliases,
-    };
-
-    let config_files = [
-        home.join(".bashrc"),
-        home.join(".bash_aliases"),
-        home.join(".zshrc"),
-        home.join(".zsh_aliases"),
-        home.join(".config/fish/config.fish"),
-        home.join(".config/fish/aliases.fish"),
-    ];
-
-    for file in &config_files {
-        if let Ok(content) = fs::read_to_string(file) {
-            // Parse bash/zsh style: alias name='command' or alias name="command"
-            for line in content.lines() {
-                let line = line.trim();
-                if let Some(rest) = line.strip_prefix("alias ") {
-                    // Handle: alias cat='bat' or alias cat="bat --paging=never"
-                    if let Some(eq_pos) = rest.find('=') {
-                        let name = rest[..eq_pos].trim();
-                        let value = rest[eq_pos + 1..].trim();
-                        // Remove surrounding quotes
-                        let value = value
-                            .strip_prefix('\'')
-                            .and_then(|v| v.strip_suffix('\''))
-                            .or_else(|| value.strip_prefix('"').and_then(|v| v.strip_suffix('"')))
-                            .unwrap_or(value);
-                        aliases.insert(name.to_string(), value.to_string());
-                    }
-                }
-                // Parse fish style: alias name 'command' or abbr -a name command
-                else if line.starts_with("alias ") || line.starts_with("abbr ") {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 3 {
-                        let name = parts[1].trim_start_matches("-a").trim();
-                        let value = parts[2..].join(" ");
-                        let value = value.trim_matches('\'').trim_matches('"').to_string();
-                        if !name.is_empty() {
-                            aliases.insert(name.to_string(), value);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    aliases
-}
-
 /// Analyze CLI usage and suggest optimizations
 pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i64) -> Result<()> {
     use crate::ai::{
-        AnalysisResult, AnalyzeTip, MODERN_REPLACEMENTS, UnderutilizedTool, analyze_prompt,
-        invoke_ai, is_binary_installed, parse_analyze_response,
+        ANALYZE, AnalysisResult, AnalyzeTip, FailingTool, MODERN_REPLACEMENTS, UnderutilizedTool,
+        analyze_prompt, is_binary_installed,
     };
+    use crate::aliases::Aliases;
     use crate::history::parse_all_histories;
     use indicatif::{ProgressBar, ProgressStyle};
 
@@ -1609,7 +1698,7 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     }
 
     // 2. Detect shell aliases (to avoid false positives like "use bat" when alias cat='bat' exists)
-    let aliases = detect_shell_aliases();
+    let aliases = Aliases::load();
 
     // 3. Find optimization opportunities (traditional tool used + modern alternative installed)
     let mut tips: Vec<AnalyzeTip> = Vec::new();
@@ -1636,9 +1725,7 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
         // - Modern tool is not installed
         // - Modern tool is already being used directly (modern_uses >= 5)
         // - There's an alias from traditional -> modern (e.g., alias cat='bat')
-        let has_alias = aliases
-            .get(replacement.traditional)
-            .is_some_and(|target: &String| target.contains(replacement.modern_binary));
+        let has_alias = aliases.resolve(replacement.traditional) == Some(replacement.modern_binary);
         let already_using_modern = modern_uses >= 5;
 
         if trad_uses >= min_uses && modern_available && !already_using_modern && !has_alias {
@@ -1655,7 +1742,7 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     }
 
     // Sort tips by usage count (most used first)
-    tips.sort_by(|a, b| b.traditional_uses.cmp(&a.traditional_uses));
+    tips.sort_by_key(|t| std::cmp::Reverse(t.traditional_uses));
 
     // 3. Get unused installed tools (high-value ones)
     let unused_tools = db.get_unused_tools()?;
@@ -1673,11 +1760,31 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     }
 
     // Sort by stars (most popular first) to highlight high-value unused tools
-    underutilized.sort_by(|a, b| b.stars.unwrap_or(0).cmp(&a.stars.unwrap_or(0)));
+    underutilized.sort_by_key(|t| std::cmp::Reverse(t.stars.unwrap_or(0)));
     underutilized.truncate(5);
 
+    // Tools that often exit with an error, as recorded by the shell hook
+    let mut failing: Vec<FailingTool> = db
+        .get_all_outcome_stats()?
+        .into_iter()
+        .filter(|(_, stats)| stats.is_failing())
+        .map(|(name, stats)| FailingTool {
+            name,
+            runs: stats.runs,
+            failures: stats.failures,
+            last_failure: stats.last_failure,
+        })
+        .collect();
+    failing.sort_by(|a, b| {
+        let rate = |t: &FailingTool| t.failures as f64 / t.runs as f64;
+        rate(b).total_cmp(&rate(a))
+    });
+    failing.truncate(5);
+
     // 4. Optional AI insights
-    let ai_insight = if !no_ai && (!tips.is_empty() || !underutilized.is_empty()) {
+    let ai_insight = if !no_ai
+        && (!tips.is_empty() || !underutilized.is_empty() || !failing.is_empty())
+    {
         if !json_output {
             let sp = ProgressBar::new_spinner();
             sp.set_style(
@@ -1689,24 +1796,25 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
             sp.enable_steady_tick(std::time::Duration::from_millis(80));
 
             let unused_names: Vec<String> = underutilized.iter().map(|t| t.name.clone()).collect();
-            let prompt = analyze_prompt(&traditional_usage, &modern_installed, &unused_names);
+            let prompt = analyze_prompt(
+                &traditional_usage,
+                &modern_installed,
+                &unused_names,
+                &failing,
+            );
 
-            match invoke_ai(&prompt) {
-                Ok(response) => {
-                    sp.finish_and_clear();
-                    parse_analyze_response(&response).ok()
-                }
-                Err(_) => {
-                    sp.finish_and_clear();
-                    None
-                }
-            }
+            let insight = ANALYZE.invoke(&prompt).ok().map(|parsed| parsed.value);
+            sp.finish_and_clear();
+            insight
         } else {
             let unused_names: Vec<String> = underutilized.iter().map(|t| t.name.clone()).collect();
-            let prompt = analyze_prompt(&traditional_usage, &modern_installed, &unused_names);
-            invoke_ai(&prompt)
-                .ok()
-                .and_then(|r| parse_analyze_response(&r).ok())
+            let prompt = analyze_prompt(
+                &traditional_usage,
+                &modern_installed,
+                &unused_names,
+                &failing,
+            );
+            ANALYZE.invoke(&prompt).ok().map(|parsed| parsed.value)
         }
     } else {
         None
@@ -1716,6 +1824,7 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
     let result = AnalysisResult {
         tips,
         underutilized,
+        failing,
         ai_insight,
     };
 
@@ -1777,6 +1886,30 @@ pub fn cmd_ai_analyze(db: &Database, json_output: bool, no_ai: bool, min_uses: i
         println!();
     }
 
+    // Display frequently failing tools
+    if !result.failing.is_empty() {
+        println!("{}", "Frequently failing tools:".red().bold());
+        for tool in &result.failing {
+            let last = tool
+                .last_failure
+                .as_deref()
+                .map(|l| format!(", last {}", l))
+                .unwrap_or_default();
+            println!(
+                "   {} {} {}",
+                "•".red(),
+                tool.name.cyan(),
+                format!("({} of {} runs failed{})", tool.failures, tool.runs, last).dimmed()
+            );
+        }
+        println!(
+            "   {} Check {} for details",
+            ">".dimmed(),
+            "hoards insights usage <tool>".cyan()
+        );
+        println!();
+    }
+
     // Display AI insight if available
     if let Some(insight) = &result.ai_insight {
         println!("{}", "AI Insight:".magenta().bold());
@@ -1812,9 +1945,7 @@ pub fn cmd_ai_migrate(
     json_output: bool,
     no_ai: bool,
 ) -> Result<()> {
-    use crate::ai::{
-        MigrationCandidate, MigrationResult, invoke_ai, migrate_prompt, parse_migrate_response,
-    };
+    use crate::ai::{MIGRATE, MigrationCandidate, MigrationResult, migrate_prompt};
     use crate::updates::{get_installed_version, get_migration_candidates};
     use dialoguer::{MultiSelect, Select, theme::ColorfulTheme};
     use indicatif::{ProgressBar, ProgressStyle};
@@ -1887,7 +2018,7 @@ pub fn cmd_ai_migrate(
     // 4. Optional: Get AI benefits
     let ai_summary: Option<String> = if !no_ai {
         let config = HoardConfig::load()?;
-        if config.ai.provider != AiProvider::None && config.ai.provider.is_installed() {
+        if config.ai.provider.uses_api() || config.ai.provider.is_installed() {
             let spinner = ProgressBar::new_spinner();
             spinner.set_style(
                 ProgressStyle::default_spinner()
@@ -1911,15 +2042,16 @@ pub fn cmd_ai_migrate(
                 .collect();
 
             let prompt = migrate_prompt(&tools_for_prompt);
-            match invoke_ai(&prompt) {
-                Ok(response) => {
+            match MIGRATE.invoke(&prompt) {
+                Ok(parsed) => {
                     spinner.finish_and_clear();
-                    if let Ok(benefits) = parse_migrate_response(&response) {
-                        // Apply benefits to candidates
-                        for candidate in &mut candidates {
-                            if let Some(benefit) = benefits.get(&candidate.name) {
-                                candidate.benefit = Some(benefit.clone());
-                            }
+                    if !json_output {
+                        print_dropped(&parsed.dropped);
+                    }
+                    // Apply benefits to candidates
+                    for candidate in &mut candidates {
+                        if let Some(benefit) = parsed.value.get(&candidate.name) {
+                            candidate.benefit = Some(benefit.clone());
                         }
                     }
                     Some("AI-generated benefits included".to_string())
@@ -498,16 +498,31 @@ pub fn cmd_bundle_update(db: &Database, name: &str, auto_yes: bool) -> Result<()
             );
         }
 
-        // Get user choice
+        // Get user choice, showing release notes as often as asked
         let choice = if auto_yes {
             'u'
         } else {
-            print!("  [U]pdate to latest, [V]ersion, [S]witch source, [N]o skip? ");
-            std::io::Write::flush(&mut std::io::stdout())?;
+            loop {
+                print!(
+                    "  [U]pdate to latest, [V]ersion, [S]witch source, [R]elease notes, [N]o skip? "
+                );
+                std::io::Write::flush(&mut std::io::stdout())?;
 
-            let mut input = String::new();
-            std::io::stdin().read_line(&mut input)?;
-            input.trim().to_lowercase().chars().next().unwrap_or('n')
+                let mut input = String::new();
+                std::io::stdin().read_line(&mut input)?;
+                match input.trim().to_lowercase().chars().next().unwrap_or('n') {
+                    'r' => {
+                        let update = Update {
+                            name: tool_update.name.clone(),
+                            current: tool_update.current.clone(),
+                            latest: tool_update.latest.clone(),
+                            source: tool_update.source.clone(),
+                        };
+                        super::updates_cmd::print_release_notes(db, &update, "  ");
+                    }
+                    choice => break choice,
+                }
+            }
         };
 
         match choice {
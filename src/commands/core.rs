@@ -196,11 +196,17 @@ pub fn cmd_show(db: &Database, name: &str) -> Result<()> {
                 println!("{}: {}", "Install".bold(), cmd);
             }
 
-            // Show GitHub info if available
+            // Show repository info if available
             if let Ok(Some(gh_info)) = db.get_github_info(&tool.name) {
-                println!("\n{}", "GitHub:".bold());
-                println!("  Repo: {}/{}", gh_info.repo_owner, gh_info.repo_name);
+                println!("\n{}", format!("{}:", gh_info.forge.label()).bold());
+                println!("  Repo: {}", gh_info.repo_ref());
                 println!("  Stars: {}", gh_info.stars.to_string().yellow());
+                if let Some(tag) = &gh_info.latest_release {
+                    println!("  Release: {}", tag);
+                }
+                if gh_info.archived {
+                    println!("  {}", "Archived (read-only)".yellow());
+                }
             }
 
             // Show usage if available
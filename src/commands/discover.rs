@@ -102,8 +102,18 @@ pub fn cmd_similar(db: &Database, tool_name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Show trending tools by GitHub stars
-pub fn cmd_trending(db: &Database, category: Option<String>, limit: usize) -> Result<()> {
+/// Show trending tools by GitHub stars, or by stars gained over `days`
+pub fn cmd_trending(
+    db: &Database,
+    category: Option<String>,
+    limit: usize,
+    by: &str,
+    days: u32,
+) -> Result<()> {
+    if by == "velocity" {
+        return trending_by_velocity(db, category, limit, days);
+    }
+
     println!("{} Trending tools by GitHub stars:\n", ">".cyan());
 
     let tools = db.list_tools(false, category.as_deref())?;
@@ -117,7 +127,7 @@ pub fn cmd_trending(db: &Database, category: Option<String>, limit: usize) -> Re
     }
 
     // Sort by stars descending
-    tools_with_stars.sort_by(|a, b| b.1.cmp(&a.1));
+    tools_with_stars.sort_by_key(|t| std::cmp::Reverse(t.1));
 
     if tools_with_stars.is_empty() {
         println!("No tools with GitHub star data found.");
@@ -143,3 +153,63 @@ pub fn cmd_trending(db: &Database, category: Option<String>, limit: usize) -> Re
 
     Ok(())
 }
+
+/// Show tools ranked by stars gained since `days` ago
+fn trending_by_velocity(
+    db: &Database,
+    category: Option<String>,
+    limit: usize,
+    days: u32,
+) -> Result<()> {
+    let since = (chrono::Utc::now().date_naive() - chrono::Duration::days(days as i64))
+        .format("%Y-%m-%d")
+        .to_string();
+
+    let tools: std::collections::HashMap<String, Tool> = db
+        .list_tools(false, category.as_deref())?
+        .into_iter()
+        .map(|t| (t.name.clone(), t))
+        .collect();
+    let velocities: Vec<_> = db
+        .get_star_velocity(&since)?
+        .into_iter()
+        .filter(|v| tools.contains_key(&v.tool_name))
+        .collect();
+
+    if velocities.is_empty() {
+        println!("No star history to compare yet.");
+        println!("Each 'hoards sync --github' records star counts; run it again on a later day.");
+        return Ok(());
+    }
+
+    println!(
+        "{} Trending tools by stars gained in the last {} days:\n",
+        ">".cyan(),
+        days
+    );
+
+    for velocity in velocities.iter().take(limit) {
+        let tool = &tools[&velocity.tool_name];
+        let status = if tool.is_installed {
+            "✓".green()
+        } else {
+            " ".normal()
+        };
+        let gained = format!("{:+}", velocity.gained);
+
+        println!(
+            "  {} {:>6} ★  {}  [{}] {}",
+            status,
+            if velocity.gained > 0 {
+                gained.green()
+            } else {
+                gained.dimmed()
+            },
+            tool.name.bold(),
+            tool.category.as_deref().unwrap_or("-"),
+            format!("{} ★ since {}", velocity.stars, velocity.since).dimmed()
+        );
+    }
+
+    Ok(())
+}
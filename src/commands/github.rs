@@ -1,50 +1,164 @@
 //! GitHub command implementations
 //!
-//! Commands for fetching and syncing GitHub repository information.
+//! Commands for fetching and syncing repository information. Tools without a
+//! repository are searched for on GitHub; pinned and already matched tools
+//! are refreshed from whichever forge hosts them.
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use colored::Colorize;
-use std::process::Command;
 
 use crate::Database;
+use crate::forge::{Forge, ForgeKind, RepoRef};
+use crate::github::{GitHubClient, RateLimit, RateLimits, RepoInfo, RepoMapping, RepoPin};
 
-/// Sync GitHub info for tools without it
+/// API client for the configured GitHub, noting when requests will be anonymous
+fn client(db: &Database) -> GitHubClient<'_> {
+    let client = GitHubClient::load().with_cache(db);
+    if client.token_source().is_none() {
+        println!(
+            "{} No GitHub token found, using anonymous rate limits (set {} or run {})",
+            "!".yellow(),
+            "GITHUB_TOKEN".cyan(),
+            "gh auth login".cyan()
+        );
+    }
+    client
+}
+
+/// Store repository info fetched from `repo`'s forge for a tool
+fn save_repo_info(db: &Database, tool_name: &str, repo: &RepoRef, info: &RepoInfo) -> Result<()> {
+    db.set_github_info(
+        tool_name,
+        crate::db::GitHubInfoInput {
+            forge: repo.forge,
+            host: &repo.host,
+            repo_owner: &info.owner.login,
+            repo_name: &info.name,
+            description: info.description.as_deref(),
+            stars: info.stars,
+            language: info.language.as_deref(),
+            homepage: info.homepage.as_deref(),
+            license: info.license_id(),
+            archived: info.archived,
+            pushed_at: info.pushed_at.as_deref(),
+            latest_release: info.latest_release.as_deref(),
+            open_issues: info.open_issues,
+            topics: &info.topics,
+        },
+    )?;
+    Ok(())
+}
+
+/// Fetch a repository's details from its forge, reusing `github` for its own host
+fn repo_details(db: &Database, github: &GitHubClient, repo: &RepoRef) -> Result<Option<RepoInfo>> {
+    if repo.forge == ForgeKind::GitHub && repo.host == Forge::host(github) {
+        github.get_repo_details(&repo.owner, &repo.repo)
+    } else {
+        crate::forge::client(repo.forge, &repo.host, db).get_repo_details(&repo.owner, &repo.repo)
+    }
+}
+
+/// Where fetched info says a repository lives (its canonical owner and name)
+fn canonical_ref(repo: &RepoRef, info: &RepoInfo) -> RepoRef {
+    RepoRef {
+        owner: info.owner.login.clone(),
+        repo: info.name.clone(),
+        ..repo.clone()
+    }
+}
+
+/// Find a tool's repository, following the user's pin if there is one and searching GitHub otherwise
+fn lookup_repo(
+    db: &Database,
+    github: &GitHubClient,
+    pins: &RepoMapping,
+    tool_name: &str,
+    source: Option<&str>,
+) -> Result<Option<(RepoRef, RepoInfo)>> {
+    match pins.get(tool_name) {
+        Some(RepoPin::Linked(repo)) => {
+            Ok(repo_details(db, github, &repo)?.map(|info| (canonical_ref(&repo, &info), info)))
+        }
+        Some(RepoPin::Unlinked) => Ok(None),
+        Some(RepoPin::Invalid(reason)) => bail!(reason),
+        None => Ok(github.find_repo(tool_name, source)?.map(|info| {
+            let repo = RepoRef {
+                forge: ForgeKind::GitHub,
+                host: Forge::host(github),
+                owner: info.owner.login.clone(),
+                repo: info.name.clone(),
+            };
+            (repo, info)
+        })),
+    }
+}
+
+/// Core and search rate limits, unlimited for servers without rate limiting
+fn current_limits(github: &GitHubClient) -> Result<RateLimits> {
+    Ok(github.rate_limits()?.unwrap_or_else(|| {
+        let unlimited = RateLimit {
+            limit: i64::MAX,
+            remaining: i64::MAX,
+            reset: 0,
+            used: 0,
+        };
+        RateLimits {
+            core: unlimited.clone(),
+            search: unlimited,
+        }
+    }))
+}
+
+/// Sync GitHub info for tools without it, then refresh the rest
+///
+/// Every refresh records the day's star count, which `discover trending --by velocity` ranks by.
 pub fn cmd_gh_sync(
     db: &Database,
     dry_run: bool,
     limit: Option<usize>,
     delay_ms: u64,
 ) -> Result<()> {
-    use crate::github::{
-        TopicMapping, find_repo, get_all_rate_limits, is_gh_available, topics_to_category,
-    };
+    let github = client(db);
 
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
-        println!("  Install it with: {}", "brew install gh".cyan());
-        return Ok(());
+    // Check both core and search rate limits
+    let limits = current_limits(&github)?;
+
+    if limits.search.limit != i64::MAX {
+        println!(
+            "{} Core API:   {}/{} remaining (resets in {} min)",
+            ">".cyan(),
+            limits.core.remaining,
+            limits.core.limit,
+            limits.core.reset_minutes()
+        );
+        println!(
+            "{} Search API: {}/{} remaining (resets in {} sec)",
+            ">".cyan(),
+            limits.search.remaining,
+            limits.search.limit,
+            limits.search.reset_seconds()
+        );
     }
 
-    // Check both core and search rate limits
-    let limits = get_all_rate_limits()?;
+    sync_new_tools(db, &github, &limits, dry_run, limit, delay_ms)?;
 
-    println!(
-        "{} Core API:   {}/{} remaining (resets in {} min)",
-        ">".cyan(),
-        limits.core.remaining,
-        limits.core.limit,
-        limits.core.reset_minutes()
-    );
-    println!(
-        "{} Search API: {}/{} remaining (resets in {} sec)",
-        ">".cyan(),
-        limits.search.remaining,
-        limits.search.limit,
-        limits.search.reset_seconds()
-    );
+    println!();
+    refresh_synced_tools(db, &github, dry_run)
+}
+
+/// Search GitHub for tools that have no repository yet
+fn sync_new_tools(
+    db: &Database,
+    github: &GitHubClient,
+    limits: &RateLimits,
+    dry_run: bool,
+    limit: Option<usize>,
+    delay_ms: u64,
+) -> Result<()> {
+    use crate::github::{TopicMapping, topics_to_category};
 
     // Search API is the bottleneck (30/minute vs 5000/hour)
-    // Each tool needs 1 search call + 1 core API call
+    // Each tool needs 1 search call + 2 core API calls (repo and latest release)
     if limits.search.remaining == 0 {
         println!(
             "\n{} Search API quota exhausted! Wait {} seconds before retrying.",
@@ -54,8 +168,17 @@ pub fn cmd_gh_sync(
         return Ok(());
     }
 
-    // Get tools without GitHub info
+    // Get tools without GitHub info (except those pinned to no repository, or to a broken pin)
+    let pins = RepoMapping::load()?;
     let mut tools_to_sync = db.get_tools_without_github()?;
+    tools_to_sync.retain(|name| match pins.get(name) {
+        Some(RepoPin::Unlinked) => false,
+        Some(RepoPin::Invalid(reason)) => {
+            println!("{} Skipping {}: {}", "!".yellow(), name, reason);
+            false
+        }
+        _ => true,
+    });
 
     if tools_to_sync.is_empty() {
         println!("{} All tools already have GitHub info", "+".green());
@@ -65,8 +188,8 @@ pub fn cmd_gh_sync(
     // Limit based on Search API (the stricter limit)
     // Each tool needs 1 search call
     let search_limited_max = limits.search.remaining as usize;
-    // Also check core API (each tool needs 1 core call for repo details)
-    let core_limited_max = limits.core.remaining as usize;
+    // Also check core API (each tool needs 2 core calls for repo details and release)
+    let core_limited_max = (limits.core.remaining / 2) as usize;
     let rate_limited_max = search_limited_max.min(core_limited_max);
 
     if let Some(max) = limit {
@@ -127,8 +250,8 @@ pub fn cmd_gh_sync(
 
         print!("  {} {}... ", ">".dimmed(), tool_name);
 
-        match find_repo(tool_name, source.as_deref()) {
-            Ok(Some(info)) => {
+        match lookup_repo(db, github, &pins, tool_name, source.as_deref()) {
+            Ok(Some((repo, info))) => {
                 if dry_run {
                     println!("{}", "[dry] found".yellow());
                     println!("       {} ({} stars)", info.full_name.dimmed(), info.stars);
@@ -136,25 +259,8 @@ pub fn cmd_gh_sync(
                         println!("       topics: {}", info.topics.join(", ").dimmed());
                     }
                 } else {
-                    // Store GitHub info
-                    db.set_github_info(
-                        tool_name,
-                        crate::db::GitHubInfoInput {
-                            repo_owner: &info.owner.login,
-                            repo_name: &info.name,
-                            description: info.description.as_deref(),
-                            stars: info.stars,
-                            language: info.language.as_deref(),
-                            homepage: info.homepage.as_deref(),
-                        },
-                    )?;
-
-                    // Add topics as labels
-                    let labels: Vec<String> =
-                        info.topics.iter().map(|t| t.to_lowercase()).collect();
-                    if !labels.is_empty() {
-                        db.add_labels(tool_name, &labels)?;
-                    }
+                    // Store GitHub info (topics show up as labels)
+                    save_repo_info(db, tool_name, &repo, &info)?;
 
                     // Auto-fill description and category if missing
                     if let Some(tool) = db.get_tool_by_name(tool_name)? {
@@ -216,16 +322,106 @@ pub fn cmd_gh_sync(
     Ok(())
 }
 
-/// Show GitHub API rate limits
-pub fn cmd_gh_rate_limit() -> Result<()> {
-    use crate::github::{get_all_rate_limits, is_gh_available};
+/// Refresh repository info (stars, activity, release) for tools that already have it
+fn refresh_synced_tools(db: &Database, github: &GitHubClient, dry_run: bool) -> Result<()> {
+    let mut repos = db.get_all_github_info()?;
+    if repos.is_empty() {
+        return Ok(());
+    }
 
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
+    if dry_run {
+        println!(
+            "{} Would refresh {} synced tool{}",
+            "[dry]".yellow(),
+            repos.len(),
+            if repos.len() == 1 { "" } else { "s" }
+        );
         return Ok(());
     }
 
-    let limits = get_all_rate_limits()?;
+    // Each refresh needs 2 core calls (repo details and latest release)
+    let limits = current_limits(github)?;
+    let core_limited_max = (limits.core.remaining / 2) as usize;
+    if repos.len() > core_limited_max {
+        println!(
+            "{} Refreshing {} of {} synced tools (core quota: {} remaining)",
+            "!".yellow(),
+            core_limited_max,
+            repos.len(),
+            limits.core.remaining
+        );
+        repos.truncate(core_limited_max);
+    } else {
+        println!(
+            "{} Refreshing {} synced tool{}...",
+            ">".cyan(),
+            repos.len(),
+            if repos.len() == 1 { "" } else { "s" }
+        );
+    }
+
+    let pins = RepoMapping::load()?;
+    let mut refreshed = 0;
+    for (tool_name, cached) in &repos {
+        // A pin set by editing the mapping file replaces whatever was matched before
+        let repo = match pins.get(tool_name) {
+            Some(RepoPin::Linked(repo)) => repo,
+            Some(RepoPin::Unlinked) => {
+                db.delete_github_info(tool_name)?;
+                continue;
+            }
+            Some(RepoPin::Invalid(reason)) => {
+                println!("  {} Skipping {}: {}", "!".yellow(), tool_name, reason);
+                continue;
+            }
+            None => cached.repo_ref(),
+        };
+
+        match repo_details(db, github, &repo) {
+            Ok(Some(info)) => {
+                save_repo_info(db, tool_name, &canonical_ref(&repo, &info), &info)?;
+                refreshed += 1;
+            }
+            Ok(None) => println!(
+                "  {} {}: {} no longer exists",
+                "!".yellow(),
+                tool_name,
+                repo
+            ),
+            Err(e) => {
+                println!("  {} {}: {}", "!".red(), tool_name, e);
+                break;
+            }
+        }
+    }
+
+    println!(
+        "{} Refreshed {} tool{}",
+        "+".green(),
+        refreshed,
+        if refreshed == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
+
+/// Show GitHub API rate limits
+pub fn cmd_gh_rate_limit(db: &Database) -> Result<()> {
+    let github = client(db);
+
+    if let Some(source) = github.token_source() {
+        println!("{} Token from {}", ">".cyan(), source);
+        println!();
+    }
+
+    let Some(limits) = github.rate_limits()? else {
+        println!(
+            "{} Rate limiting is disabled on {}",
+            "+".green(),
+            github.api_url()
+        );
+        return Ok(());
+    };
 
     println!("{}", "Core API (5000/hour):".bold());
     println!("  Limit:     {}", limits.core.limit);
@@ -262,7 +458,24 @@ pub fn cmd_gh_rate_limit() -> Result<()> {
 
 /// Backfill descriptions from cached GitHub data
 pub fn cmd_gh_backfill(db: &Database, dry_run: bool) -> Result<()> {
-    let tools = db.get_tools_needing_description_backfill()?;
+    // Cached data from a repository other than the pinned one was a wrong match
+    let pins = RepoMapping::load()?;
+    let mut tools = db.get_tools_needing_description_backfill()?;
+    tools.retain(|(name, _)| match pins.get(name) {
+        Some(RepoPin::Linked(repo)) => {
+            db.get_github_info(name).ok().flatten().is_some_and(|info| {
+                info.host == repo.host
+                    && info.repo_owner.eq_ignore_ascii_case(&repo.owner)
+                    && info.repo_name.eq_ignore_ascii_case(&repo.repo)
+            })
+        }
+        Some(RepoPin::Unlinked) => false,
+        Some(RepoPin::Invalid(reason)) => {
+            println!("{} Skipping {}: {}", "!".yellow(), name, reason);
+            false
+        }
+        None => true,
+    });
 
     if tools.is_empty() {
         println!(
@@ -319,12 +532,7 @@ pub fn cmd_gh_backfill(db: &Database, dry_run: bool) -> Result<()> {
 
 /// Fetch GitHub info for a specific tool
 pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
-    use crate::github::{TopicMapping, find_repo, is_gh_available, topics_to_category};
-
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
-        return Ok(());
-    }
+    use crate::github::{TopicMapping, topics_to_category};
 
     // Check if tool exists in DB
     let tool = db.get_tool_by_name(name)?;
@@ -338,29 +546,27 @@ pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
     }
     let source = tool.map(|t| t.source.to_string());
 
-    println!("{} Fetching GitHub info for '{}'...", ">".cyan(), name);
+    let pins = RepoMapping::load()?;
+    if pins.get(name) == Some(RepoPin::Unlinked) {
+        println!(
+            "{} '{}' is unlinked from any repository",
+            "!".yellow(),
+            name
+        );
+        println!(
+            "  Link it with: {}",
+            format!("hoards gh link {} <repo-url>", name).cyan()
+        );
+        return Ok(());
+    }
+
+    let github = client(db);
+    println!("{} Fetching repository info for '{}'...", ">".cyan(), name);
 
-    match find_repo(name, source.as_deref())? {
-        Some(info) => {
-            // Store GitHub info
-            db.set_github_info(
-                name,
-                crate::db::GitHubInfoInput {
-                    repo_owner: &info.owner.login,
-                    repo_name: &info.name,
-                    description: info.description.as_deref(),
-                    stars: info.stars,
-                    language: info.language.as_deref(),
-                    homepage: info.homepage.as_deref(),
-                },
-            )?;
-
-            // Add topics as labels
-            let labels: Vec<String> = info.topics.iter().map(|t| t.to_lowercase()).collect();
-            if !labels.is_empty() {
-                db.clear_labels(name)?;
-                db.add_labels(name, &labels)?;
-            }
+    match lookup_repo(db, &github, &pins, name, source.as_deref())? {
+        Some((repo, info)) => {
+            // Store GitHub info (topics show up as labels)
+            save_repo_info(db, name, &repo, &info)?;
 
             // Auto-categorize if uncategorized
             let mapping = TopicMapping::load();
@@ -377,8 +583,8 @@ pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
             }
 
             println!();
-            println!("{}", "GitHub Info:".bold());
-            println!("  Repo:     {}", info.full_name.cyan());
+            println!("{}", format!("{} Info:", repo.forge.label()).bold());
+            println!("  Repo:     {}", repo.to_string().cyan());
             println!("  Stars:    {}", info.stars);
             if let Some(desc) = &info.description {
                 println!("  Desc:     {}", desc.dimmed());
@@ -386,11 +592,23 @@ pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
             if let Some(lang) = &info.language {
                 println!("  Language: {}", lang);
             }
+            if let Some(license) = info.license_id() {
+                println!("  License:  {}", license);
+            }
+            if let Some(tag) = &info.latest_release {
+                println!("  Release:  {}", tag);
+            }
+            if let Some(pushed) = &info.pushed_at {
+                println!("  Pushed:   {}", pushed.get(..10).unwrap_or(pushed));
+            }
+            if info.archived {
+                println!("  {}", "Archived (read-only)".yellow());
+            }
             if !info.topics.is_empty() {
                 println!("  Topics:   {}", info.topics.join(", "));
             }
             println!();
-            println!("{} GitHub info saved", "+".green());
+            println!("{} Repository info saved", "+".green());
         }
         None => {
             println!("{} '{}' not found on GitHub", "!".yellow(), name);
@@ -400,47 +618,307 @@ pub fn cmd_gh_fetch(db: &Database, name: &str) -> Result<()> {
     Ok(())
 }
 
-/// Search GitHub repositories
-pub fn cmd_gh_search(query: &str, limit: usize) -> Result<()> {
-    use crate::github::is_gh_available;
+/// Pin a tool to a repository on any supported forge and fetch its info
+pub fn cmd_gh_link(db: &Database, name: &str, repo: &str) -> Result<()> {
+    let Some(tool) = db.get_tool_by_name(name)? else {
+        println!("{} Tool '{}' not found in database", "!".yellow(), name);
+        return Ok(());
+    };
+    let repo = RepoRef::parse(repo, &crate::forge::configured_hosts())?;
 
-    if !is_gh_available() {
-        println!("{} GitHub CLI (gh) is not installed", "!".red());
+    let forge = crate::forge::client(repo.forge, &repo.host, db);
+    let Some(info) = forge.get_repo_details(&repo.owner, &repo.repo)? else {
+        println!("{} {} not found on {}", "!".red(), repo, repo.forge.label());
         return Ok(());
+    };
+    let repo = canonical_ref(&repo, &info);
+
+    let mut pins = RepoMapping::load()?;
+    pins.link(name, &repo);
+    pins.save()?;
+
+    // Replace a description that came from the previously matched repository
+    let previous = db.get_github_info(name)?;
+    save_repo_info(db, name, &repo, &info)?;
+    if let Some(desc) = &info.description
+        && (tool.description.is_none()
+            || previous.is_some_and(|p| p.description == tool.description))
+    {
+        db.update_tool_description(name, desc)?;
     }
 
-    println!("{} Searching GitHub for '{}'...", ">".cyan(), query);
+    println!(
+        "{} Linked {} to {} ({} stars)",
+        "+".green(),
+        name.bold(),
+        repo.to_string().cyan(),
+        info.stars
+    );
+
+    Ok(())
+}
 
-    let output = Command::new("gh")
-        .args([
-            "search",
-            "repos",
-            query,
-            "--json",
-            "name,fullName,description,stargazersCount",
-            "--limit",
-            &limit.to_string(),
-        ])
-        .output()?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        println!("{} Search failed: {}", "!".red(), stderr);
+/// Pin a tool to no repository, dropping any info matched for it
+pub fn cmd_gh_unlink(db: &Database, name: &str) -> Result<()> {
+    if db.get_tool_by_name(name)?.is_none() {
+        println!("{} Tool '{}' not found in database", "!".yellow(), name);
         return Ok(());
     }
 
-    #[derive(serde::Deserialize)]
-    struct SearchResult {
-        #[allow(dead_code)]
-        name: String,
-        #[serde(rename = "fullName")]
-        full_name: String,
-        description: Option<String>,
-        #[serde(rename = "stargazersCount")]
-        stars: i64,
+    let mut pins = RepoMapping::load()?;
+    pins.unlink(name);
+    pins.save()?;
+    db.delete_github_info(name)?;
+
+    println!(
+        "{} Unlinked {} from its repository; sync won't search for it",
+        "+".green(),
+        name.bold()
+    );
+    println!(
+        "  Undo by removing it from {}",
+        RepoMapping::path()?.display().to_string().cyan()
+    );
+
+    Ok(())
+}
+
+/// Add starred repositories that look like CLI tools as not-installed tools
+pub fn cmd_gh_import_stars(db: &Database, user: Option<&str>, dry_run: bool) -> Result<()> {
+    use crate::github::{TopicMapping, cli_score, topics_to_category};
+    use crate::models::Tool;
+    use std::collections::HashSet;
+
+    let github = client(db);
+    if user.is_none() && github.token_source().is_none() {
+        println!(
+            "{} Importing your own stars needs a token, or pass {} to import someone's public stars",
+            "!".yellow(),
+            "--user <name>".cyan()
+        );
+        return Ok(());
+    }
+
+    println!(
+        "{} Fetching repositories starred by {}...",
+        ">".cyan(),
+        user.unwrap_or("you")
+    );
+    let starred = github.starred(user)?;
+
+    // Repositories already tracked under some tool name
+    let tracked: HashSet<String> = db
+        .get_all_github_info()?
+        .into_iter()
+        .map(|(_, info)| format!("{}/{}", info.repo_owner, info.repo_name).to_lowercase())
+        .collect();
+
+    let mapping = TopicMapping::load();
+    let mut pins = RepoMapping::load()?;
+    let mut imported = 0;
+    let mut already_tracked = 0;
+    let mut not_cli = 0;
+    let mut failed = 0;
+
+    for mut info in starred {
+        if tracked.contains(&info.full_name.to_lowercase())
+            || db.get_tool_by_name(&info.name.to_lowercase())?.is_some()
+        {
+            already_tracked += 1;
+            continue;
+        }
+
+        // Borderline repositories count as tools only if they publish releases
+        let score = cli_score(&info);
+        if !info.archived && score == 1 {
+            match github.latest_release(&info.owner.login, &info.name) {
+                Ok(release) => info.latest_release = release,
+                Err(e) => {
+                    println!("  {} {}: {}", "!".yellow(), info.full_name, e);
+                    failed += 1;
+                    continue;
+                }
+            }
+        }
+        if info.archived || score == 0 || (score == 1 && info.latest_release.is_none()) {
+            not_cli += 1;
+            continue;
+        }
+
+        // A dry run doesn't spend a README request per repository
+        let Inferred {
+            name,
+            source,
+            install_command,
+            binary,
+        } = infer_install(db, &github, &info, !dry_run);
+        if db.get_tool_by_name(&name)?.is_some() {
+            already_tracked += 1;
+            continue;
+        }
+        let repo = RepoRef {
+            forge: ForgeKind::GitHub,
+            host: Forge::host(&github),
+            owner: info.owner.login.clone(),
+            repo: info.name.clone(),
+        };
+
+        if dry_run {
+            println!(
+                "  {} {} ({}, {})",
+                "[dry]".yellow(),
+                name.bold(),
+                repo.to_string().dimmed(),
+                source
+            );
+            imported += 1;
+            continue;
+        }
+
+        let mut tool = Tool::new(&name).with_source(source.clone());
+        if let Some(desc) = &info.description {
+            tool = tool.with_description(desc);
+        }
+        if let Some(category) = topics_to_category(&info.topics, &mapping) {
+            tool = tool.with_category(&category);
+        }
+        if let Some(binary) = &binary {
+            tool = tool.with_binary(binary);
+        }
+        if let Some(command) = &install_command {
+            tool = tool.with_install_command(command);
+        }
+        let added = db.transaction(|db| {
+            db.insert_tool(&tool)?;
+            save_repo_info(db, &name, &repo, &info)
+        });
+        if let Err(e) = added {
+            println!("  {} {}: {}", "!".yellow(), name, e);
+            failed += 1;
+            continue;
+        }
+        // Saved as we go, so the mapping matches the database whenever the import stops
+        pins.link(&name, &repo);
+        pins.save()?;
+
+        println!(
+            "  {} {} ({})",
+            "+".green(),
+            name.bold(),
+            install_command
+                .unwrap_or_else(|| source.to_string())
+                .dimmed()
+        );
+        imported += 1;
+    }
+
+    println!();
+    println!(
+        "{} {} {} tool{} ({} already tracked, {} don't look like CLI tools)",
+        "+".green(),
+        if dry_run { "Would import" } else { "Imported" },
+        imported,
+        if imported == 1 { "" } else { "s" },
+        already_tracked,
+        not_cli
+    );
+    if failed > 0 {
+        println!(
+            "{} Skipped {} repositor{} after errors",
+            "!".yellow(),
+            failed,
+            if failed == 1 { "y" } else { "ies" }
+        );
+    }
+    if imported > 0 && !dry_run {
+        println!(
+            "  They're in the TUI's Available tab; install one with {}",
+            "hoards install <tool>".cyan()
+        );
     }
 
-    let results: Vec<SearchResult> = serde_json::from_slice(&output.stdout)?;
+    Ok(())
+}
+
+/// How a starred repository installs, as found by [`infer_install`]
+struct Inferred {
+    /// Tool name, which is also the package `hoards install` installs
+    name: String,
+    source: crate::models::InstallSource,
+    /// Command shown for the tool: the one `hoards install` runs when there is one
+    install_command: Option<String>,
+    binary: Option<String>,
+}
+
+/// How to install a starred repository: from a previous `ai extract` or its README
+///
+/// `hoards install` installs a tool by its name, so the name is the package
+/// found there. Otherwise the repository name is kept with an unknown source:
+/// a package of that name on the language's registry may be anyone's.
+fn infer_install(
+    db: &Database,
+    github: &GitHubClient,
+    info: &RepoInfo,
+    fetch_readme: bool,
+) -> Inferred {
+    use crate::commands::{get_safe_install_command, validate_package_name};
+    use crate::github::{install_from_readme, package_from_command};
+    use crate::models::InstallSource;
+
+    let repo_name = info.name.to_lowercase();
+    let registry = |name: String, source: InstallSource, shown: Option<String>, binary| {
+        let install_command = get_safe_install_command(&name, &source.to_string(), None)
+            .ok()
+            .flatten()
+            .map(|c| c.display)
+            .or(shown);
+        Inferred {
+            name,
+            source,
+            install_command,
+            binary,
+        }
+    };
+    // Installed by hand with the command shown
+    let manual = |command: Option<String>, binary| Inferred {
+        name: repo_name.clone(),
+        source: if command.is_some() {
+            InstallSource::Manual
+        } else {
+            InstallSource::Unknown
+        },
+        install_command: command,
+        binary,
+    };
+
+    if let Ok(Some(cached)) = db.get_any_cached_extraction(&info.owner.login, &info.name) {
+        return if validate_package_name(&cached.name).is_ok() && !cached.name.contains('/') {
+            let source = InstallSource::from(cached.source.as_str());
+            registry(cached.name, source, cached.install_command, cached.binary)
+        } else {
+            manual(cached.install_command, cached.binary)
+        };
+    }
+
+    if fetch_readme
+        && let Ok(readme) = github.readme(&info.owner.login, &info.name)
+        && let Some((source, command)) = install_from_readme(&readme)
+    {
+        return match package_from_command(&command) {
+            Some(package) => registry(package, source, Some(command), None),
+            None => manual(Some(command), None),
+        };
+    }
+
+    manual(None, None)
+}
+
+/// Search GitHub repositories
+pub fn cmd_gh_search(db: &Database, query: &str, limit: usize) -> Result<()> {
+    let github = client(db);
+    println!("{} Searching GitHub for '{}'...", ">".cyan(), query);
+
+    let results = github.search_repos(query, limit)?;
 
     if results.is_empty() {
         println!("{} No results found", "!".yellow());
@@ -475,8 +953,8 @@ pub fn cmd_gh_info(db: &Database, name: &str) -> Result<()> {
     // Get cached GitHub info
     match db.get_github_info(name)? {
         Some(info) => {
-            println!("{}", "GitHub Info:".bold());
-            println!("  Repo:     {}/{}", info.repo_owner, info.repo_name);
+            println!("{}", format!("{} Info:", info.forge.label()).bold());
+            println!("  Repo:     {}", info.repo_ref());
             println!("  Stars:    {}", info.stars);
             if let Some(desc) = &info.description {
                 println!("  Desc:     {}", desc.dimmed());
@@ -487,6 +965,21 @@ pub fn cmd_gh_info(db: &Database, name: &str) -> Result<()> {
             if let Some(hp) = &info.homepage {
                 println!("  Homepage: {}", hp);
             }
+            if let Some(license) = &info.license {
+                println!("  License:  {}", license);
+            }
+            if let Some(tag) = &info.latest_release {
+                println!("  Release:  {}", tag);
+            }
+            if let Some(pushed) = &info.pushed_at {
+                println!("  Pushed:   {}", pushed.get(..10).unwrap_or(pushed));
+            }
+            if let Some(issues) = info.open_issues {
+                println!("  Issues:   {} open", issues);
+            }
+            if info.archived {
+                println!("  {}", "Archived (read-only)".yellow());
+            }
 
             // Show labels
             let labels = db.get_labels(name)?;
@@ -495,7 +988,7 @@ pub fn cmd_gh_info(db: &Database, name: &str) -> Result<()> {
             }
         }
         None => {
-            println!("{} No GitHub info cached for '{}'", "!".yellow(), name);
+            println!("{} No repository info cached for '{}'", "!".yellow(), name);
             println!(
                 "  Fetch it with: {}",
                 format!("hoards gh fetch {}", name).cyan()
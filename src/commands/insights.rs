@@ -1,4 +1,4 @@
-//! Insights commands: stats, info, overview, categories
+//! Insights commands: stats, info, overview, categories, disk
 
 use anyhow::Result;
 use colored::Colorize;
@@ -78,7 +78,7 @@ pub fn cmd_overview(db: &Database) -> Result<()> {
             tools_with_usage.push((tool.name.clone(), usage.use_count));
         }
     }
-    tools_with_usage.sort_by(|a, b| b.1.cmp(&a.1));
+    tools_with_usage.sort_by_key(|t| std::cmp::Reverse(t.1));
 
     if tools_with_usage.is_empty() {
         println!("   (no usage data - run 'hoards sync --usage')");
@@ -144,3 +144,202 @@ pub fn cmd_categories(db: &Database) -> Result<()> {
 
     Ok(())
 }
+
+/// Upper bound on threads measuring disk usage at once
+const DISK_WORKERS: usize = 8;
+
+/// Measure a tool's disk footprint via its package source
+fn measure_tool_disk_usage(tool: &crate::models::Tool) -> Option<u64> {
+    match crate::sources::source_for(&tool.source) {
+        Some(source) => source.disk_usage(tool),
+        None => {
+            let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+            crate::disk::binary_size(binary)
+        }
+    }
+}
+
+/// Show per-tool disk usage with totals per source
+pub fn cmd_insights_disk(db: &Database, limit: usize, unused_only: bool) -> Result<()> {
+    use crate::disk::format_bytes;
+    use crate::icons::source_icon;
+    use comfy_table::{
+        Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
+    };
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let tools = db.list_tools(true, None)?;
+
+    if tools.is_empty() {
+        println!("{} No installed tools tracked", "!".yellow());
+        println!("  Run {} first", "hoards sync --scan".cyan());
+        return Ok(());
+    }
+
+    println!(
+        "{} Measuring disk usage for {} tools...",
+        ">".cyan(),
+        tools.len()
+    );
+
+    // Measure on a few threads - some sources shell out to their package manager
+    let workers = std::thread::available_parallelism()
+        .map_or(4, |n| n.get())
+        .min(DISK_WORKERS)
+        .min(tools.len());
+    let next = AtomicUsize::new(0);
+    let mut sizes: Vec<Option<u64>> = vec![None; tools.len()];
+    std::thread::scope(|s| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut measured = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(tool) = tools.get(i) else {
+                            break;
+                        };
+                        measured.push((i, measure_tool_disk_usage(tool)));
+                    }
+                    measured
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            match handle.join() {
+                Ok(measured) => {
+                    for (i, bytes) in measured {
+                        sizes[i] = bytes;
+                    }
+                }
+                Err(_) => eprintln!(
+                    "{} Measuring failed for some tools, they are left out",
+                    "!".yellow()
+                ),
+            }
+        }
+    });
+    let measured: Vec<(&crate::models::Tool, Option<u64>)> = tools.iter().zip(sizes).collect();
+
+    // Cache results so the TUI can sort by size without re-measuring
+    for (tool, bytes) in &measured {
+        if let Some(bytes) = bytes {
+            db.set_disk_usage(&tool.name, *bytes)?;
+        }
+    }
+
+    let usage: HashMap<String, crate::db::ToolUsage> = db.get_all_usage()?.into_iter().collect();
+    let is_unused = |name: &str| usage.get(name).is_none_or(|u| u.use_count == 0);
+
+    let mut rows: Vec<(&crate::models::Tool, u64)> = measured
+        .iter()
+        .filter_map(|(tool, bytes)| bytes.map(|b| (*tool, b)))
+        .filter(|(tool, _)| !unused_only || is_unused(&tool.name))
+        .collect();
+    rows.sort_by_key(|(_, bytes)| std::cmp::Reverse(*bytes));
+
+    if rows.is_empty() {
+        println!("{} No disk usage could be measured", "!".yellow());
+        return Ok(());
+    }
+
+    let term_width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0)
+        .unwrap_or(120);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(term_width)
+        .set_header(vec![
+            Cell::new("💾 Tool").fg(Color::Cyan),
+            Cell::new("Src").fg(Color::Cyan),
+            Cell::new("Size").fg(Color::Cyan),
+            Cell::new("Uses").fg(Color::Cyan),
+        ]);
+
+    for (tool, bytes) in rows.iter().take(limit) {
+        let uses = usage.get(&tool.name).map(|u| u.use_count).unwrap_or(0);
+        let (uses_cell, size_color) = if is_unused(&tool.name) {
+            (Cell::new("unused").fg(Color::Red), Color::Red)
+        } else {
+            (Cell::new(uses), Color::Green)
+        };
+
+        table.add_row(vec![
+            Cell::new(&tool.name),
+            Cell::new(source_icon(&tool.source.to_string())),
+            Cell::new(format_bytes(*bytes)).fg(size_color),
+            uses_cell,
+        ]);
+    }
+
+    println!("{table}");
+
+    if rows.len() > limit {
+        println!(
+            "{} Showing top {} of {} tools. Use {} to see more.",
+            ">".cyan(),
+            limit,
+            rows.len(),
+            "--limit".yellow()
+        );
+    }
+
+    // Totals per source
+    let mut per_source: HashMap<String, (u64, usize)> = HashMap::new();
+    for (tool, bytes) in &rows {
+        let entry = per_source.entry(tool.source.to_string()).or_default();
+        entry.0 += bytes;
+        entry.1 += 1;
+    }
+    let mut per_source: Vec<_> = per_source.into_iter().collect();
+    per_source.sort_by_key(|(_, (bytes, _))| std::cmp::Reverse(*bytes));
+
+    println!();
+    println!("{}", "Totals by source:".bold());
+    for (source, (bytes, count)) in &per_source {
+        println!(
+            "  {} {:10} {:>10}  ({} tool{})",
+            source_icon(source),
+            source,
+            format_bytes(*bytes),
+            count,
+            if *count == 1 { "" } else { "s" }
+        );
+    }
+
+    let total: u64 = rows.iter().map(|(_, b)| b).sum();
+    println!(
+        "💾 Total: {} across {} tools",
+        format_bytes(total),
+        rows.len()
+    );
+
+    // Highlight what removing unused tools would free
+    let unused: Vec<_> = rows.iter().filter(|(t, _)| is_unused(&t.name)).collect();
+    if !unused.is_empty() && !unused_only {
+        let reclaimable: u64 = unused.iter().map(|(_, b)| b).sum();
+        println!();
+        println!(
+            "{} {} unused tool{} using {}",
+            "!".yellow(),
+            unused.len(),
+            if unused.len() == 1 { "" } else { "s" },
+            format_bytes(reclaimable).red()
+        );
+        for (tool, bytes) in unused.iter().take(5) {
+            println!("    {:20} {}", tool.name, format_bytes(*bytes).dimmed());
+        }
+        println!(
+            "  Consider uninstalling with: {}",
+            "hoards uninstall <tool>".cyan()
+        );
+    }
+
+    Ok(())
+}
@@ -11,6 +11,9 @@ use crate::{Database, InstallSource, Tool};
 /// Maximum number of items to display in doctor command output
 const MAX_DISPLAY_ITEMS: usize = 10;
 
+/// Repositories without a push for this long are flagged as unmaintained
+const UNMAINTAINED_AFTER_DAYS: i64 = 365;
+
 /// Export tools to JSON or TOML
 pub fn cmd_export(
     db: &Database,
@@ -399,6 +402,138 @@ pub fn cmd_doctor(db: &Database, fix: bool) -> Result<()> {
         println!("  {} No duplicate binaries", "✓".green());
     }
 
+    // Check 7: Usage data that may hold command arguments
+    println!("{}", "Checking usage privacy...".dimmed());
+    let usage_config = crate::config::HoardConfig::load()
+        .map(|c| c.usage)
+        .unwrap_or_default();
+    let mut privacy_issues = 0;
+
+    // Subcommands that look like arguments, or of tools no longer opted in
+    let leaked: Vec<_> = db
+        .get_all_subcommand_usage()?
+        .into_iter()
+        .filter(|(tool, binary, sub)| {
+            !crate::cmdline::looks_like_subcommand(sub)
+                || !usage_config.tracks_subcommands(tool, binary)
+        })
+        .collect();
+    if !leaked.is_empty() {
+        println!(
+            "  {} {} subcommand records that shouldn't be stored",
+            "!".yellow(),
+            leaked.len()
+        );
+        privacy_issues += leaked.len();
+        if fix {
+            for (tool, _, sub) in &leaked {
+                db.delete_subcommand_usage(tool, sub)?;
+            }
+            fixed += leaked.len();
+            println!("    {} Deleted {} records", "✓".green(), leaked.len());
+        }
+    }
+
+    // History bytes kept by older versions to resume scans
+    let raw_checkpoints = db.count_raw_history_checkpoints()?;
+    if raw_checkpoints > 0 {
+        println!(
+            "  {} {} history checkpoints store raw history lines",
+            "!".yellow(),
+            raw_checkpoints
+        );
+        privacy_issues += raw_checkpoints;
+        if fix {
+            db.fingerprint_history_checkpoints()?;
+            fixed += raw_checkpoints;
+            println!(
+                "    {} Replaced {} with fingerprints",
+                "✓".green(),
+                raw_checkpoints
+            );
+        }
+    }
+
+    // Usage recorded before a command was ignored
+    let usage: std::collections::HashSet<String> = db
+        .get_all_usage()?
+        .into_iter()
+        .map(|(name, _)| name)
+        .collect();
+    let ignored: Vec<String> = db
+        .get_tool_binaries()?
+        .into_iter()
+        .filter(|(name, binary)| {
+            usage.contains(name)
+                && (usage_config.ignores_command(binary) || usage_config.ignores_command(name))
+        })
+        .map(|(name, _)| name)
+        .collect();
+    if !ignored.is_empty() {
+        println!(
+            "  {} Usage recorded for ignored commands: {}",
+            "!".yellow(),
+            ignored.join(", ")
+        );
+        privacy_issues += ignored.len();
+        if fix {
+            for name in &ignored {
+                db.purge_usage(Some(name), None)?;
+            }
+            fixed += ignored.len();
+            println!("    {} Purged {} tools", "✓".green(), ignored.len());
+        }
+    }
+
+    if privacy_issues == 0 {
+        println!("  {} No command arguments stored", "✓".green());
+    }
+    issues_found += privacy_issues;
+
+    // Check 8: Installed tools whose upstream is archived or inactive
+    println!("{}", "Checking for unmaintained tools...".dimmed());
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(UNMAINTAINED_AFTER_DAYS))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+    let unmaintained = db.get_unmaintained_tools(&cutoff)?;
+
+    if !unmaintained.is_empty() {
+        println!(
+            "  {} {} installed tools look unmaintained:",
+            "!".yellow(),
+            unmaintained.len()
+        );
+        for (name, info) in unmaintained.iter().take(MAX_DISPLAY_ITEMS) {
+            let reason = if info.archived {
+                "archived".to_string()
+            } else {
+                let pushed = info.pushed_at.as_deref().unwrap_or_default();
+                format!("no push since {}", pushed.get(..10).unwrap_or(pushed))
+            };
+            println!(
+                "    {} ({}/{}, {})",
+                name.red(),
+                info.repo_owner,
+                info.repo_name,
+                reason
+            );
+        }
+        if unmaintained.len() > MAX_DISPLAY_ITEMS {
+            println!(
+                "    ... and {} more",
+                unmaintained.len() - MAX_DISPLAY_ITEMS
+            );
+        }
+        issues_found += unmaintained.len();
+        println!(
+            "    {} Data is from the last GitHub sync; refresh with {}",
+            "?".blue(),
+            "hoards gh fetch <tool>".cyan()
+        );
+    } else {
+        println!("  {} No archived or inactive repositories", "✓".green());
+    }
+
     // Summary
     println!();
     if issues_found == 0 {
@@ -35,7 +35,7 @@ pub use sync::{cmd_fetch_descriptions, cmd_scan, cmd_sync_status};
 pub use discover::{cmd_similar, cmd_suggest, cmd_trending};
 
 // Re-export insights commands
-pub use insights::{cmd_categories, cmd_info, cmd_overview, cmd_stats};
+pub use insights::{cmd_categories, cmd_info, cmd_insights_disk, cmd_overview, cmd_stats};
 
 // Re-export workflow commands
 pub use workflow::{cmd_cleanup, cmd_init, cmd_maintain};
@@ -61,13 +61,16 @@ pub use ai::{
 
 // Re-export GitHub commands
 pub use github::{
-    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_info, cmd_gh_rate_limit, cmd_gh_search, cmd_gh_sync,
+    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_import_stars, cmd_gh_info, cmd_gh_link,
+    cmd_gh_rate_limit, cmd_gh_search, cmd_gh_sync, cmd_gh_unlink,
 };
 
 // Re-export usage commands
 pub use usage::{
-    cmd_labels, cmd_recommend, cmd_unused, cmd_usage_config, cmd_usage_init, cmd_usage_log,
-    cmd_usage_reset, cmd_usage_scan, cmd_usage_show, cmd_usage_tool, ensure_usage_configured,
+    cmd_insights_project, cmd_labels, cmd_recommend, cmd_unused, cmd_usage_by_project,
+    cmd_usage_config, cmd_usage_export, cmd_usage_hosts, cmd_usage_import, cmd_usage_init,
+    cmd_usage_log, cmd_usage_purge, cmd_usage_reset, cmd_usage_scan, cmd_usage_show,
+    cmd_usage_subcommands, cmd_usage_tool, ensure_usage_configured,
 };
 
 // Re-export misc commands
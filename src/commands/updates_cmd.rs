@@ -4,8 +4,12 @@ use anyhow::Result;
 use colored::Colorize;
 
 use crate::db::Database;
+use crate::release_notes::release_notes;
 use crate::updates::*;
 
+/// Lines of each release's notes shown before the rest is cut
+const NOTES_PREVIEW_LINES: usize = 12;
+
 /// Check for available updates
 pub fn cmd_updates(
     db: &Database,
@@ -13,6 +17,7 @@ pub fn cmd_updates(
     cross: bool,
     tracked: bool,
     all_versions: bool,
+    notes: bool,
 ) -> Result<()> {
     if cross {
         return cmd_updates_cross(db);
@@ -20,7 +25,7 @@ pub fn cmd_updates(
 
     // If --tracked or --all-versions, use the tracked tools mode
     if tracked || all_versions {
-        return cmd_updates_tracked(db, source_filter, all_versions);
+        return cmd_updates_tracked(db, source_filter, all_versions, notes);
     }
 
     println!("{} Checking for updates...\n", ">".cyan());
@@ -45,6 +50,9 @@ pub fn cmd_updates(
                         update.current.dimmed(),
                         update.latest.green()
                     );
+                    if notes {
+                        print_release_notes(db, update, "      ");
+                    }
                 }
                 Ok(updates.len())
             }
@@ -92,6 +100,7 @@ pub fn cmd_updates_tracked(
     db: &Database,
     source_filter: Option<String>,
     all_versions: bool,
+    notes: bool,
 ) -> Result<()> {
     println!(
         "{} Checking tracked tools for updates{}...\n",
@@ -151,6 +160,15 @@ pub fn cmd_updates_tracked(
                     };
                     println!("    {} {}", ver.green(), marker.dimmed());
                 }
+                if notes && let Some(latest) = versions.last() {
+                    let update = Update {
+                        name: tool.name.clone(),
+                        current,
+                        latest: latest.clone(),
+                        source,
+                    };
+                    print_release_notes(db, &update, "    ");
+                }
             }
         } else {
             // Just check for latest
@@ -172,6 +190,15 @@ pub fn cmd_updates_tracked(
                     current.dimmed(),
                     latest.green()
                 );
+                if notes {
+                    let update = Update {
+                        name: tool.name.clone(),
+                        current,
+                        latest,
+                        source,
+                    };
+                    print_release_notes(db, &update, "    ");
+                }
             }
         }
     }
@@ -196,6 +223,62 @@ pub fn cmd_updates_tracked(
     Ok(())
 }
 
+/// Print the release notes of an update, indented under it
+pub(crate) fn print_release_notes(db: &Database, update: &Update, indent: &str) {
+    let notes = match release_notes(db, update) {
+        Ok(notes) if notes.is_empty() => {
+            println!("{}{}", indent, "No release notes found".dimmed());
+            return;
+        }
+        Ok(notes) => notes,
+        Err(e) => {
+            println!(
+                "{}{} Release notes unavailable: {}",
+                indent,
+                "!".yellow(),
+                e
+            );
+            return;
+        }
+    };
+
+    for release in &notes.releases {
+        let date = release
+            .published_at
+            .as_deref()
+            .map(|d| format!(" ({})", d.get(..10).unwrap_or(d)))
+            .unwrap_or_default();
+        print!(
+            "{}{}{}",
+            indent,
+            release.tag_name.cyan().bold(),
+            date.dimmed()
+        );
+        match release.name.as_deref().map(str::trim) {
+            Some(name) if !name.is_empty() && name != release.tag_name => {
+                println!(" {}", name.bold())
+            }
+            _ => println!(),
+        }
+
+        let body = release.body.as_deref().unwrap_or_default().trim();
+        let lines: Vec<&str> = body.lines().map(str::trim_end).collect();
+        for line in lines.iter().take(NOTES_PREVIEW_LINES) {
+            println!("{}  {}", indent, line);
+        }
+        if lines.len() > NOTES_PREVIEW_LINES {
+            println!(
+                "{}  {}",
+                indent,
+                format!("... {} more lines", lines.len() - NOTES_PREVIEW_LINES).dimmed()
+            );
+        }
+    }
+    if let Some(link) = &notes.link {
+        println!("{}{} {}", indent, "More:".dimmed(), link.cyan());
+    }
+}
+
 /// Check for cross-source upgrade opportunities
 pub fn cmd_updates_cross(db: &Database) -> Result<()> {
     println!(
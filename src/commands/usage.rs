@@ -4,8 +4,10 @@
 
 use anyhow::Result;
 use colored::Colorize;
+use std::path::Path;
 
 use crate::Database;
+use crate::db::{OutcomeStats, format_duration_ms};
 
 /// Show all labels
 pub fn cmd_labels(db: &Database) -> Result<()> {
@@ -46,29 +48,173 @@ pub fn cmd_labels(db: &Database) -> Result<()> {
     Ok(())
 }
 
+/// Uses of a command on one day, with the latest timestamp seen (if any)
+#[derive(Default)]
+struct DayUsage {
+    count: i64,
+    last: Option<i64>,
+}
+
+impl DayUsage {
+    fn add(&mut self, count: i64, timestamp: Option<i64>) {
+        self.count += count;
+        self.last = self.last.max(timestamp);
+    }
+}
+
+/// A history entry's directory, exit status and duration, attributed to one command
+struct EntryDetail {
+    program: String,
+    cwd: Option<String>,
+    status: Option<i32>,
+    duration_ms: Option<u64>,
+    timestamp: i64,
+}
+
 /// Scan shell history for usage data
+///
+/// Reads from the history provider in the usage config (shell files, Atuin or McFly).
+/// Each input is read from where the previous scan stopped, so repeated
+/// scans only count new entries. `reset` clears usage and re-imports everything.
 pub fn cmd_usage_scan(db: &Database, dry_run: bool, reset: bool) -> Result<()> {
-    use crate::history::parse_all_histories;
+    use crate::aliases::Aliases;
+    use crate::cmdline::invocations_with;
+    use crate::config::{HistoryProvider, HoardConfig};
+    use crate::history::history_inputs;
+    use crate::spool::project_context;
+    use std::collections::HashMap;
+
+    let usage_config = HoardConfig::load().map(|c| c.usage).unwrap_or_default();
+    let provider = usage_config.history;
+    println!("{} Scanning {} history...", ">".cyan(), provider);
+
+    let inputs = history_inputs(provider);
+    if inputs.is_empty() {
+        match provider {
+            HistoryProvider::Shell => println!("{} No shell history found", "!".yellow()),
+            _ => println!(
+                "{} No {} database found (change with {})",
+                "!".yellow(),
+                provider,
+                "hoards usage config --history".cyan()
+            ),
+        }
+        return Ok(());
+    }
+
+    // Reset if requested
+    if reset && !dry_run {
+        db.clear_usage()?;
+        db.clear_history_checkpoints()?;
+        println!("{} Cleared existing usage data", ">".cyan());
+    }
 
-    println!("{} Scanning shell history...", ">".cyan());
+    // Attribute `ll` to eza for `alias ll='eza -l'`; aliases are read as they are
+    // now, which is the best guess for past entries too
+    let aliases = Aliases::load();
+
+    // command -> date -> (count, latest timestamp), dated from entry timestamps
+    // where the shell records them and attributed to today otherwise
+    let now = chrono::Utc::now();
+    let today = now.format("%Y-%m-%d").to_string();
+    let mut counts: HashMap<String, HashMap<String, DayUsage>> = HashMap::new();
+    // (command, subcommand) -> uses, for tools opted into subcommand tracking
+    let mut subcommand_counts: HashMap<(String, String), DayUsage> = HashMap::new();
+    // Where and how each command ran, for history providers that record it (Atuin)
+    let mut details: Vec<EntryDetail> = Vec::new();
+    let mut checkpoints = Vec::new();
+    let mut new_entries = 0;
+
+    for input in &inputs {
+        let key = input.path.to_string_lossy().to_string();
+        let previous = if reset {
+            None
+        } else {
+            db.get_history_checkpoint(&key)?
+        };
+
+        let (entries, checkpoint) = match input.read_new_entries(previous.as_ref()) {
+            Ok(read) => read,
+            Err(e) => {
+                eprintln!("{} {}", "!".yellow(), e);
+                continue;
+            }
+        };
 
-    // Parse all shell histories
-    let counts = parse_all_histories()?;
+        new_entries += entries.len();
+        for entry in &entries {
+            if let Some(cwd) = &entry.cwd
+                && usage_config.ignores_directory(Path::new(cwd))
+            {
+                continue;
+            }
+            let when = entry
+                .timestamp
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .filter(|dt| *dt <= now);
+            let date = when.map_or_else(|| today.clone(), |dt| dt.format("%Y-%m-%d").to_string());
+            let timestamp = when.unwrap_or(now).timestamp();
+            let invocations = invocations_with(&entry.command, &aliases);
+            // An exit status or duration belongs to the whole line, so only
+            // counts towards a tool when it's the only one in it
+            let single = invocations.len() == 1;
+            for invocation in invocations
+                .into_iter()
+                .filter(|i| !usage_config.ignores_command(i.program))
+            {
+                if entry.cwd.is_some() || entry.status.is_some() || entry.duration_ms.is_some() {
+                    details.push(EntryDetail {
+                        program: invocation.program.to_string(),
+                        cwd: entry.cwd.clone(),
+                        status: entry.status.filter(|_| single),
+                        duration_ms: entry.duration_ms.filter(|_| single),
+                        timestamp,
+                    });
+                }
+                counts
+                    .entry(invocation.program.to_string())
+                    .or_default()
+                    .entry(date.clone())
+                    .or_default()
+                    .add(1, Some(timestamp));
+                if let Some(sub) = invocation.subcommand
+                    && !usage_config.subcommands.is_empty()
+                {
+                    subcommand_counts
+                        .entry((invocation.program.to_string(), sub.to_string()))
+                        .or_default()
+                        .add(1, Some(timestamp));
+                }
+            }
+        }
+        checkpoints.push((key, checkpoint));
+    }
+
+    let save_checkpoints = || -> Result<()> {
+        if !dry_run {
+            for (key, checkpoint) in &checkpoints {
+                db.set_history_checkpoint(key, checkpoint)?;
+            }
+        }
+        Ok(())
+    };
 
     if counts.is_empty() {
-        println!("{} No shell history found", "!".yellow());
+        save_checkpoints()?;
+        println!("{} No new history entries since the last scan", "+".green());
         return Ok(());
     }
 
     println!(
-        "{} Found {} unique commands in history",
+        "{} Found {} new entries ({} unique commands) in history",
         ">".cyan(),
+        new_entries,
         counts.len()
     );
 
     // Get tool binaries from database for matching
     let tool_binaries = db.get_tool_binaries()?;
-    let binary_to_tool: std::collections::HashMap<String, String> = tool_binaries
+    let binary_to_tool: HashMap<String, String> = tool_binaries
         .iter()
         .map(|(name, binary)| (binary.clone(), name.clone()))
         .collect();
@@ -77,43 +223,46 @@ pub fn cmd_usage_scan(db: &Database, dry_run: bool, reset: bool) -> Result<()> {
     let tool_names: std::collections::HashSet<String> =
         tool_binaries.iter().map(|(name, _)| name.clone()).collect();
 
-    // Reset if requested
-    if reset && !dry_run {
-        db.clear_usage()?;
-        println!("{} Cleared existing usage data", ">".cyan());
-    }
-
     // Match commands to tools
-    let mut matched = 0;
     let mut total_uses = 0i64;
+    let mut tool_days: HashMap<String, HashMap<String, DayUsage>> = HashMap::new();
 
-    let mut tool_counts: Vec<(String, i64)> = Vec::new();
-
-    for (cmd, count) in &counts {
-        // Check if command matches a tool binary or name
-        let tool_name = binary_to_tool.get(cmd).cloned().or_else(|| {
+    // Check if command matches a tool binary or name
+    let tool_for = |cmd: &String| {
+        binary_to_tool.get(cmd).cloned().or_else(|| {
             if tool_names.contains(cmd) {
                 Some(cmd.clone())
             } else {
                 None
             }
-        });
+        })
+    };
 
-        if let Some(name) = tool_name {
-            tool_counts.push((name, *count));
-            matched += 1;
-            total_uses += count;
+    for (cmd, days) in counts {
+        if let Some(name) = tool_for(&cmd) {
+            let merged = tool_days.entry(name).or_default();
+            for (date, day) in days {
+                total_uses += day.count;
+                merged.entry(date).or_default().add(day.count, day.last);
+            }
         }
     }
 
-    // Sort by count descending
-    tool_counts.sort_by(|a, b| b.1.cmp(&a.1));
-
-    if tool_counts.is_empty() {
-        println!("{} No matching tools found in history", "!".yellow());
+    if tool_days.is_empty() {
+        save_checkpoints()?;
+        println!("{} No matching tools found in new history", "!".yellow());
         return Ok(());
     }
 
+    let mut tool_counts: Vec<(String, i64)> = tool_days
+        .iter()
+        .map(|(name, days)| (name.clone(), days.values().map(|d| d.count).sum()))
+        .collect();
+
+    // Sort by count descending
+    tool_counts.sort_by_key(|t| std::cmp::Reverse(t.1));
+
+    let matched = tool_counts.len();
     println!();
     println!(
         "{} Matched {} tool{} ({} total uses):",
@@ -123,24 +272,65 @@ pub fn cmd_usage_scan(db: &Database, dry_run: bool, reset: bool) -> Result<()> {
         total_uses
     );
 
+    if !dry_run {
+        for (name, days) in &tool_days {
+            for (date, day) in days {
+                let last_used = day
+                    .last
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                    .map(|dt| dt.to_rfc3339());
+                db.record_usage_on(name, day.count, last_used.as_deref(), date)?;
+            }
+        }
+
+        for ((cmd, sub), day) in &subcommand_counts {
+            if let Some(name) = tool_for(cmd)
+                && usage_config.tracks_subcommands(&name, cmd)
+            {
+                let last_used = day
+                    .last
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                    .map(|dt| dt.to_rfc3339());
+                db.record_subcommand_usage(&name, sub, day.count, last_used.as_deref())?;
+            }
+        }
+
+        let mut contexts: HashMap<String, (String, Option<String>)> = HashMap::new();
+        for detail in &details {
+            let Some(name) = tool_for(&detail.program) else {
+                continue;
+            };
+            let Some(last_used) =
+                chrono::DateTime::from_timestamp(detail.timestamp, 0).map(|dt| dt.to_rfc3339())
+            else {
+                continue;
+            };
+            db.record_outcome(&name, detail.status, detail.duration_ms, &last_used)?;
+            if let Some(cwd) = &detail.cwd {
+                let (directory, git_root) = contexts.entry(cwd.clone()).or_insert_with(|| {
+                    let (directory, git_root) = project_context(Path::new(cwd));
+                    (
+                        directory.to_string_lossy().to_string(),
+                        git_root.map(|r| r.to_string_lossy().to_string()),
+                    )
+                });
+                db.record_usage_context(&name, directory, git_root.as_deref(), &last_used)?;
+            }
+        }
+    }
+    save_checkpoints()?;
+
     // Show top results
     for (name, count) in tool_counts.iter().take(20) {
         if dry_run {
             println!("  {} {:20} {:>6} uses", "[dry]".yellow(), name, count);
         } else {
-            db.record_usage(name, *count, None)?;
             println!("  {} {:20} {:>6} uses", "+".green(), name, count);
         }
     }
 
     if tool_counts.len() > 20 {
-        let remaining = tool_counts.len() - 20;
-        if !dry_run {
-            for (name, count) in tool_counts.iter().skip(20) {
-                db.record_usage(name, *count, None)?;
-            }
-        }
-        println!("  {} ...and {} more", "".dimmed(), remaining);
+        println!("  {} ...and {} more", "".dimmed(), tool_counts.len() - 20);
     }
 
     println!();
@@ -158,12 +348,17 @@ pub fn cmd_usage_scan(db: &Database, dry_run: bool, reset: bool) -> Result<()> {
 }
 
 /// Show usage statistics
-pub fn cmd_usage_show(db: &Database, limit: usize) -> Result<()> {
+pub fn cmd_usage_show(db: &Database, limit: usize, all_hosts: bool) -> Result<()> {
     use comfy_table::{
         Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
     };
 
-    let usage = db.get_all_usage()?;
+    let hosts = if all_hosts { imported_hosts(db)? } else { 0 };
+    let usage = if all_hosts {
+        db.get_all_usage_all_hosts()?
+    } else {
+        db.get_all_usage()?
+    };
 
     if usage.is_empty() {
         println!(
@@ -218,14 +413,42 @@ pub fn cmd_usage_show(db: &Database, limit: usize) -> Result<()> {
         );
     }
 
-    println!("📈 Total: {} uses across {} tools", total, usage.len());
+    print!("📈 Total: {} uses across {} tools", total, usage.len());
+    match hosts {
+        0 => println!(),
+        1 => println!(" (this machine and 1 other)"),
+        n => println!(" (this machine and {} others)", n),
+    }
 
     Ok(())
 }
 
+/// How many machines usage was imported from, with a hint when there are none
+fn imported_hosts(db: &Database) -> Result<usize> {
+    let hosts = db.get_hosts()?.len();
+    if hosts == 0 {
+        println!(
+            "{} No usage imported from other machines yet. See {}",
+            "!".yellow(),
+            "hoards usage export --help".cyan()
+        );
+    }
+    Ok(hosts)
+}
+
+/// The day part of an RFC 3339 timestamp
+fn day(timestamp: &str) -> &str {
+    timestamp.get(..10).unwrap_or(timestamp)
+}
+
 /// Show usage for a specific tool
-pub fn cmd_usage_tool(db: &Database, name: &str) -> Result<()> {
+pub fn cmd_usage_tool(db: &Database, name: &str, all_hosts: bool) -> Result<()> {
     let usage = db.get_usage(name)?;
+    let hosts = if all_hosts && imported_hosts(db)? > 0 {
+        db.get_tool_usage_by_host(name)?
+    } else {
+        Vec::new()
+    };
 
     match usage {
         Some(stats) => {
@@ -235,6 +458,24 @@ pub fn cmd_usage_tool(db: &Database, name: &str) -> Result<()> {
                 println!("  Last used:  {}", last);
             }
             println!("  First seen: {}", stats.first_seen);
+
+            let subcommands = db.get_subcommand_usage(name)?;
+            if !subcommands.is_empty() {
+                let top: Vec<String> = subcommands
+                    .iter()
+                    .take(5)
+                    .map(|s| format!("{} ({})", s.subcommand, s.use_count))
+                    .collect();
+                println!("  Top subcommands: {}", top.join(", "));
+            }
+
+            if let Some(outcomes) = db.get_outcome_stats(name)? {
+                print_outcome_stats(&outcomes);
+            }
+        }
+        None if !hosts.is_empty() => {
+            println!("{} {}", "Usage for".bold(), name.cyan());
+            println!("  Not used on this machine");
         }
         None => {
             println!("{} No usage data for '{}'", "!".yellow(), name);
@@ -242,17 +483,283 @@ pub fn cmd_usage_tool(db: &Database, name: &str) -> Result<()> {
         }
     }
 
+    if !hosts.is_empty() {
+        println!("  Other machines:");
+        for host in &hosts {
+            print!(
+                "    {}: {} uses, {} in the last 30 days",
+                host.host.cyan(),
+                host.usage.use_count,
+                host.recent
+            );
+            match &host.usage.last_used {
+                Some(last) => println!(" (last used {})", day(last)),
+                None => println!(),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Success rate and duration percentiles captured by the shell hook
+fn print_outcome_stats(outcomes: &OutcomeStats) {
+    if let Some(rate) = outcomes.success_rate() {
+        let rate_str = format!("{:.0}%", rate * 100.0);
+        let rate_str = if outcomes.is_failing() {
+            rate_str.red()
+        } else {
+            rate_str.green()
+        };
+        print!("  Success:    {} of {} runs", rate_str, outcomes.runs);
+        match &outcomes.last_failure {
+            Some(last) => println!(" (last failure {})", last),
+            None => println!(),
+        }
+    }
+    if let (Some(p50), Some(p95)) = (outcomes.p50_ms, outcomes.p95_ms) {
+        println!(
+            "  Duration:   p50 {}, p95 {}",
+            format_duration_ms(p50),
+            format_duration_ms(p95)
+        );
+    }
+}
+
+/// Show a tool's usage broken down by subcommand
+pub fn cmd_usage_subcommands(db: &Database, name: &str, limit: usize) -> Result<()> {
+    use comfy_table::{
+        Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
+    };
+
+    let subcommands = db.get_subcommand_usage(name)?;
+
+    if subcommands.is_empty() {
+        println!("{} No subcommand usage for '{}'", "!".yellow(), name);
+        let tracked = crate::config::HoardConfig::load()
+            .map(|c| c.usage.tracks_subcommands(name, name))
+            .unwrap_or(false);
+        if !tracked {
+            println!(
+                "  Enable it with {}",
+                format!("hoards usage config --subcommands {}", name).cyan()
+            );
+        }
+        return Ok(());
+    }
+
+    let total: i64 = subcommands.iter().map(|s| s.use_count).sum();
+
+    let term_width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0)
+        .unwrap_or(120);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(term_width)
+        .set_header(vec![
+            Cell::new(format!("📊 {} subcommand", name)).fg(Color::Cyan),
+            Cell::new("Uses").fg(Color::Cyan),
+            Cell::new("%").fg(Color::Cyan),
+            Cell::new("Last used").fg(Color::Cyan),
+        ]);
+
+    for sub in subcommands.iter().take(limit) {
+        let percent = (sub.use_count as f64 / total as f64) * 100.0;
+        let last_used = sub
+            .last_used
+            .as_deref()
+            .and_then(|s| s.get(..10))
+            .unwrap_or("-");
+
+        table.add_row(vec![
+            Cell::new(&sub.subcommand),
+            Cell::new(sub.use_count),
+            Cell::new(format!("{:.1}", percent)),
+            Cell::new(last_used),
+        ]);
+    }
+
+    println!("{table}");
+
+    if subcommands.len() > limit {
+        println!(
+            "{} Showing top {} of {} subcommands. Use {} to see more.",
+            ">".cyan(),
+            limit,
+            subcommands.len(),
+            "--limit".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Show which tools each git repository relies on
+pub fn cmd_usage_by_project(db: &Database, limit: usize) -> Result<()> {
+    use comfy_table::{
+        Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
+    };
+
+    let projects = db.get_usage_by_project()?;
+
+    if projects.is_empty() {
+        println!("{} No per-project usage recorded yet", "!".yellow());
+        println!(
+            "  Project context comes from the shell hook: {}",
+            "hoards usage config --mode hook".cyan()
+        );
+        return Ok(());
+    }
+
+    let home = dirs::home_dir();
+    let term_width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0)
+        .unwrap_or(120);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(term_width)
+        .set_header(vec![
+            Cell::new("📁 Project").fg(Color::Cyan),
+            Cell::new("Uses").fg(Color::Cyan),
+            Cell::new("Tools").fg(Color::Cyan),
+        ]);
+
+    for (root, tools) in projects.iter().take(limit) {
+        let total: i64 = tools.iter().map(|t| t.use_count).sum();
+        let top: Vec<String> = tools
+            .iter()
+            .take(8)
+            .map(|t| format!("{} ({})", t.tool, t.use_count))
+            .collect();
+        let more = if tools.len() > 8 {
+            format!(" +{} more", tools.len() - 8)
+        } else {
+            String::new()
+        };
+
+        table.add_row(vec![
+            Cell::new(display_path(root, home.as_deref())),
+            Cell::new(total),
+            Cell::new(format!("{}{}", top.join(", "), more)),
+        ]);
+    }
+
+    println!("{table}");
+
+    if projects.len() > limit {
+        println!(
+            "{} Showing top {} of {} projects. Use {} to see more.",
+            ">".cyan(),
+            limit,
+            projects.len(),
+            "--limit".yellow()
+        );
+    }
+
+    Ok(())
+}
+
+/// Show the tools used in a project (its git repository, or the directory itself)
+pub fn cmd_insights_project(db: &Database, path: &Path) -> Result<()> {
+    use comfy_table::{
+        Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
+    };
+
+    if !path.is_dir() {
+        anyhow::bail!("Not a directory: {}", path.display());
+    }
+
+    let (dir, git_root) = crate::spool::project_context(path);
+    let project = git_root.unwrap_or(dir);
+    let project_str = project.to_string_lossy();
+    let usage = db.get_project_usage(&project_str)?;
+
+    let home = dirs::home_dir();
+    let shown = display_path(&project_str, home.as_deref());
+
+    if usage.is_empty() {
+        println!("{} No usage recorded in {}", "!".yellow(), shown);
+        println!(
+            "  Project context comes from the shell hook: {}",
+            "hoards usage config --mode hook".cyan()
+        );
+        return Ok(());
+    }
+
+    let total: i64 = usage.iter().map(|u| u.use_count).sum();
+    println!(
+        "{} Tools used in {} ({} uses)",
+        ">".cyan(),
+        shown.bold(),
+        total
+    );
+
+    let term_width = terminal_size::terminal_size()
+        .map(|(w, _)| w.0)
+        .unwrap_or(120);
+
+    let mut table = Table::new();
+    table
+        .load_preset(UTF8_FULL)
+        .apply_modifier(UTF8_ROUND_CORNERS)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_width(term_width)
+        .set_header(vec![
+            Cell::new("🔧 Tool").fg(Color::Cyan),
+            Cell::new("Uses").fg(Color::Cyan),
+            Cell::new("%").fg(Color::Cyan),
+            Cell::new("Last used").fg(Color::Cyan),
+        ]);
+
+    for tool in &usage {
+        let percent = (tool.use_count as f64 / total as f64) * 100.0;
+        let last_used = tool
+            .last_used
+            .as_deref()
+            .and_then(|s| s.get(..10))
+            .unwrap_or("-");
+
+        table.add_row(vec![
+            Cell::new(&tool.tool),
+            Cell::new(tool.use_count),
+            Cell::new(format!("{:.1}", percent)),
+            Cell::new(last_used),
+        ]);
+    }
+
+    println!("{table}");
+
     Ok(())
 }
 
+/// Shorten a path under the home directory to `~/...`
+fn display_path(path: &str, home: Option<&Path>) -> String {
+    home.and_then(|home| Path::new(path).strip_prefix(home).ok())
+        .map(|rest| format!("~/{}", rest.display()))
+        .unwrap_or_else(|| path.to_string())
+}
+
 /// Show unused tools
-pub fn cmd_unused(db: &Database) -> Result<()> {
+pub fn cmd_unused(db: &Database, all_hosts: bool) -> Result<()> {
     use crate::icons::source_icon;
     use comfy_table::{
         Cell, Color, ContentArrangement, Table, modifiers::UTF8_ROUND_CORNERS, presets::UTF8_FULL,
     };
 
-    let unused = db.get_unused_tools()?;
+    let unused = if all_hosts {
+        imported_hosts(db)?;
+        db.get_unused_tools_all_hosts()?
+    } else {
+        db.get_unused_tools()?
+    };
 
     if unused.is_empty() {
         println!("{} All installed tools have been used!", "✓".green());
@@ -263,7 +770,14 @@ pub fn cmd_unused(db: &Database) -> Result<()> {
         return Ok(());
     }
 
-    println!("{}", "🗑 Installed tools with no recorded usage:".bold());
+    if all_hosts {
+        println!(
+            "{}",
+            "🗑 Installed tools with no recorded usage on any machine:".bold()
+        );
+    } else {
+        println!("{}", "🗑 Installed tools with no recorded usage:".bold());
+    }
     println!();
 
     let term_width = terminal_size::terminal_size()
@@ -306,6 +820,17 @@ pub fn cmd_unused(db: &Database) -> Result<()> {
         "hoards uninstall <tool>".cyan()
     );
 
+    if !all_hosts && !db.get_hosts()?.is_empty() {
+        let used_elsewhere = unused.len() - db.get_unused_tools_all_hosts()?.len();
+        if used_elsewhere > 0 {
+            println!(
+                "  {} of them are used on other machines (see {})",
+                used_elsewhere,
+                "--all-hosts".yellow()
+            );
+        }
+    }
+
     Ok(())
 }
 
@@ -336,7 +861,7 @@ pub fn cmd_recommend(db: &Database, count: usize) -> Result<()> {
 
     // Sort categories by usage
     let mut cats: Vec<_> = category_scores.into_iter().collect();
-    cats.sort_by(|a, b| b.1.cmp(&a.1));
+    cats.sort_by_key(|c| std::cmp::Reverse(c.1));
 
     if cats.is_empty() {
         println!("{} Not enough data for recommendations", "!".yellow());
@@ -400,39 +925,82 @@ pub fn cmd_recommend(db: &Database, count: usize) -> Result<()> {
 }
 
 /// Log a single command usage (for shell hooks)
-/// This is called by shell preexec hooks and must be fast and silent
-pub fn cmd_usage_log(db: &Database, command: &str) -> Result<()> {
-    use crate::history::extract_command;
-
-    // Extract base command (handles sudo, env vars, etc.)
-    let cmd = match extract_command(command) {
-        Some(c) => c,
-        None => return Ok(()),
-    };
+///
+/// This runs after every shell command, so it only appends to the usage spool;
+/// the next `hoards` invocation folds it into the database.
+pub fn cmd_usage_log(
+    command: &str,
+    cwd: Option<&Path>,
+    status: Option<i32>,
+    duration_ms: Option<u64>,
+) -> Result<()> {
+    use crate::config::HoardConfig;
+    use crate::spool::{SpoolEntry, cached_aliases, cached_tool_commands};
 
-    if cmd.is_empty() {
+    // Hooks that log after the command also fire on empty prompts
+    if command.trim().is_empty() {
         return Ok(());
     }
 
-    // Fast lookup: is this a tracked tool?
-    if let Some(tool_name) = db.match_command_to_tool(cmd)? {
-        let now = chrono::Utc::now().to_rfc3339();
-        db.record_usage(&tool_name, 1, Some(&now))?;
+    let cwd = cwd
+        .map(Path::to_path_buf)
+        .or_else(|| std::env::current_dir().ok());
+
+    let config = HoardConfig::load().map(|c| c.usage).unwrap_or_default();
+    if cwd
+        .as_deref()
+        .is_some_and(|dir| config.ignores_directory(dir))
+    {
+        return Ok(());
     }
 
-    Ok(())
+    // The line itself is never written out, only the programs it ran
+    let Some(mut entry) =
+        SpoolEntry::from_line(command, status, duration_ms, &config, &cached_aliases())
+    else {
+        return Ok(());
+    };
+    // Programs that match no tool (a password typed at the prompt) never reach the spool
+    let Some(tools) = cached_tool_commands() else {
+        return Ok(());
+    };
+    entry.commands.retain(|c| tools.contains(&c.program));
+    if entry.commands.is_empty() {
+        return Ok(());
+    }
+    entry.cwd = cwd.map(|dir| dir.to_string_lossy().to_string());
+    crate::spool::append(&entry)
 }
 
 /// Detect the current shell from environment
 fn detect_shell() -> String {
-    // Try SHELL env var first
+    // Shells that set a marker variable for their child processes; these win over
+    // $SHELL, which only names the login shell
+    if std::env::var_os("NU_VERSION").is_some() {
+        return "nushell".to_string();
+    }
+    if std::env::var_os("XONSH_VERSION").is_some() {
+        return "xonsh".to_string();
+    }
+    if std::env::var_os("PSModulePath").is_some() && !cfg!(windows) {
+        return "powershell".to_string();
+    }
+
+    // Try SHELL env var next
     if let Ok(shell) = std::env::var("SHELL") {
-        if shell.contains("fish") {
-            return "fish".to_string();
-        } else if shell.contains("zsh") {
-            return "zsh".to_string();
-        } else if shell.contains("bash") {
-            return "bash".to_string();
+        let name = std::path::Path::new(&shell)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match name.as_str() {
+            "fish" => return "fish".to_string(),
+            "zsh" => return "zsh".to_string(),
+            "bash" => return "bash".to_string(),
+            "nu" => return "nushell".to_string(),
+            "elvish" => return "elvish".to_string(),
+            "xonsh" => return "xonsh".to_string(),
+            "pwsh" | "powershell" => return "powershell".to_string(),
+            _ => {}
         }
     }
 
@@ -440,6 +1008,197 @@ fn detect_shell() -> String {
     "bash".to_string()
 }
 
+/// Real-time usage hook for one shell
+struct ShellHook {
+    /// Config file the hook is appended to
+    config_path: std::path::PathBuf,
+    /// The same path as shown to users
+    display_path: &'static str,
+    /// Snippet calling `hoards usage log` after each command
+    code: &'static str,
+    /// How to load the hook without restarting
+    reload: &'static str,
+}
+
+/// Hook snippet and config location for a shell
+fn shell_hook(shell: &str) -> Option<ShellHook> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let config = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
+
+    let hook = match shell {
+        "fish" => ShellHook {
+            config_path: home.join(".config/fish/config.fish"),
+            display_path: "~/.config/fish/config.fish",
+            code: r#"
+# Hoards usage tracking (added by hoards)
+function __hoard_log --on-event fish_postexec
+    set -l exit_status $status
+    test -n "$argv[1]"; or return
+    command hoards usage log --cwd "$PWD" --status $exit_status --duration-ms "$CMD_DURATION" "$argv[1]" &>/dev/null &
+    disown 2>/dev/null
+end
+"#,
+            reload: "source ~/.config/fish/config.fish",
+        },
+        "zsh" => ShellHook {
+            config_path: home.join(".zshrc"),
+            display_path: "~/.zshrc",
+            code: r#"
+# Hoards usage tracking (added by hoards)
+zmodload zsh/datetime
+__hoards_preexec() { __hoards_cmd=$1; __hoards_start=$EPOCHREALTIME }
+__hoards_precmd() {
+    local exit_status=$?
+    [[ -n $__hoards_cmd ]] || return
+    local -i ms=$(( (EPOCHREALTIME - __hoards_start) * 1000 ))
+    command hoards usage log --cwd "$PWD" --status $exit_status --duration-ms $ms "$__hoards_cmd" &>/dev/null &!
+    unset __hoards_cmd
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook preexec __hoards_preexec
+add-zsh-hook precmd __hoards_precmd
+"#,
+            reload: "source ~/.zshrc",
+        },
+        "bash" => ShellHook {
+            config_path: home.join(".bashrc"),
+            display_path: "~/.bashrc (after sourcing bash-preexec)",
+            code: r#"
+# Hoards usage tracking (added by hoards)
+[[ -f ~/.bash-preexec.sh ]] && source ~/.bash-preexec.sh
+__hoards_preexec() { __hoards_cmd=$1; __hoards_start=${EPOCHREALTIME/[.,]/}; }
+__hoards_precmd() {
+    local exit_status=$? now=${EPOCHREALTIME/[.,]/}
+    [[ -n $__hoards_cmd ]] || return
+    # EPOCHREALTIME (bash 5+) is in microseconds once the decimal point is dropped
+    (command hoards usage log --cwd "$PWD" --status "$exit_status" \
+        ${now:+--duration-ms $(( (now - __hoards_start) / 1000 ))} "$__hoards_cmd" &>/dev/null &)
+    unset __hoards_cmd
+}
+preexec_functions+=(__hoards_preexec)
+precmd_functions+=(__hoards_precmd)
+"#,
+            reload: "source ~/.bashrc",
+        },
+        "nushell" => ShellHook {
+            config_path: config.join("nushell").join("config.nu"),
+            display_path: "$nu.config-path",
+            code: r#"
+# Hoards usage tracking (added by hoards)
+$env.config.hooks.pre_execution = ($env.config.hooks.pre_execution? | default [] | append {||
+    $env.__HOARDS_CMD = (commandline)
+})
+$env.config.hooks.pre_prompt = ($env.config.hooks.pre_prompt? | default [] | append {||
+    let cmd = ($env.__HOARDS_CMD? | default "")
+    if ($cmd | str trim | is-not-empty) {
+        ^sh -c 'hoards usage log --cwd "$1" --status "$2" --duration-ms "$3" "$4" >/dev/null 2>&1 &' hoards $env.PWD $env.LAST_EXIT_CODE $env.CMD_DURATION_MS $cmd
+    }
+    $env.__HOARDS_CMD = ""
+})
+"#,
+            reload: "exec nu",
+        },
+        "elvish" => ShellHook {
+            config_path: config.join("elvish").join("rc.elv"),
+            display_path: "~/.config/elvish/rc.elv",
+            code: r#"
+# Hoards usage tracking (added by hoards)
+set edit:after-command = [$@edit:after-command {|m|
+    var cmd = $m[src][code]
+    if (not-eq $cmd '') {
+        var code = 0
+        if (not-eq $m[error] $nil) {
+            set code = 1
+            try { set code = $m[error][reason][exit-status] } catch { }
+        }
+        var ms = (printf '%.0f' (* $m[duration] 1000))
+        e:sh -c 'hoards usage log --cwd "$1" --status "$2" --duration-ms "$3" "$4" >/dev/null 2>&1 &' hoards $pwd $code $ms $cmd
+    }
+}]
+"#,
+            reload: "exec elvish",
+        },
+        "xonsh" => ShellHook {
+            config_path: home.join(".xonshrc"),
+            display_path: "~/.xonshrc",
+            code: r#"
+# Hoards usage tracking (added by hoards)
+@events.on_postcommand
+def __hoards_log(cmd, rtn, out, ts, **kwargs):
+    import os, subprocess
+    if cmd.strip():
+        args = ["hoards", "usage", "log", "--cwd", os.getcwd()]
+        if rtn is not None:
+            args += ["--status", str(rtn)]
+        if ts and ts[1] is not None:
+            args += ["--duration-ms", str(int((ts[1] - ts[0]) * 1000))]
+        subprocess.Popen(args + [cmd.strip()],
+                         stdout=subprocess.DEVNULL, stderr=subprocess.DEVNULL)
+"#,
+            reload: "source ~/.xonshrc",
+        },
+        "powershell" => ShellHook {
+            config_path: if cfg!(windows) {
+                dirs::document_dir()
+                    .unwrap_or_default()
+                    .join("PowerShell")
+                    .join("Microsoft.PowerShell_profile.ps1")
+            } else {
+                config
+                    .join("powershell")
+                    .join("Microsoft.PowerShell_profile.ps1")
+            },
+            display_path: "$PROFILE",
+            code: r#"
+# Hoards usage tracking (added by hoards)
+$global:__hoardsPrompt = $function:prompt
+function global:prompt {
+    $succeeded = $?
+    $exitCode = $global:LASTEXITCODE
+    $last = Get-History -Count 1
+    if ($last -and $last.Id -ne $global:__hoardsLastId) {
+        $global:__hoardsLastId = $last.Id
+        $code = if ($succeeded) { 0 } elseif ($exitCode) { $exitCode } else { 1 }
+        $ms = [long]($last.EndExecutionTime - $last.StartExecutionTime).TotalMilliseconds
+        $psi = [System.Diagnostics.ProcessStartInfo]::new('hoards')
+        foreach ($arg in 'usage', 'log', '--cwd', $PWD.ProviderPath, '--status', $code, '--duration-ms', $ms, $last.CommandLine) {
+            $psi.ArgumentList.Add([string]$arg)
+        }
+        $psi.UseShellExecute = $false
+        $psi.RedirectStandardOutput = $true
+        $psi.RedirectStandardError = $true
+        [void][System.Diagnostics.Process]::Start($psi)
+    }
+    # Run the prompt that was configured before
+    $global:LASTEXITCODE = $exitCode
+    & $global:__hoardsPrompt
+}
+"#,
+            reload: ". $PROFILE",
+        },
+        _ => return None,
+    };
+
+    Some(hook)
+}
+
+/// The bash hook snippet (needs bash-preexec)
+fn bash_hook_code() -> &'static str {
+    shell_hook("bash").map(|hook| hook.code).unwrap_or_default()
+}
+
+/// Whether an installed hook predates exit status and duration capture
+fn hook_is_outdated(config: &str) -> bool {
+    config.contains("hoards") && config.contains("usage") && !config.contains("--status")
+}
+
+fn warn_outdated_hook() {
+    println!(
+        "{} The hook predates exit status and duration tracking; replace it with:",
+        "!".yellow()
+    );
+}
+
 /// Offer to set up shell hook automatically, or print manual instructions
 fn print_hook_instructions(shell: &str) {
     // For bash, the setup is handled by offer_bash_preexec_install
@@ -447,7 +1206,7 @@ fn print_hook_instructions(shell: &str) {
         return;
     }
 
-    // For fish and zsh, offer automatic setup
+    // For other shells, offer automatic setup
     if let Err(e) = offer_shell_hook_setup(shell) {
         // If interactive setup fails (e.g., not a terminal), show manual instructions
         eprintln!("{} Could not run interactive setup: {}", "!".yellow(), e);
@@ -455,45 +1214,20 @@ fn print_hook_instructions(shell: &str) {
     }
 }
 
-/// Offer automatic shell hook setup for fish/zsh
+/// Offer automatic shell hook setup (every supported shell except bash)
 fn offer_shell_hook_setup(shell: &str) -> Result<()> {
     use dialoguer::Confirm;
 
-    let home = dirs::home_dir().unwrap_or_default();
-
-    let (config_path, hook_code) = match shell {
-        "fish" => {
-            let path = home.join(".config/fish/config.fish");
-            let code = r#"
-# Hoards usage tracking (added by hoards)
-function __hoard_log --on-event fish_preexec
-    command hoards usage log "$argv[1]" &>/dev/null &
-    disown 2>/dev/null
-end
-"#;
-            (path, code)
-        }
-        "zsh" => {
-            let path = home.join(".zshrc");
-            let code = r#"
-# Hoards usage tracking (added by hoards)
-preexec() { command hoards usage log "$1" &>/dev/null & }
-"#;
-            (path, code)
-        }
-        _ => {
-            println!("{} Unsupported shell: {}", "!".yellow(), shell);
-            return Ok(());
-        }
+    let Some(hook) = shell_hook(shell) else {
+        println!("{} Unsupported shell: {}", "!".yellow(), shell);
+        return Ok(());
     };
+    let config_path = &hook.config_path;
 
     // Check if hook is already installed
-    let hook_installed = if config_path.exists() {
-        let content = std::fs::read_to_string(&config_path).unwrap_or_default();
-        content.contains("hoards usage log")
-    } else {
-        false
-    };
+    let content = std::fs::read_to_string(config_path).unwrap_or_default();
+    let hook_installed =
+        content.contains("hoards usage log") || content.contains("Hoards usage tracking");
 
     if hook_installed {
         println!();
@@ -502,6 +1236,10 @@ preexec() { command hoards usage log "$1" &>/dev/null & }
             "+".green(),
             config_path
         );
+        if hook_is_outdated(&content) {
+            warn_outdated_hook();
+            print_manual_hook_instructions(shell);
+        }
         return Ok(());
     }
 
@@ -516,7 +1254,7 @@ preexec() { command hoards usage log "$1" &>/dev/null & }
         return Ok(());
     }
 
-    // Ensure parent directory exists (for fish)
+    // Ensure parent directory exists (for fish, nushell, elvish, powershell)
     if let Some(parent) = config_path.parent() {
         std::fs::create_dir_all(parent)?;
     }
@@ -527,23 +1265,18 @@ preexec() { command hoards usage log "$1" &>/dev/null & }
     let mut file = std::fs::OpenOptions::new()
         .create(true)
         .append(true)
-        .open(&config_path)?;
+        .open(config_path)?;
 
     use std::io::Write;
-    file.write_all(hook_code.as_bytes())?;
+    file.write_all(hook.code.as_bytes())?;
 
     println!("{} Hook added successfully!", "+".green());
     println!();
 
-    let source_cmd = match shell {
-        "fish" => "source ~/.config/fish/config.fish".to_string(),
-        _ => format!("source ~/.{}rc", shell),
-    };
-
     println!(
         "{} Restart your shell or run: {}",
         ">".cyan(),
-        source_cmd.yellow()
+        hook.reload.yellow()
     );
 
     Ok(())
@@ -551,48 +1284,21 @@ preexec() { command hoards usage log "$1" &>/dev/null & }
 
 /// Print manual hook setup instructions
 fn print_manual_hook_instructions(shell: &str) {
+    let Some(hook) = shell_hook(shell) else {
+        println!("{} Unsupported shell: {}", "!".yellow(), shell);
+        return;
+    };
+
     println!();
     println!("{} Add this to your shell config:", ">".cyan());
     println!();
-
-    match shell {
-        "fish" => {
-            println!("{}", "# Add to ~/.config/fish/config.fish".dimmed());
-            println!(r#"function __hoard_log --on-event fish_preexec"#);
-            println!(r#"    command hoards usage log "$argv[1]" &>/dev/null &"#);
-            println!(r#"    disown 2>/dev/null"#);
-            println!(r#"end"#);
-        }
-        "zsh" => {
-            println!("{}", "# Add to ~/.zshrc".dimmed());
-            println!(r#"preexec() {{"#);
-            println!(r#"    command hoards usage log "$1" &>/dev/null &"#);
-            println!(r#"}}"#);
-        }
-        "bash" => {
-            println!(
-                "{}",
-                "# Add to ~/.bashrc (after sourcing bash-preexec)".dimmed()
-            );
-            println!(r#"[[ -f ~/.bash-preexec.sh ]] && source ~/.bash-preexec.sh"#);
-            println!(r#"preexec() {{"#);
-            println!(r#"    command hoards usage log "$1" &>/dev/null &"#);
-            println!(r#"}}"#);
-        }
-        _ => {
-            println!("{} Unsupported shell: {}", "!".yellow(), shell);
-        }
-    }
-
+    println!("{}", format!("# Add to {}", hook.display_path).dimmed());
+    println!("{}", hook.code.trim());
     println!();
-    let source_cmd = match shell {
-        "fish" => "source ~/.config/fish/config.fish",
-        _ => &format!("source ~/.{}rc", shell),
-    };
     println!(
         "{} After adding, restart your shell or run: {}",
         ">".cyan(),
-        source_cmd.yellow()
+        hook.reload.yellow()
     );
 }
 
@@ -632,8 +1338,102 @@ pub fn cmd_usage_init(
 pub fn cmd_usage_config(
     config: &mut crate::config::HoardConfig,
     mode: Option<String>,
+    history: Option<String>,
+    subcommands: Option<Vec<String>>,
+    ignore_commands: Option<Vec<String>>,
+    ignore_dirs: Option<Vec<String>>,
 ) -> Result<()> {
-    use crate::config::UsageMode;
+    use crate::config::{HistoryProvider, UsageMode};
+
+    let changed = history.is_some()
+        || subcommands.is_some()
+        || ignore_commands.is_some()
+        || ignore_dirs.is_some();
+
+    if let Some(history) = history {
+        let provider = match history.as_str() {
+            "shell" => HistoryProvider::Shell,
+            "atuin" => HistoryProvider::Atuin,
+            "mcfly" => HistoryProvider::Mcfly,
+            _ => anyhow::bail!(
+                "Invalid history source '{}'. Use 'shell', 'atuin' or 'mcfly'.",
+                history
+            ),
+        };
+
+        if crate::history::history_inputs(provider).is_empty() {
+            println!(
+                "{} No {} history found on this machine yet",
+                "!".yellow(),
+                provider
+            );
+        }
+        config.usage.history = provider;
+        println!(
+            "{} History source: {}",
+            ">".cyan(),
+            provider.to_string().cyan()
+        );
+
+        // Start fresh so the new source isn't counted on top of the old one's checkpoints
+        println!(
+            "  Run {} to re-import usage from it",
+            "hoards usage scan --reset".yellow()
+        );
+    }
+
+    if let Some(tools) = subcommands {
+        let tools = config_list(tools);
+        if tools.is_empty() {
+            println!("{} Subcommand tracking disabled", ">".cyan());
+        } else {
+            println!(
+                "{} Tracking subcommands for: {}",
+                ">".cyan(),
+                tools.join(", ").cyan()
+            );
+        }
+        config.usage.subcommands = tools;
+    }
+
+    if let Some(patterns) = ignore_commands {
+        let patterns = config_list(patterns);
+        if patterns.is_empty() {
+            println!("{} No commands ignored", ">".cyan());
+        } else {
+            println!(
+                "{} Ignoring commands: {}",
+                ">".cyan(),
+                patterns.join(", ").cyan()
+            );
+            // Only new commands are filtered
+            println!(
+                "  Forget what was already recorded with {}",
+                "hoards usage purge --tool <name>".yellow()
+            );
+        }
+        config.usage.ignore_commands = patterns;
+    }
+
+    if let Some(patterns) = ignore_dirs {
+        let patterns = config_list(patterns);
+        if patterns.is_empty() {
+            println!("{} No directories ignored", ">".cyan());
+        } else {
+            println!(
+                "{} Ignoring commands run in: {}",
+                ">".cyan(),
+                patterns.join(", ").cyan()
+            );
+        }
+        config.usage.ignore_dirs = patterns;
+    }
+
+    if changed && mode.is_none() {
+        config.save()?;
+        println!("{} Configuration saved.", "+".green());
+        return Ok(());
+    }
 
     match mode {
         None => {
@@ -643,16 +1443,36 @@ pub fn cmd_usage_config(
 
             match config.usage.mode {
                 UsageMode::Scan => {
-                    println!("  Mode:  {} (manual)", "scan".cyan());
-                    println!("  Info:  Run 'hoards usage scan' periodically");
+                    println!("  Mode:    {} (manual)", "scan".cyan());
+                    println!("  History: {}", config.usage.history.to_string().cyan());
+                    println!("  Info:    Run 'hoards usage scan' periodically");
                 }
                 UsageMode::Hook => {
                     let shell = config.usage.shell.as_deref().unwrap_or("unknown");
-                    println!("  Mode:  {} (automatic)", "hook".cyan());
-                    println!("  Shell: {}", shell.cyan());
-                    println!("  Info:  Commands tracked in real-time via shell hook");
+                    println!("  Mode:    {} (automatic)", "hook".cyan());
+                    println!("  Shell:   {}", shell.cyan());
+                    println!("  Info:    Commands tracked in real-time via shell hook");
                 }
             }
+
+            if !config.usage.subcommands.is_empty() {
+                println!(
+                    "  Subcommands tracked for: {}",
+                    config.usage.subcommands.join(", ").cyan()
+                );
+            }
+            if !config.usage.ignore_commands.is_empty() {
+                println!(
+                    "  Ignored commands: {}",
+                    config.usage.ignore_commands.join(", ").cyan()
+                );
+            }
+            if !config.usage.ignore_dirs.is_empty() {
+                println!(
+                    "  Ignored directories: {}",
+                    config.usage.ignore_dirs.join(", ").cyan()
+                );
+            }
         }
         Some(new_mode) => {
             // Change mode (doesn't reset counters)
@@ -711,15 +1531,15 @@ fn offer_bash_preexec_install() -> Result<()> {
     let preexec_exists = preexec_path.exists();
 
     // Check if hook is already in .bashrc
-    let hook_installed = if bashrc_path.exists() {
-        let content = std::fs::read_to_string(&bashrc_path).unwrap_or_default();
-        content.contains("hoards usage log")
-    } else {
-        false
-    };
+    let content = std::fs::read_to_string(&bashrc_path).unwrap_or_default();
+    let hook_installed = content.contains("hoards usage log");
 
     if preexec_exists && hook_installed {
         println!("{} bash-preexec and hook already configured.", "+".green());
+        if hook_is_outdated(&content) {
+            warn_outdated_hook();
+            print_manual_hook_instructions("bash");
+        }
         return Ok(());
     }
 
@@ -740,8 +1560,7 @@ fn offer_bash_preexec_install() -> Result<()> {
         );
         println!();
         println!("2. Add to ~/.bashrc:");
-        println!("   [[ -f ~/.bash-preexec.sh ]] && source ~/.bash-preexec.sh");
-        println!("   preexec() {{ command hoards usage log \"$1\" &>/dev/null & }}");
+        println!("{}", bash_hook_code().trim());
         println!();
         return Ok(());
     }
@@ -764,12 +1583,7 @@ fn offer_bash_preexec_install() -> Result<()> {
     if !hook_installed {
         println!("{} Adding hook to ~/.bashrc...", ">".cyan());
 
-        let hook_code = r#"
-
-# Hoards usage tracking (added by hoards)
-[[ -f ~/.bash-preexec.sh ]] && source ~/.bash-preexec.sh
-preexec() { command hoards usage log "$1" &>/dev/null & }
-"#;
+        let hook_code = format!("\n{}", bash_hook_code());
 
         let mut file = std::fs::OpenOptions::new()
             .create(true)
@@ -794,6 +1608,18 @@ preexec() { command hoards usage log "$1" &>/dev/null & }
     Ok(())
 }
 
+/// A comma-separated config list from the command line, "none" clearing it
+fn config_list(values: Vec<String>) -> Vec<String> {
+    let mut values: Vec<String> = values
+        .into_iter()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty() && v != "none")
+        .collect();
+    values.sort();
+    values.dedup();
+    values
+}
+
 /// Reset all usage counters to zero
 pub fn cmd_usage_reset(db: &Database, force: bool) -> Result<()> {
     use dialoguer::Confirm;
@@ -815,6 +1641,171 @@ pub fn cmd_usage_reset(db: &Database, force: bool) -> Result<()> {
     Ok(())
 }
 
+/// Write this machine's usage to a snapshot for `usage import` elsewhere
+pub fn cmd_usage_export(db: &Database, output: Option<&Path>, host: Option<&str>) -> Result<()> {
+    use anyhow::Context;
+
+    let host = host
+        .map(str::to_string)
+        .or_else(crate::history::local_hostname)
+        .context("Couldn't determine this machine's host name; pass --host")?;
+
+    let snapshot = db.export_usage_snapshot(&host)?;
+    let content = serde_json::to_string_pretty(&snapshot)?;
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, content + "\n")?;
+            println!(
+                "{} Exported usage of {} tools from {} to {}",
+                "+".green(),
+                snapshot.tools.len(),
+                host.cyan(),
+                path.display().to_string().cyan()
+            );
+        }
+        None => println!("{}", content),
+    }
+
+    Ok(())
+}
+
+/// Import another machine's usage snapshot
+pub fn cmd_usage_import(db: &Database, file: &Path, force: bool) -> Result<()> {
+    use crate::db::UsageSnapshot;
+    use anyhow::Context;
+    use std::io::Read;
+
+    let content = if file == Path::new("-") {
+        let mut content = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        content
+    } else {
+        std::fs::read_to_string(file)
+            .with_context(|| format!("Failed to read {}", file.display()))?
+    };
+    let snapshot: UsageSnapshot = serde_json::from_str(&content)
+        .context("Not a usage snapshot from `hoards usage export`")?;
+
+    // Counting this machine's own usage twice would skew every merged view
+    if crate::history::local_hostname().as_deref() == Some(snapshot.host.as_str()) {
+        anyhow::bail!(
+            "This snapshot was exported on this machine ({}); its usage is already counted here",
+            snapshot.host
+        );
+    }
+
+    let previous = db
+        .get_hosts()?
+        .into_iter()
+        .find(|h| h.host == snapshot.host);
+    let count = db.import_usage_snapshot(&snapshot, force)?;
+
+    println!(
+        "{} Imported usage of {} tools from {} (exported {})",
+        "+".green(),
+        count,
+        snapshot.host.cyan(),
+        day(&snapshot.exported_at)
+    );
+    if let Some(previous) = previous {
+        println!(
+            "  Replaced the snapshot exported {}",
+            day(&previous.exported_at)
+        );
+    }
+    println!(
+        "  See merged usage with {}",
+        "hoards insights usage --all-hosts".cyan()
+    );
+
+    Ok(())
+}
+
+/// List, or forget, machines whose usage was imported
+pub fn cmd_usage_hosts(db: &Database, remove: Option<&str>) -> Result<()> {
+    if let Some(host) = remove {
+        if db.delete_host_usage(host)? {
+            println!("{} Forgot usage imported from {}", "+".green(), host.cyan());
+        } else {
+            println!("{} No usage imported from '{}'", "!".yellow(), host);
+        }
+        return Ok(());
+    }
+
+    let hosts = db.get_hosts()?;
+    if hosts.is_empty() {
+        println!("{} No usage imported from other machines", "!".yellow());
+        println!(
+            "  Run {} there, then {} here",
+            "hoards usage export -o usage.json".cyan(),
+            "hoards usage import usage.json".cyan()
+        );
+        return Ok(());
+    }
+
+    println!("{}", "Imported machines".bold());
+    println!("{}", "-".repeat(40));
+    for host in &hosts {
+        println!(
+            "  {}: {} uses of {} tools (exported {}, imported {})",
+            host.host.cyan(),
+            host.uses,
+            host.tools,
+            day(&host.exported_at),
+            day(&host.imported_at)
+        );
+    }
+
+    Ok(())
+}
+
+/// Forget recorded usage of a tool and/or since a date
+pub fn cmd_usage_purge(
+    db: &Database,
+    tool: Option<&str>,
+    since: Option<&str>,
+    force: bool,
+) -> Result<()> {
+    use dialoguer::Confirm;
+
+    if let Some(since) = since
+        && chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_err()
+    {
+        anyhow::bail!("Invalid date '{}'. Use YYYY-MM-DD.", since);
+    }
+    if let Some(tool) = tool
+        && db.get_tool_by_name(tool)?.is_none()
+    {
+        anyhow::bail!("Tool '{}' not found", tool);
+    }
+
+    let what = match (tool, since) {
+        (Some(tool), Some(since)) => format!("usage of {} since {}", tool, since),
+        (Some(tool), None) => format!("all usage of {}", tool),
+        (None, Some(since)) => format!("all usage since {}", since),
+        (None, None) => anyhow::bail!("Pass --tool and/or --since"),
+    };
+
+    if !force {
+        let confirm = Confirm::new()
+            .with_prompt(format!("Forget {}?", what))
+            .default(false)
+            .interact()?;
+
+        if !confirm {
+            println!("{} Cancelled.", "!".yellow());
+            return Ok(());
+        }
+    }
+
+    // Fold in anything spooled while the prompt was up, so it goes too
+    crate::spool::ingest(db)?;
+    let removed = db.purge_usage(tool, since)?;
+    println!("{} Forgot {} ({} uses)", "+".green(), what, removed);
+    Ok(())
+}
+
 /// Ensure usage tracking is configured (interactive setup if not)
 /// With JSON config, mode defaults to Scan - this function offers the user
 /// a chance to switch to Hook mode during initial setup
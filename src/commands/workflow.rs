@@ -4,6 +4,8 @@ use anyhow::Result;
 use colored::Colorize;
 
 use crate::db::Database;
+use crate::disk::format_bytes;
+use crate::sources::all_sources;
 
 use super::completions::cmd_completions_install;
 use super::github::cmd_gh_sync;
@@ -101,7 +103,7 @@ pub fn cmd_maintain(db: &Database, auto: bool, dry_run: bool) -> Result<()> {
 
     // Step 2: Check for updates
     println!("\n{} Checking for updates...", "2.".bold());
-    cmd_updates(db, None, false, true, false)?;
+    cmd_updates(db, None, false, true, false, false)?;
 
     // Step 3: Scan usage
     println!("\n{} Scanning shell history for usage...", "3.".bold());
@@ -122,8 +124,79 @@ pub fn cmd_maintain(db: &Database, auto: bool, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
+/// Report reclaimable cache space per package manager, purging it when asked to
+///
+/// Purging can remove OS packages (apt autoremove), so each manager is
+/// confirmed on its own and `--force` never skips that.
+fn cleanup_caches(purge: bool, dry_run: bool) -> Result<()> {
+    let sources = all_sources();
+    // Each probe shells out (and may walk large directories), so measure in parallel
+    let caches: Vec<_> = std::thread::scope(|s| {
+        let handles: Vec<_> = sources
+            .iter()
+            .map(|source| s.spawn(move || (source, source.cache_usage())))
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|h| h.join().ok())
+            .filter_map(|(source, usage)| usage.filter(|u| u.bytes > 0).map(|u| (source, u)))
+            .collect()
+    });
+
+    if caches.is_empty() {
+        println!("   {} Nothing to reclaim", "+".green());
+        return Ok(());
+    }
+
+    let total: u64 = caches.iter().map(|(_, u)| u.bytes).sum();
+    for (source, usage) in &caches {
+        println!(
+            "   {} {:<8} {:>10}  {}",
+            "-".yellow(),
+            source.name(),
+            format_bytes(usage.bytes),
+            usage.detail.dimmed()
+        );
+    }
+    println!("   {} reclaimable in total", format_bytes(total).bold());
+
+    if dry_run {
+        return Ok(());
+    }
+    if !purge {
+        println!(
+            "   Run {} to reclaim it",
+            "hoards cleanup --purge-caches".cyan()
+        );
+        return Ok(());
+    }
+
+    for (source, usage) in &caches {
+        let prompt = format!(
+            "Purge {} cache ({}, runs: {})?",
+            source.name(),
+            format_bytes(usage.bytes),
+            usage.purge_display
+        );
+        if !confirm(&prompt)? {
+            continue;
+        }
+        match source.purge_cache() {
+            Ok(()) => println!(
+                "   {} Freed ~{} from {}",
+                "+".green(),
+                format_bytes(usage.bytes),
+                source.name()
+            ),
+            Err(e) => println!("   {} {}: {}", "!".yellow(), source.name(), e),
+        }
+    }
+
+    Ok(())
+}
+
 /// Cleanup wizard for removing unused tools
-pub fn cmd_cleanup(db: &Database, force: bool, dry_run: bool) -> Result<()> {
+pub fn cmd_cleanup(db: &Database, force: bool, purge_caches: bool, dry_run: bool) -> Result<()> {
     println!("{}", "═══════════════════════════════════════".cyan());
     println!("{}", "        HOARD CLEANUP WIZARD            ".bold());
     println!("{}", "═══════════════════════════════════════".cyan());
@@ -183,8 +256,12 @@ pub fn cmd_cleanup(db: &Database, force: bool, dry_run: bool) -> Result<()> {
         }
     }
 
-    // Step 3: Run health fix
-    println!("\n{} Running health checks...", "3.".bold());
+    // Step 3: Package manager caches
+    println!("\n{} Package manager caches:", "3.".bold());
+    cleanup_caches(purge_caches, dry_run)?;
+
+    // Step 4: Run health fix
+    println!("\n{} Running health checks...", "4.".bold());
     cmd_doctor(db, !dry_run && force)?;
 
     println!();
@@ -1,6 +1,9 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::forge::ForgeKind;
 
 /// AI provider options
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
@@ -12,6 +15,14 @@ pub enum AiProvider {
     Gemini,
     Codex,
     Opencode,
+    /// Anthropic Messages API, or a server speaking it
+    Anthropic,
+    /// OpenAI Chat Completions API, or a server speaking it
+    OpenAi,
+    /// Ollama running on this machine
+    Ollama,
+    /// Local OpenAI-compatible server (llama.cpp, LM Studio, vLLM)
+    Local,
 }
 
 impl std::fmt::Display for AiProvider {
@@ -21,6 +32,10 @@ impl std::fmt::Display for AiProvider {
             Self::Gemini => write!(f, "gemini"),
             Self::Codex => write!(f, "codex"),
             Self::Opencode => write!(f, "opencode"),
+            Self::Anthropic => write!(f, "anthropic"),
+            Self::OpenAi => write!(f, "openai"),
+            Self::Ollama => write!(f, "ollama"),
+            Self::Local => write!(f, "local"),
             Self::None => write!(f, "none"),
         }
     }
@@ -33,6 +48,10 @@ impl From<&str> for AiProvider {
             "gemini" => Self::Gemini,
             "codex" => Self::Codex,
             "opencode" | "open-code" => Self::Opencode,
+            "anthropic" => Self::Anthropic,
+            "openai" | "openai-compatible" => Self::OpenAi,
+            "ollama" => Self::Ollama,
+            "local" | "llama.cpp" | "llamacpp" | "lmstudio" => Self::Local,
             _ => Self::None,
         }
     }
@@ -46,7 +65,24 @@ impl AiProvider {
             Self::Gemini => Some("gemini"),
             Self::Codex => Some("codex"),
             Self::Opencode => Some("opencode"),
-            Self::None => None,
+            Self::Anthropic | Self::OpenAi | Self::Ollama | Self::Local | Self::None => None,
+        }
+    }
+
+    /// Whether the provider is called over HTTP rather than through a CLI
+    pub fn uses_api(&self) -> bool {
+        matches!(
+            self,
+            Self::Anthropic | Self::OpenAi | Self::Ollama | Self::Local
+        )
+    }
+
+    /// Environment variable usually holding the provider's API key
+    pub fn api_key_env(&self) -> Option<&'static str> {
+        match self {
+            Self::Anthropic => Some("ANTHROPIC_API_KEY"),
+            Self::OpenAi => Some("OPENAI_API_KEY"),
+            _ => None,
         }
     }
 
@@ -67,6 +103,10 @@ impl AiProvider {
             AiProvider::Gemini,
             AiProvider::Codex,
             AiProvider::Opencode,
+            AiProvider::Anthropic,
+            AiProvider::OpenAi,
+            AiProvider::Ollama,
+            AiProvider::Local,
         ]
     }
 }
@@ -91,21 +131,154 @@ impl std::fmt::Display for UsageMode {
     }
 }
 
+/// Where `usage scan` reads command history from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum HistoryProvider {
+    /// Plain shell history files (fish, bash, zsh)
+    #[default]
+    Shell,
+    /// Atuin's SQLite history database
+    Atuin,
+    /// McFly's SQLite history database
+    Mcfly,
+}
+
+impl std::fmt::Display for HistoryProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shell => write!(f, "shell"),
+            Self::Atuin => write!(f, "atuin"),
+            Self::Mcfly => write!(f, "mcfly"),
+        }
+    }
+}
+
 /// Usage tracking configuration
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UsageConfig {
     /// Tracking mode: scan (manual) or hook (automatic)
     #[serde(default)]
     pub mode: UsageMode,
-    /// Shell for hook mode (fish, bash, zsh)
+    /// Shell for hook mode (fish, bash, zsh, nushell, elvish, xonsh, powershell)
     pub shell: Option<String>,
+    /// History source for scan mode (shell files, atuin, mcfly)
+    #[serde(default)]
+    pub history: HistoryProvider,
+    /// Tools whose subcommands are tracked too (e.g. git, kubectl)
+    #[serde(default)]
+    pub subcommands: Vec<String>,
+    /// Commands never recorded (glob patterns on the command name, e.g. pass, vault*)
+    #[serde(default)]
+    pub ignore_commands: Vec<String>,
+    /// Directories where nothing is recorded (glob patterns; subdirectories included)
+    #[serde(default)]
+    pub ignore_dirs: Vec<String>,
+}
+
+impl UsageConfig {
+    /// Whether subcommands are tracked for a tool, by tool or command name
+    pub fn tracks_subcommands(&self, tool: &str, command: &str) -> bool {
+        self.subcommands.iter().any(|t| t == tool || t == command)
+    }
+
+    /// Whether a command is excluded from usage tracking
+    pub fn ignores_command(&self, command: &str) -> bool {
+        self.ignore_commands
+            .iter()
+            .any(|pattern| glob_match(pattern, command))
+    }
+
+    /// Whether commands run in a directory (or below it) are excluded from tracking
+    pub fn ignores_directory(&self, dir: &Path) -> bool {
+        if self.ignore_dirs.is_empty() {
+            return false;
+        }
+        let home = dirs::home_dir();
+        self.ignore_dirs.iter().any(|pattern| {
+            let pattern = match (pattern.strip_prefix("~/"), &home) {
+                (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+                _ => pattern.trim_end_matches('/').to_string(),
+            };
+            dir.ancestors()
+                .any(|ancestor| glob_match(&pattern, &ancestor.to_string_lossy()))
+        })
+    }
+}
+
+/// Match `text` against a glob pattern where `*` is any run of characters and
+/// `?` any single one
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    // Iterative matching with backtracking to the last `*`
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, star_t + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
 }
 
 /// AI-specific configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiConfig {
     #[serde(default)]
     pub provider: AiProvider,
+    /// API base URL, for a proxy or compatible server (API providers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Model to request (API providers)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Environment variable to read the API key from instead of the provider's usual one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_key_env: Option<String>,
+    /// Seconds to wait for an API response
+    #[serde(default = "default_ai_timeout")]
+    pub timeout_secs: u64,
+    /// Retries after rate limiting, server errors and timeouts
+    #[serde(default = "default_ai_retries")]
+    pub max_retries: u32,
+}
+
+fn default_ai_timeout() -> u64 {
+    120
+}
+
+fn default_ai_retries() -> u32 {
+    2
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            provider: AiProvider::None,
+            base_url: None,
+            model: None,
+            api_key_env: None,
+            timeout_secs: default_ai_timeout(),
+            max_retries: default_ai_retries(),
+        }
+    }
 }
 
 /// TUI theme options
@@ -189,6 +362,14 @@ pub struct TuiConfig {
     pub theme: TuiTheme,
 }
 
+/// GitHub API configuration
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct GitHubConfig {
+    /// REST API base URL, for GitHub Enterprise (e.g. https://github.example.com/api/v3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+}
+
 /// Package source configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SourcesConfig {
@@ -306,6 +487,13 @@ pub struct HoardConfig {
 
     #[serde(default)]
     pub sources: SourcesConfig,
+
+    #[serde(default)]
+    pub github: GitHubConfig,
+
+    /// Kind of self-hosted forges by host (e.g. "git.example.com": "gitlab")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub forges: BTreeMap<String, ForgeKind>,
 }
 
 impl HoardConfig {
@@ -363,9 +551,15 @@ impl HoardConfig {
                 usage: UsageConfig {
                     mode: legacy.usage.mode.unwrap_or_default(),
                     shell: legacy.usage.shell,
+                    history: HistoryProvider::default(),
+                    subcommands: Vec::new(),
+                    ignore_commands: Vec::new(),
+                    ignore_dirs: Vec::new(),
                 },
                 tui: TuiConfig::default(),
                 sources: SourcesConfig::default(),
+                github: GitHubConfig::default(),
+                forges: BTreeMap::new(),
             };
 
             // Save as JSON
@@ -453,9 +647,32 @@ mod tests {
     fn test_ai_provider_from_str() {
         assert_eq!(AiProvider::from("claude"), AiProvider::Claude);
         assert_eq!(AiProvider::from("GEMINI"), AiProvider::Gemini);
+        assert_eq!(AiProvider::from("openai"), AiProvider::OpenAi);
+        assert_eq!(AiProvider::from("llama.cpp"), AiProvider::Local);
         assert_eq!(AiProvider::from("unknown"), AiProvider::None);
     }
 
+    #[test]
+    fn test_usage_ignore_patterns() {
+        let config = UsageConfig {
+            ignore_commands: vec!["pass".into(), "vault*".into(), "op?".into()],
+            ignore_dirs: vec!["/work/client-*".into(), "/secrets/".into()],
+            ..Default::default()
+        };
+
+        assert!(config.ignores_command("pass"));
+        assert!(config.ignores_command("vault-agent"));
+        assert!(config.ignores_command("ops"));
+        assert!(!config.ignores_command("op"));
+        assert!(!config.ignores_command("passwd"));
+
+        assert!(config.ignores_directory(Path::new("/work/client-acme")));
+        assert!(config.ignores_directory(Path::new("/work/client-acme/src/deep")));
+        assert!(config.ignores_directory(Path::new("/secrets")));
+        assert!(!config.ignores_directory(Path::new("/work/internal")));
+        assert!(!UsageConfig::default().ignores_directory(Path::new("/secrets")));
+    }
+
     #[test]
     fn test_theme_cycling() {
         let theme = TuiTheme::CatppuccinMocha;
@@ -510,8 +727,23 @@ mod tests {
         }"#;
         let config: HoardConfig = serde_json::from_str(json).unwrap();
         assert_eq!(config.ai.provider, AiProvider::Claude);
+        assert_eq!(config.ai.timeout_secs, 120);
         assert_eq!(config.tui.theme, TuiTheme::Dracula);
         assert!(config.sources.cargo);
         assert!(config.sources.pip);
     }
+
+    #[test]
+    fn test_ai_api_config() {
+        let json = r#"{ "ai": { "provider": "openai", "model": "gpt-4.1", "max_retries": 0 } }"#;
+        let config: HoardConfig = serde_json::from_str(json).unwrap();
+        assert!(config.ai.provider.uses_api());
+        assert_eq!(config.ai.model.as_deref(), Some("gpt-4.1"));
+        assert_eq!(config.ai.base_url, None);
+        assert_eq!(config.ai.max_retries, 0);
+
+        let json = serde_json::to_string(&config.ai).unwrap();
+        assert!(json.contains(r#""provider":"openai""#));
+        assert!(!json.contains("base_url"));
+    }
 }
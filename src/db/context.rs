@@ -0,0 +1,106 @@
+//! Per-project usage context database operations
+
+use anyhow::Result;
+use rusqlite::params;
+
+use super::{Database, retry_busy};
+
+/// Usage of one tool within a project or directory
+#[derive(Debug, Clone)]
+pub struct ProjectToolUsage {
+    pub tool: String,
+    pub use_count: i64,
+    pub last_used: Option<String>,
+}
+
+impl Database {
+    // ==================== Usage Context ====================
+
+    /// Record a tool use in a working directory, and the git repository it belongs to
+    pub fn record_usage_context(
+        &self,
+        tool_name: &str,
+        directory: &str,
+        git_root: Option<&str>,
+        last_used: &str,
+    ) -> Result<bool> {
+        let inserted = retry_busy(|| {
+            Ok(self.conn.execute(
+                "INSERT INTO usage_context (tool_id, directory, git_root, use_count, last_used)
+                 SELECT id, ?2, ?3, 1, ?4 FROM tools WHERE name = ?1
+                 ON CONFLICT(tool_id, directory) DO UPDATE SET
+                    use_count = use_count + 1,
+                    git_root = ?3,
+                    last_used = CASE WHEN last_used IS NULL OR ?4 > last_used THEN ?4 ELSE last_used END",
+                params![tool_name, directory, git_root, last_used],
+            )?)
+        })?;
+        Ok(inserted > 0)
+    }
+
+    /// Tools used in a project, most used first
+    ///
+    /// `project` matches a git repository root (covering every directory inside it),
+    /// or a single directory outside any repository.
+    pub fn get_project_usage(&self, project: &str) -> Result<Vec<ProjectToolUsage>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, SUM(uc.use_count), MAX(uc.last_used)
+             FROM usage_context uc
+             INNER JOIN tools t ON uc.tool_id = t.id
+             WHERE uc.git_root = ?1 OR (uc.git_root IS NULL AND uc.directory = ?1)
+             GROUP BY t.name
+             ORDER BY SUM(uc.use_count) DESC, t.name",
+        )?;
+
+        let results = stmt
+            .query_map([project], |row| {
+                Ok(ProjectToolUsage {
+                    tool: row.get(0)?,
+                    use_count: row.get(1)?,
+                    last_used: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// Tool usage grouped by git repository, busiest repository first
+    pub fn get_usage_by_project(&self) -> Result<Vec<(String, Vec<ProjectToolUsage>)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT uc.git_root, t.name, SUM(uc.use_count), MAX(uc.last_used)
+             FROM usage_context uc
+             INNER JOIN tools t ON uc.tool_id = t.id
+             WHERE uc.git_root IS NOT NULL
+             GROUP BY uc.git_root, t.name
+             ORDER BY uc.git_root, SUM(uc.use_count) DESC, t.name",
+        )?;
+
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ProjectToolUsage {
+                        tool: row.get(1)?,
+                        use_count: row.get(2)?,
+                        last_used: row.get(3)?,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut projects: Vec<(String, Vec<ProjectToolUsage>)> = Vec::new();
+        for (root, usage) in rows {
+            match projects.last_mut() {
+                Some((last, tools)) if *last == root => tools.push(usage),
+                _ => projects.push((root, vec![usage])),
+            }
+        }
+
+        projects.sort_by_key(|(_, tools)| {
+            std::cmp::Reverse(tools.iter().map(|t| t.use_count).sum::<i64>())
+        });
+
+        Ok(projects)
+    }
+}
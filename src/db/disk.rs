@@ -0,0 +1,52 @@
+//! Disk usage database operations
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::params;
+
+use super::Database;
+
+impl Database {
+    // ==================== Disk Usage Operations ====================
+
+    /// Store the measured disk usage for a tool
+    pub fn set_disk_usage(&self, tool_name: &str, bytes: u64) -> Result<bool> {
+        let tool_id: i64 =
+            match self
+                .conn
+                .query_row("SELECT id FROM tools WHERE name = ?1", [tool_name], |row| {
+                    row.get(0)
+                }) {
+                Ok(id) => id,
+                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(false),
+                Err(e) => return Err(e.into()),
+            };
+
+        self.conn.execute(
+            "INSERT OR REPLACE INTO tool_disk_usage (tool_id, bytes, measured_at)
+             VALUES (?1, ?2, ?3)",
+            params![tool_id, bytes as i64, Utc::now().to_rfc3339()],
+        )?;
+
+        Ok(true)
+    }
+
+    /// Get the last measured disk usage for all tools (tool_name -> bytes)
+    pub fn get_all_disk_usage(&self) -> Result<HashMap<String, u64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, du.bytes
+             FROM tool_disk_usage du
+             JOIN tools t ON du.tool_id = t.id",
+        )?;
+
+        let sizes = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+            })?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(sizes)
+    }
+}
@@ -20,6 +20,22 @@ pub struct CachedExtraction {
     pub extracted_at: String,
 }
 
+/// Read a [`CachedExtraction`] from a row of the extraction cache columns
+fn extraction_from_row(row: &rusqlite::Row) -> rusqlite::Result<CachedExtraction> {
+    Ok(CachedExtraction {
+        repo_owner: row.get(0)?,
+        repo_name: row.get(1)?,
+        version: row.get(2)?,
+        name: row.get(3)?,
+        binary: row.get(4)?,
+        source: row.get(5)?,
+        install_command: row.get(6)?,
+        description: row.get(7)?,
+        category: row.get(8)?,
+        extracted_at: row.get(9)?,
+    })
+}
+
 impl Database {
     // ==================== Extraction Cache ====================
 
@@ -42,23 +58,36 @@ impl Database {
         let mut rows = stmt.query(params![owner, repo, version])?;
 
         if let Some(row) = rows.next()? {
-            Ok(Some(CachedExtraction {
-                repo_owner: row.get(0)?,
-                repo_name: row.get(1)?,
-                version: row.get(2)?,
-                name: row.get(3)?,
-                binary: row.get(4)?,
-                source: row.get(5)?,
-                install_command: row.get(6)?,
-                description: row.get(7)?,
-                category: row.get(8)?,
-                extracted_at: row.get(9)?,
-            }))
+            Ok(Some(extraction_from_row(row)?))
         } else {
             Ok(None)
         }
     }
 
+    /// Get the cached extraction for a repository, whichever version it was made from
+    pub fn get_any_cached_extraction(
+        &self,
+        owner: &str,
+        repo: &str,
+    ) -> Result<Option<CachedExtraction>> {
+        let result = self.conn.query_row(
+            r#"
+            SELECT repo_owner, repo_name, version, name, binary, source,
+                   install_command, description, category, extracted_at
+            FROM extraction_cache
+            WHERE lower(repo_owner) = lower(?1) AND lower(repo_name) = lower(?2)
+            "#,
+            params![owner, repo],
+            extraction_from_row,
+        );
+
+        match result {
+            Ok(extraction) => Ok(Some(extraction)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
     /// Cache an extraction (upserts if repo already exists)
     pub fn cache_extraction(&self, extraction: &CachedExtraction) -> Result<()> {
         self.conn.execute(
@@ -104,20 +133,7 @@ impl Database {
             "#,
         )?;
 
-        let rows = stmt.query_map([], |row| {
-            Ok(CachedExtraction {
-                repo_owner: row.get(0)?,
-                repo_name: row.get(1)?,
-                version: row.get(2)?,
-                name: row.get(3)?,
-                binary: row.get(4)?,
-                source: row.get(5)?,
-                install_command: row.get(6)?,
-                description: row.get(7)?,
-                category: row.get(8)?,
-                extracted_at: row.get(9)?,
-            })
-        })?;
+        let rows = stmt.query_map([], extraction_from_row)?;
 
         let mut extractions = Vec::new();
         for row in rows {
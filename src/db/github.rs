@@ -5,27 +5,98 @@ use chrono::Utc;
 use rusqlite::params;
 
 use super::Database;
+use crate::forge::{ForgeKind, RepoRef};
 
-/// GitHub repository info
+/// Repository info from a tool's forge (GitHub, GitLab or Gitea)
 #[derive(Debug, Clone)]
 pub struct GitHubInfo {
+    /// Forge hosting the repository
+    pub forge: ForgeKind,
+    /// Web host of the forge, e.g. `github.com`
+    pub host: String,
     pub repo_owner: String,
     pub repo_name: String,
     pub description: Option<String>,
     pub stars: i64,
     pub language: Option<String>,
     pub homepage: Option<String>,
+    /// SPDX identifier of the license
+    pub license: Option<String>,
+    pub archived: bool,
+    /// Last push to any branch (RFC 3339)
+    pub pushed_at: Option<String>,
+    /// Tag of the latest release
+    pub latest_release: Option<String>,
+    /// Open issues and pull requests
+    pub open_issues: Option<i64>,
+}
+
+impl GitHubInfo {
+    /// Where the repository lives
+    pub fn repo_ref(&self) -> RepoRef {
+        RepoRef {
+            forge: self.forge,
+            host: self.host.clone(),
+            owner: self.repo_owner.clone(),
+            repo: self.repo_name.clone(),
+        }
+    }
+}
+
+/// Stars a tool gained over a window (see [`Database::get_star_velocity`])
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarVelocity {
+    pub tool_name: String,
+    pub stars: i64,
+    pub gained: i64,
+    /// Date of the snapshot the gain is measured from (YYYY-MM-DD)
+    pub since: String,
 }
 
 /// Input data for storing GitHub repo info
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct GitHubInfoInput<'a> {
+    pub forge: ForgeKind,
+    pub host: &'a str,
     pub repo_owner: &'a str,
     pub repo_name: &'a str,
     pub description: Option<&'a str>,
     pub stars: i64,
     pub language: Option<&'a str>,
     pub homepage: Option<&'a str>,
+    pub license: Option<&'a str>,
+    pub archived: bool,
+    pub pushed_at: Option<&'a str>,
+    pub latest_release: Option<&'a str>,
+    pub open_issues: Option<i64>,
+    pub topics: &'a [String],
+}
+
+/// Columns of `tool_github` read into [`GitHubInfo`], in order
+const GITHUB_INFO_COLUMNS: &str =
+    "tg.repo_owner, tg.repo_name, tg.description, tg.stars, tg.language, tg.homepage,
+     tg.license, tg.archived, tg.pushed_at, tg.latest_release, tg.open_issues, tg.forge, tg.host";
+
+/// Read a [`GitHubInfo`] from [`GITHUB_INFO_COLUMNS`] starting at `offset`
+fn github_info_from_row(row: &rusqlite::Row, offset: usize) -> rusqlite::Result<GitHubInfo> {
+    Ok(GitHubInfo {
+        repo_owner: row.get(offset)?,
+        repo_name: row.get(offset + 1)?,
+        description: row.get(offset + 2)?,
+        stars: row.get(offset + 3)?,
+        language: row.get(offset + 4)?,
+        homepage: row.get(offset + 5)?,
+        license: row.get(offset + 6)?,
+        archived: row.get(offset + 7)?,
+        pushed_at: row.get(offset + 8)?,
+        latest_release: row.get(offset + 9)?,
+        open_issues: row.get(offset + 10)?,
+        forge: row
+            .get::<_, String>(offset + 11)?
+            .parse()
+            .unwrap_or(ForgeKind::GitHub),
+        host: row.get(offset + 12)?,
+    })
 }
 
 impl Database {
@@ -44,10 +115,20 @@ impl Database {
                 Err(e) => return Err(e.into()),
             };
 
-        self.conn.execute(
+        let tx = self.conn.unchecked_transaction()?;
+        // Star history of a different repository (a corrected match) doesn't carry over
+        tx.execute(
+            "DELETE FROM github_star_history WHERE tool_id = ?1 AND EXISTS (
+                SELECT 1 FROM tool_github WHERE tool_id = ?1
+                AND (lower(repo_owner) != lower(?2) OR lower(repo_name) != lower(?3)
+                     OR host != ?4))",
+            params![tool_id, info.repo_owner, info.repo_name, info.host],
+        )?;
+        tx.execute(
             "INSERT OR REPLACE INTO tool_github
-             (tool_id, repo_owner, repo_name, description, stars, language, homepage, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+             (tool_id, repo_owner, repo_name, description, stars, language, homepage,
+              license, archived, pushed_at, latest_release, open_issues, forge, host, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
             params![
                 tool_id,
                 info.repo_owner,
@@ -56,9 +137,36 @@ impl Database {
                 info.stars,
                 info.language,
                 info.homepage,
+                info.license,
+                info.archived,
+                info.pushed_at,
+                info.latest_release,
+                info.open_issues,
+                info.forge.to_string(),
+                info.host,
                 Utc::now().to_rfc3339()
             ],
         )?;
+        tx.execute(
+            "INSERT INTO github_star_history (tool_id, date, stars) VALUES (?1, ?2, ?3)
+             ON CONFLICT(tool_id, date) DO UPDATE SET stars = excluded.stars",
+            params![
+                tool_id,
+                Utc::now().format("%Y-%m-%d").to_string(),
+                info.stars
+            ],
+        )?;
+        tx.execute(
+            "DELETE FROM tool_github_topics WHERE tool_id = ?1",
+            [tool_id],
+        )?;
+        for topic in info.topics {
+            tx.execute(
+                "INSERT OR IGNORE INTO tool_github_topics (tool_id, topic) VALUES (?1, ?2)",
+                params![tool_id, topic.to_lowercase()],
+            )?;
+        }
+        tx.commit()?;
 
         Ok(true)
     }
@@ -66,21 +174,14 @@ impl Database {
     /// Get GitHub info for a tool
     pub fn get_github_info(&self, tool_name: &str) -> Result<Option<GitHubInfo>> {
         let result = self.conn.query_row(
-            "SELECT tg.repo_owner, tg.repo_name, tg.description, tg.stars, tg.language, tg.homepage
-             FROM tool_github tg
-             JOIN tools t ON tg.tool_id = t.id
-             WHERE t.name = ?1",
+            &format!(
+                "SELECT {GITHUB_INFO_COLUMNS}
+                 FROM tool_github tg
+                 JOIN tools t ON tg.tool_id = t.id
+                 WHERE t.name = ?1"
+            ),
             [tool_name],
-            |row| {
-                Ok(GitHubInfo {
-                    repo_owner: row.get(0)?,
-                    repo_name: row.get(1)?,
-                    description: row.get(2)?,
-                    stars: row.get(3)?,
-                    language: row.get(4)?,
-                    homepage: row.get(5)?,
-                })
-            },
+            |row| github_info_from_row(row, 0),
         );
 
         match result {
@@ -90,6 +191,25 @@ impl Database {
         }
     }
 
+    /// Forget the GitHub info, topics and star history stored for a tool
+    pub fn delete_github_info(&self, tool_name: &str) -> Result<bool> {
+        let tx = self.conn.unchecked_transaction()?;
+        for table in ["github_star_history", "tool_github_topics"] {
+            tx.execute(
+                &format!(
+                    "DELETE FROM {table} WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)"
+                ),
+                [tool_name],
+            )?;
+        }
+        let deleted = tx.execute(
+            "DELETE FROM tool_github WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
+            [tool_name],
+        )?;
+        tx.commit()?;
+        Ok(deleted > 0)
+    }
+
     /// Check if tool has GitHub info cached
     pub fn has_github_info(&self, tool_name: &str) -> Result<bool> {
         let count: i64 = self.conn.query_row(
@@ -134,27 +254,115 @@ impl Database {
 
     /// Get all GitHub info for all tools (for batch loading in TUI)
     pub fn get_all_github_info(&self) -> Result<Vec<(String, GitHubInfo)>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT t.name, tg.repo_owner, tg.repo_name, tg.description, tg.stars, tg.language, tg.homepage
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT t.name, {GITHUB_INFO_COLUMNS}
              FROM tools t
              INNER JOIN tool_github tg ON t.id = tg.tool_id
-             ORDER BY t.name",
+             ORDER BY t.name"
+        ))?;
+        let results = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, github_info_from_row(row, 1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
+    }
+
+    /// Get the repository topics stored for a tool
+    pub fn get_github_topics(&self, tool_name: &str) -> Result<Vec<String>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT gt.topic FROM tool_github_topics gt
+             JOIN tools t ON gt.tool_id = t.id
+             WHERE t.name = ?1
+             ORDER BY gt.topic",
         )?;
+        let topics = stmt
+            .query_map([tool_name], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(topics)
+    }
+
+    /// Installed tools whose repository is archived or hasn't been pushed to since `cutoff`
+    pub fn get_unmaintained_tools(&self, cutoff: &str) -> Result<Vec<(String, GitHubInfo)>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT t.name, {GITHUB_INFO_COLUMNS}
+             FROM tools t
+             INNER JOIN tool_github tg ON t.id = tg.tool_id
+             WHERE t.is_installed = 1 AND (tg.archived = 1 OR tg.pushed_at < ?1)
+             ORDER BY tg.archived DESC, tg.pushed_at"
+        ))?;
         let results = stmt
+            .query_map([cutoff], |row| {
+                Ok((row.get::<_, String>(0)?, github_info_from_row(row, 1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(results)
+    }
+
+    /// Recorded star counts for a tool over the last `days` days, oldest first
+    pub fn get_star_history(&self, tool_name: &str, days: u32) -> Result<Vec<(String, i64)>> {
+        let since = (Utc::now().date_naive() - chrono::Duration::days(days as i64))
+            .format("%Y-%m-%d")
+            .to_string();
+        let mut stmt = self.conn.prepare(
+            "SELECT h.date, h.stars FROM github_star_history h
+             JOIN tools t ON h.tool_id = t.id
+             WHERE t.name = ?1 AND h.date > ?2
+             ORDER BY h.date",
+        )?;
+        let history = stmt
+            .query_map(params![tool_name, since], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(history)
+    }
+
+    /// Stars gained by each tool since `since` (YYYY-MM-DD), most gained first
+    ///
+    /// Gains are measured from the last snapshot on or before `since`, or the
+    /// first one after it for tools synced more recently. Tools with a single
+    /// snapshot in that range are left out.
+    pub fn get_star_velocity(&self, since: &str) -> Result<Vec<StarVelocity>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, h.date, h.stars FROM github_star_history h
+             JOIN tools t ON h.tool_id = t.id
+             ORDER BY t.name, h.date",
+        )?;
+        let rows = stmt
             .query_map([], |row| {
                 Ok((
                     row.get::<_, String>(0)?,
-                    GitHubInfo {
-                        repo_owner: row.get(1)?,
-                        repo_name: row.get(2)?,
-                        description: row.get(3)?,
-                        stars: row.get(4)?,
-                        language: row.get(5)?,
-                        homepage: row.get(6)?,
-                    },
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
                 ))
             })?
             .collect::<Result<Vec<_>, _>>()?;
-        Ok(results)
+
+        let mut velocities = Vec::new();
+        for history in rows.chunk_by(|a, b| a.0 == b.0) {
+            let baseline = history
+                .iter()
+                .rev()
+                .find(|(_, date, _)| date.as_str() <= since)
+                .unwrap_or(&history[0]);
+            let latest = &history[history.len() - 1];
+            if baseline.1 == latest.1 {
+                continue;
+            }
+            velocities.push(StarVelocity {
+                tool_name: latest.0.clone(),
+                stars: latest.2,
+                gained: latest.2 - baseline.2,
+                since: baseline.1.clone(),
+            });
+        }
+
+        velocities.sort_by(|a, b| {
+            b.gained
+                .cmp(&a.gained)
+                .then_with(|| a.tool_name.cmp(&b.tool_name))
+        });
+        Ok(velocities)
     }
 }
@@ -0,0 +1,89 @@
+//! Shell history scan checkpoint operations
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, params};
+
+use crate::history::{HistoryCheckpoint, TailFingerprint};
+
+use super::Database;
+
+impl Database {
+    // ==================== History Checkpoints ====================
+
+    /// Get the checkpoint left by the previous scan of a history file
+    pub fn get_history_checkpoint(&self, path: &str) -> Result<Option<HistoryCheckpoint>> {
+        let checkpoint = self
+            .conn
+            .query_row(
+                "SELECT inode, byte_offset, last_timestamp, tail
+                 FROM history_checkpoints WHERE path = ?1",
+                [path],
+                |row| {
+                    Ok(HistoryCheckpoint {
+                        inode: row.get::<_, i64>(0)? as u64,
+                        offset: row.get::<_, i64>(1)? as u64,
+                        last_timestamp: row.get(2)?,
+                        tail: TailFingerprint::from_bytes(&row.get::<_, Vec<u8>>(3)?),
+                    })
+                },
+            )
+            .optional()?;
+
+        Ok(checkpoint)
+    }
+
+    /// Store the checkpoint for a history file after ingesting it
+    pub fn set_history_checkpoint(&self, path: &str, checkpoint: &HistoryCheckpoint) -> Result<()> {
+        self.conn.execute(
+            "INSERT OR REPLACE INTO history_checkpoints
+                (path, inode, byte_offset, last_timestamp, tail, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                path,
+                checkpoint.inode as i64,
+                checkpoint.offset as i64,
+                checkpoint.last_timestamp,
+                checkpoint.tail.to_bytes(),
+                Utc::now().to_rfc3339()
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Checkpoints written by older versions, which kept raw history bytes
+    pub fn count_raw_history_checkpoints(&self) -> Result<usize> {
+        let count: i64 = self.conn.query_row(
+            "SELECT COUNT(*) FROM history_checkpoints WHERE length(tail) NOT IN (0, ?1)",
+            [TailFingerprint::ENCODED_LEN as i64],
+            |row| row.get(0),
+        )?;
+        Ok(count as usize)
+    }
+
+    /// Replace raw history bytes left by older versions with their fingerprint
+    pub fn fingerprint_history_checkpoints(&self) -> Result<usize> {
+        let mut stmt = self.conn.prepare(
+            "SELECT path, tail FROM history_checkpoints WHERE length(tail) NOT IN (0, ?1)",
+        )?;
+        let raw = stmt
+            .query_map([TailFingerprint::ENCODED_LEN as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (path, tail) in &raw {
+            self.conn.execute(
+                "UPDATE history_checkpoints SET tail = ?2 WHERE path = ?1",
+                params![path, TailFingerprint::of(tail).to_bytes()],
+            )?;
+        }
+        Ok(raw.len())
+    }
+
+    /// Forget all checkpoints so the next scan re-reads every history file
+    pub fn clear_history_checkpoints(&self) -> Result<()> {
+        self.conn.execute("DELETE FROM history_checkpoints", [])?;
+        Ok(())
+    }
+}
@@ -0,0 +1,299 @@
+//! Usage snapshots shared between machines
+//!
+//! The regular usage tables always describe this machine. Snapshots imported
+//! from other hosts are kept apart, keyed by tool name (the tool may not be
+//! tracked here), and replaced as a whole on every import of a newer snapshot.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use rusqlite::{OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+
+use crate::models::Tool;
+
+use super::tools::tool_from_row;
+use super::{Database, ToolUsage};
+
+/// Snapshot format version written by this build
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// One machine's usage counts, as written by `hoards usage export`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UsageSnapshot {
+    pub version: u32,
+    pub host: String,
+    pub exported_at: String,
+    pub tools: Vec<ToolSnapshot>,
+}
+
+/// A tool's usage on the exporting machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ToolSnapshot {
+    pub name: String,
+    pub use_count: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_seen: Option<String>,
+    /// Uses per day (`YYYY-MM-DD`)
+    #[serde(default)]
+    pub daily: BTreeMap<String, i64>,
+}
+
+/// A machine whose usage was imported
+#[derive(Debug, Clone)]
+pub struct HostInfo {
+    pub host: String,
+    pub exported_at: String,
+    pub imported_at: String,
+    pub tools: usize,
+    pub uses: i64,
+}
+
+/// A tool's usage on one machine
+#[derive(Debug, Clone)]
+pub struct HostToolUsage {
+    pub host: String,
+    pub usage: ToolUsage,
+    /// Uses over the last 30 days
+    pub recent: i64,
+}
+
+impl Database {
+    // ==================== Usage Snapshots ====================
+
+    /// Snapshot of this machine's usage (counts, daily buckets and last use)
+    pub fn export_usage_snapshot(&self, host: &str) -> Result<UsageSnapshot> {
+        let mut tools: Vec<ToolSnapshot> = self
+            .get_all_usage()?
+            .into_iter()
+            .map(|(name, usage)| ToolSnapshot {
+                name,
+                use_count: usage.use_count,
+                last_used: usage.last_used,
+                first_seen: Some(usage.first_seen),
+                daily: BTreeMap::new(),
+            })
+            .collect();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, ud.date, ud.count FROM usage_daily ud
+             INNER JOIN tools t ON ud.tool_id = t.id",
+        )?;
+        let daily = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        for (name, date, count) in daily {
+            if let Some(tool) = tools.iter_mut().find(|t| t.name == name) {
+                tool.daily.insert(date, count);
+            }
+        }
+
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(UsageSnapshot {
+            version: SNAPSHOT_VERSION,
+            host: host.to_string(),
+            exported_at: Utc::now().to_rfc3339(),
+            tools,
+        })
+    }
+
+    /// Store another machine's snapshot, replacing what was imported from it before
+    ///
+    /// A snapshot exported before the one already imported from the host is refused
+    /// unless `force` is set, so stale data can't replace newer counts.
+    pub fn import_usage_snapshot(&self, snapshot: &UsageSnapshot, force: bool) -> Result<usize> {
+        if snapshot.version > SNAPSHOT_VERSION {
+            bail!(
+                "Snapshot format v{} is newer than this version of hoards supports (v{})",
+                snapshot.version,
+                SNAPSHOT_VERSION
+            );
+        }
+        let Ok(exported_at) = DateTime::parse_from_rfc3339(&snapshot.exported_at) else {
+            bail!(
+                "Snapshot has an invalid export time: {}",
+                snapshot.exported_at
+            );
+        };
+
+        self.transaction(|db| {
+            let previous: Option<String> = db
+                .conn
+                .query_row(
+                    "SELECT exported_at FROM usage_hosts WHERE host = ?1",
+                    [&snapshot.host],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if !force
+                && let Some(previous) = previous
+                && DateTime::parse_from_rfc3339(&previous).is_ok_and(|p| p > exported_at)
+            {
+                bail!(
+                    "A newer snapshot from {} was already imported (exported {}, this one {}); use --force to replace it anyway",
+                    snapshot.host,
+                    previous,
+                    snapshot.exported_at
+                );
+            }
+
+            db.delete_host_usage(&snapshot.host)?;
+
+            db.conn.execute(
+                "INSERT INTO usage_hosts (host, exported_at, imported_at) VALUES (?1, ?2, ?3)",
+                params![snapshot.host, snapshot.exported_at, Utc::now().to_rfc3339()],
+            )?;
+            for tool in &snapshot.tools {
+                db.conn.execute(
+                    "INSERT INTO host_usage (host, tool_name, use_count, last_used, first_seen)
+                     VALUES (?1, ?2, ?3, ?4, ?5)
+                     ON CONFLICT(host, tool_name) DO UPDATE SET use_count = use_count + ?3",
+                    params![
+                        snapshot.host,
+                        tool.name,
+                        tool.use_count,
+                        tool.last_used,
+                        tool.first_seen
+                    ],
+                )?;
+                for (date, count) in &tool.daily {
+                    db.conn.execute(
+                        "INSERT INTO host_usage_daily (host, tool_name, date, count)
+                         VALUES (?1, ?2, ?3, ?4)
+                         ON CONFLICT(host, tool_name, date) DO UPDATE SET count = count + ?4",
+                        params![snapshot.host, tool.name, date, count],
+                    )?;
+                }
+            }
+            Ok(snapshot.tools.len())
+        })
+    }
+
+    /// Forget everything imported from a machine
+    pub fn delete_host_usage(&self, host: &str) -> Result<bool> {
+        self.conn
+            .execute("DELETE FROM host_usage_daily WHERE host = ?1", [host])?;
+        self.conn
+            .execute("DELETE FROM host_usage WHERE host = ?1", [host])?;
+        let deleted = self
+            .conn
+            .execute("DELETE FROM usage_hosts WHERE host = ?1", [host])?;
+        Ok(deleted > 0)
+    }
+
+    /// Machines whose usage was imported, by name
+    pub fn get_hosts(&self) -> Result<Vec<HostInfo>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT h.host, h.exported_at, h.imported_at,
+                    COUNT(hu.tool_name), COALESCE(SUM(hu.use_count), 0)
+             FROM usage_hosts h
+             LEFT JOIN host_usage hu ON hu.host = h.host
+             GROUP BY h.host
+             ORDER BY h.host",
+        )?;
+
+        let hosts = stmt
+            .query_map([], |row| {
+                Ok(HostInfo {
+                    host: row.get(0)?,
+                    exported_at: row.get(1)?,
+                    imported_at: row.get(2)?,
+                    tools: row.get::<_, i64>(3)? as usize,
+                    uses: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(hosts)
+    }
+
+    /// Usage merged across this machine and every imported one, most used first
+    pub fn get_all_usage_all_hosts(&self) -> Result<Vec<(String, ToolUsage)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT name, SUM(use_count), MAX(last_used), MIN(first_seen) FROM (
+                SELECT t.name AS name, tu.use_count, tu.last_used, tu.first_seen
+                FROM tool_usage tu INNER JOIN tools t ON tu.tool_id = t.id
+                UNION ALL
+                SELECT tool_name, use_count, last_used, first_seen FROM host_usage
+             )
+             GROUP BY name
+             ORDER BY SUM(use_count) DESC, name",
+        )?;
+
+        let results = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    ToolUsage {
+                        use_count: row.get(1)?,
+                        last_used: row.get(2)?,
+                        first_seen: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// A tool's usage on each imported machine (this one not included)
+    pub fn get_tool_usage_by_host(&self, tool_name: &str) -> Result<Vec<HostToolUsage>> {
+        let since = (Utc::now().date_naive() - chrono::Duration::days(29))
+            .format("%Y-%m-%d")
+            .to_string();
+        let mut stmt = self.conn.prepare(
+            "SELECT hu.host, hu.use_count, hu.last_used, hu.first_seen,
+                    (SELECT COALESCE(SUM(count), 0) FROM host_usage_daily d
+                     WHERE d.host = hu.host AND d.tool_name = hu.tool_name AND d.date >= ?2)
+             FROM host_usage hu
+             WHERE hu.tool_name = ?1
+             ORDER BY hu.use_count DESC, hu.host",
+        )?;
+
+        let results = stmt
+            .query_map(params![tool_name, since], |row| {
+                Ok(HostToolUsage {
+                    host: row.get(0)?,
+                    usage: ToolUsage {
+                        use_count: row.get(1)?,
+                        last_used: row.get(2)?,
+                        first_seen: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                    },
+                    recent: row.get(4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// Installed tools with no usage on this machine nor on any imported one
+    pub fn get_unused_tools_all_hosts(&self) -> Result<Vec<Tool>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.id, t.name, t.description, t.category, t.source, t.install_command,
+                    t.binary_name, t.is_installed, t.is_favorite, t.notes, t.created_at, t.updated_at
+             FROM tools t
+             LEFT JOIN tool_usage tu ON t.id = tu.tool_id
+             WHERE t.is_installed = 1 AND (tu.tool_id IS NULL OR tu.use_count = 0)
+               AND NOT EXISTS (
+                   SELECT 1 FROM host_usage hu WHERE hu.tool_name = t.name AND hu.use_count > 0
+               )
+             ORDER BY t.name",
+        )?;
+
+        let tools = stmt
+            .query_map([], tool_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(tools)
+    }
+}
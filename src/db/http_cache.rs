@@ -0,0 +1,47 @@
+//! Conditional request cache for API responses
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+/// A response body kept with the ETag it was served with
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedResponse {
+    pub etag: String,
+    pub body: String,
+}
+
+impl Database {
+    // ==================== HTTP Cache ====================
+
+    /// Get the cached response for a URL
+    pub fn get_cached_response(&self, url: &str) -> Result<Option<CachedResponse>> {
+        let cached = self
+            .conn
+            .query_row(
+                "SELECT etag, body FROM http_cache WHERE url = ?1",
+                [url],
+                |row| {
+                    Ok(CachedResponse {
+                        etag: row.get(0)?,
+                        body: row.get(1)?,
+                    })
+                },
+            )
+            .optional()?;
+        Ok(cached)
+    }
+
+    /// Cache a response, replacing any previous one for the URL
+    pub fn cache_response(&self, url: &str, etag: &str, body: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO http_cache (url, etag, body, fetched_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(url) DO UPDATE SET
+                etag = excluded.etag, body = excluded.body, fetched_at = excluded.fetched_at",
+            params![url, etag, body, Utc::now().to_rfc3339()],
+        )?;
+        Ok(())
+    }
+}
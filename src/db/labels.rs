@@ -10,6 +10,10 @@ use crate::models::Tool;
 use super::Database;
 use super::tools::tool_from_row;
 
+/// Labels together with GitHub topics, as `(tool_id, label)` rows
+const ALL_LABELS: &str = "(SELECT tool_id, label FROM tool_labels
+      UNION SELECT tool_id, topic FROM tool_github_topics)";
+
 impl Database {
     // ==================== Label Operations ====================
 
@@ -38,14 +42,14 @@ impl Database {
         Ok(true)
     }
 
-    /// Get labels for a tool
+    /// Get labels for a tool (including its GitHub topics)
     pub fn get_labels(&self, tool_name: &str) -> Result<Vec<String>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT tl.label FROM tool_labels tl
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT tl.label FROM {ALL_LABELS} tl
              JOIN tools t ON tl.tool_id = t.id
              WHERE t.name = ?1
-             ORDER BY tl.label",
-        )?;
+             ORDER BY tl.label"
+        ))?;
         let labels = stmt
             .query_map([tool_name], |row| row.get(0))?
             .collect::<Result<Vec<_>, _>>()?;
@@ -54,9 +58,9 @@ impl Database {
 
     /// Get all unique labels
     pub fn get_all_labels(&self) -> Result<Vec<String>> {
-        let mut stmt = self
-            .conn
-            .prepare("SELECT DISTINCT label FROM tool_labels ORDER BY label")?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT DISTINCT label FROM {ALL_LABELS} ORDER BY label"
+        ))?;
         let labels = stmt
             .query_map([], |row| row.get(0))?
             .collect::<Result<Vec<_>, _>>()?;
@@ -65,9 +69,9 @@ impl Database {
 
     /// Get all labels with their tool counts in a single query
     pub fn get_label_counts(&self) -> Result<Vec<(String, usize)>> {
-        let mut stmt = self.conn.prepare(
-            "SELECT label, COUNT(*) as count FROM tool_labels GROUP BY label ORDER BY label",
-        )?;
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT label, COUNT(*) as count FROM {ALL_LABELS} GROUP BY label ORDER BY label"
+        ))?;
         let counts = stmt
             .query_map([], |row| {
                 Ok((row.get::<_, String>(0)?, row.get::<_, usize>(1)?))
@@ -78,22 +82,22 @@ impl Database {
 
     /// List tools by label
     pub fn list_tools_by_label(&self, label: &str) -> Result<Vec<Tool>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT t.id, t.name, t.description, t.category, t.source, t.install_command,
                     t.binary_name, t.is_installed, t.is_favorite, t.notes,
                     t.created_at, t.updated_at
              FROM tools t
-             JOIN tool_labels tl ON t.id = tl.tool_id
+             JOIN {ALL_LABELS} tl ON t.id = tl.tool_id
              WHERE tl.label = ?1
-             ORDER BY t.name",
-        )?;
+             ORDER BY t.name"
+        ))?;
 
         let tool_iter = stmt.query_map([label.to_lowercase()], tool_from_row)?;
 
         tool_iter.collect::<Result<Vec<_>, _>>().map_err(Into::into)
     }
 
-    /// Clear labels for a tool
+    /// Clear labels for a tool (GitHub topics are kept)
     pub fn clear_labels(&self, tool_name: &str) -> Result<bool> {
         let rows = self.conn.execute(
             "DELETE FROM tool_labels WHERE tool_id = (SELECT id FROM tools WHERE name = ?1)",
@@ -105,12 +109,12 @@ impl Database {
     /// Get all labels for all tools (batch operation for TUI)
     /// Returns a map of tool_name -> Vec<label>
     pub fn get_all_tool_labels(&self) -> Result<HashMap<String, Vec<String>>> {
-        let mut stmt = self.conn.prepare(
+        let mut stmt = self.conn.prepare(&format!(
             "SELECT t.name, tl.label
-             FROM tool_labels tl
+             FROM {ALL_LABELS} tl
              JOIN tools t ON tl.tool_id = t.id
-             ORDER BY t.name, tl.label",
-        )?;
+             ORDER BY t.name, tl.label"
+        ))?;
 
         let mut result: HashMap<String, Vec<String>> = HashMap::new();
         let rows = stmt.query_map([], |row| {
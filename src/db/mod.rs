@@ -5,29 +5,53 @@
 //! - `tools`: Tool and Interest CRUD operations
 //! - `bundles`: Bundle operations
 //! - `configs`: Config file tracking
+//! - `disk`: Per-tool disk usage measurements
 //! - `labels`: Tool labeling operations
 //! - `github`: GitHub metadata storage
+//! - `history`: Shell history scan checkpoints
+//! - `http_cache`: API responses kept for conditional requests
 //! - `usage`: Usage tracking operations
+//! - `outcomes`: Exit status and duration of tracked tools
+//! - `hosts`: Usage imported from other machines
 //! - `extractions`: AI extraction cache
+//! - `release_notes`: Release notes cached per version range
 
 mod bundles;
 mod configs;
+mod context;
+mod disk;
 mod extractions;
 mod github;
+mod history;
+mod hosts;
+mod http_cache;
 mod labels;
+mod outcomes;
+mod release_notes;
 mod schema;
 mod tools;
 mod usage;
 
 // Re-export commonly used types
+pub use context::ProjectToolUsage;
 pub use extractions::CachedExtraction;
-pub use github::{GitHubInfo, GitHubInfoInput};
-pub use usage::ToolUsage;
+pub use github::{GitHubInfo, GitHubInfoInput, StarVelocity};
+pub use hosts::{HostInfo, HostToolUsage, SNAPSHOT_VERSION, ToolSnapshot, UsageSnapshot};
+pub use http_cache::CachedResponse;
+pub use outcomes::{OutcomeStats, format_duration_ms};
+pub use usage::{SubcommandUsage, ToolUsage};
 
 use anyhow::{Context, Result};
 use directories::ProjectDirs;
-use rusqlite::Connection;
-use std::path::PathBuf;
+use rusqlite::{Connection, ErrorCode, OpenFlags, Transaction, TransactionBehavior};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long a connection waits on another writer before failing with SQLITE_BUSY
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Extra attempts for writes still refused with SQLITE_BUSY after the busy timeout
+const BUSY_RETRIES: u32 = 3;
 
 /// Database wrapper for hoards
 pub struct Database {
@@ -37,14 +61,24 @@ pub struct Database {
 impl Database {
     /// Open or create the database at the default location
     pub fn open() -> Result<Self> {
-        let path = Self::db_path()?;
+        Self::open_at(&Self::db_path()?)
+    }
 
+    /// Open or create the database at a given path
+    pub fn open_at(path: &Path) -> Result<Self> {
         // Ensure parent directory exists
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent).context("Failed to create database directory")?;
         }
 
-        let conn = Connection::open(&path).context("Failed to open database")?;
+        let conn = Connection::open(path).context("Failed to open database")?;
+
+        // The TUI, shell hook ingestion and other commands can all have the database
+        // open at once: WAL lets readers and a writer proceed concurrently, and the
+        // busy timeout makes writers wait their turn instead of failing
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get::<_, String>(0))?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
 
         let db = Self { conn };
         schema::init_schema(&db.conn)?;
@@ -52,6 +86,16 @@ impl Database {
         Ok(db)
     }
 
+    /// Open an existing database for reading only, without schema setup
+    ///
+    /// For the shell hook, which must stay fast and never write.
+    pub fn open_read_only(path: &Path) -> Result<Self> {
+        let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+            .context("Failed to open database")?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        Ok(Self { conn })
+    }
+
     /// Open an in-memory database (for testing)
     #[allow(dead_code)]
     pub fn open_in_memory() -> Result<Self> {
@@ -61,6 +105,31 @@ impl Database {
         Ok(db)
     }
 
+    /// Run `f` in a write transaction, committing only if it succeeds
+    ///
+    /// The whole transaction is retried if the database stays locked by another
+    /// writer. Nested calls join the outermost transaction.
+    pub fn transaction<T>(&self, mut f: impl FnMut(&Self) -> Result<T>) -> Result<T> {
+        if !self.conn.is_autocommit() {
+            return f(self);
+        }
+
+        retry_busy(|| {
+            // IMMEDIATE takes the write lock up front, where the busy timeout applies
+            let tx = Transaction::new_unchecked(&self.conn, TransactionBehavior::Immediate)?;
+            let result = f(self)?;
+            tx.commit()?;
+            Ok(result)
+        })
+    }
+
+    /// Counter that changes whenever another connection commits to the database
+    pub fn data_version(&self) -> Result<i64> {
+        Ok(self
+            .conn
+            .query_row("PRAGMA data_version", [], |row| row.get(0))?)
+    }
+
     /// Get the database file path
     pub fn db_path() -> Result<PathBuf> {
         let proj_dirs = ProjectDirs::from("dev", "hoards", "hoards")
@@ -70,9 +139,32 @@ impl Database {
     }
 }
 
+/// Run a write, retrying with backoff while the database is locked by another connection
+pub(crate) fn retry_busy<T>(mut f: impl FnMut() -> Result<T>) -> Result<T> {
+    let mut attempt = 0;
+    loop {
+        match f() {
+            Err(e) if attempt < BUSY_RETRIES && is_busy(&e) => {
+                attempt += 1;
+                std::thread::sleep(Duration::from_millis(50 << attempt));
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_busy(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<rusqlite::Error>(),
+        Some(rusqlite::Error::SqliteFailure(e, _))
+            if matches!(e.code, ErrorCode::DatabaseBusy | ErrorCode::DatabaseLocked)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::forge::ForgeKind;
     use crate::models::{Bundle, InstallSource, Tool};
 
     // ==================== Tool CRUD Tests ====================
@@ -361,6 +453,205 @@ mod tests {
         Ok(())
     }
 
+    // ==================== GitHub Tests ====================
+
+    #[test]
+    fn test_github_info() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("rg").installed())?;
+        db.insert_tool(&Tool::new("old").installed())?;
+        db.add_labels("rg", &["favorite".to_string()])?;
+
+        let topics = vec!["CLI".to_string(), "search".to_string()];
+        let info = GitHubInfoInput {
+            forge: ForgeKind::GitHub,
+            host: "github.com",
+            repo_owner: "BurntSushi",
+            repo_name: "ripgrep",
+            description: Some("fast grep"),
+            stars: 50000,
+            language: Some("Rust"),
+            homepage: None,
+            license: Some("Unlicense"),
+            archived: false,
+            pushed_at: Some("2026-10-01T12:00:00Z"),
+            latest_release: Some("14.1.1"),
+            open_issues: Some(80),
+            topics: &topics,
+        };
+        assert!(db.set_github_info("rg", info)?);
+        db.set_github_info(
+            "old",
+            GitHubInfoInput {
+                forge: ForgeKind::Gitea,
+                host: "codeberg.org",
+                repo_owner: "someone",
+                repo_name: "old",
+                description: None,
+                stars: 10,
+                language: None,
+                homepage: None,
+                license: None,
+                archived: true,
+                pushed_at: Some("2026-09-01T00:00:00Z"),
+                latest_release: None,
+                open_issues: None,
+                topics: &[],
+            },
+        )?;
+
+        let stored = db.get_github_info("rg")?.unwrap();
+        assert_eq!(stored.forge, ForgeKind::GitHub);
+        assert_eq!(db.get_github_info("old")?.unwrap().host, "codeberg.org");
+        assert_eq!(stored.license.as_deref(), Some("Unlicense"));
+        assert_eq!(stored.latest_release.as_deref(), Some("14.1.1"));
+        assert_eq!(stored.open_issues, Some(80));
+        assert!(!stored.archived);
+
+        // Every write records the day's star count
+        let history = db.get_star_history("rg", 30)?;
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].1, 50000);
+
+        // Topics are stored lowercase and listed with the tool's own labels
+        assert_eq!(db.get_github_topics("rg")?, ["cli", "search"]);
+        assert_eq!(db.get_labels("rg")?, ["cli", "favorite", "search"]);
+        assert_eq!(db.list_tools_by_label("search")?.len(), 1);
+        assert!(db.get_label_counts()?.contains(&("cli".to_string(), 1)));
+
+        // Clearing labels keeps topics; a refresh replaces them
+        db.clear_labels("rg")?;
+        assert_eq!(db.get_labels("rg")?, ["cli", "search"]);
+        let topics = vec!["grep".to_string()];
+        db.set_github_info(
+            "rg",
+            GitHubInfoInput {
+                topics: &topics,
+                ..info
+            },
+        )?;
+        assert_eq!(db.get_labels("rg")?, ["grep"]);
+
+        // Archived, or not pushed to since the cutoff
+        let unmaintained = db.get_unmaintained_tools("2026-01-01T00:00:00Z")?;
+        assert_eq!(unmaintained.len(), 1);
+        assert_eq!(unmaintained[0].0, "old");
+        let unmaintained = db.get_unmaintained_tools("2026-10-10T00:00:00Z")?;
+        assert_eq!(unmaintained.len(), 2);
+
+        // Linking a different repository starts its star history over
+        db.conn.execute(
+            "INSERT INTO github_star_history (tool_id, date, stars)
+             SELECT id, '2020-01-01', 1 FROM tools WHERE name = 'rg'",
+            [],
+        )?;
+        assert_eq!(db.get_star_history("rg", 100_000)?.len(), 2);
+        db.set_github_info(
+            "rg",
+            GitHubInfoInput {
+                repo_owner: "burntsushi",
+                ..info
+            },
+        )?;
+        assert_eq!(db.get_star_history("rg", 100_000)?.len(), 2);
+        db.set_github_info(
+            "rg",
+            GitHubInfoInput {
+                repo_owner: "someone-else",
+                ..info
+            },
+        )?;
+        assert_eq!(db.get_star_history("rg", 100_000)?.len(), 1);
+
+        assert!(db.delete_github_info("rg")?);
+        assert!(db.get_github_info("rg")?.is_none());
+        assert!(db.get_github_topics("rg")?.is_empty());
+        assert!(db.get_star_history("rg", 100_000)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_star_velocity() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        for name in ["rg", "fd", "new"] {
+            db.insert_tool(&Tool::new(name))?;
+        }
+        let history = [
+            ("rg", "2026-08-01", 100),
+            ("rg", "2026-09-01", 150),
+            ("rg", "2026-10-01", 170),
+            ("fd", "2026-09-20", 500),
+            ("fd", "2026-10-01", 700),
+            ("new", "2026-10-01", 1000),
+        ];
+        for (name, date, stars) in history {
+            db.conn.execute(
+                "INSERT INTO github_star_history (tool_id, date, stars)
+                 SELECT id, ?2, ?3 FROM tools WHERE name = ?1",
+                rusqlite::params![name, date, stars],
+            )?;
+        }
+
+        // rg is measured from the last snapshot before the window, fd from its first
+        let velocity = db.get_star_velocity("2026-09-10")?;
+        assert_eq!(
+            velocity,
+            [
+                StarVelocity {
+                    tool_name: "fd".to_string(),
+                    stars: 700,
+                    gained: 200,
+                    since: "2026-09-20".to_string(),
+                },
+                StarVelocity {
+                    tool_name: "rg".to_string(),
+                    stars: 170,
+                    gained: 20,
+                    since: "2026-09-01".to_string(),
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_github_columns_migration() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        conn.execute_batch(
+            "CREATE TABLE tool_github (
+                tool_id INTEGER PRIMARY KEY,
+                repo_owner TEXT NOT NULL,
+                repo_name TEXT NOT NULL,
+                description TEXT,
+                stars INTEGER DEFAULT 0,
+                language TEXT,
+                homepage TEXT,
+                updated_at TEXT NOT NULL
+            );
+            INSERT INTO tool_github (tool_id, repo_owner, repo_name, updated_at)
+            VALUES (1, 'a', 'b', '2026-01-01');",
+        )?;
+
+        // Running twice must not try to add the columns again
+        schema::init_schema(&conn)?;
+        schema::init_schema(&conn)?;
+
+        let (archived, forge, host): (bool, String, String) = conn.query_row(
+            "SELECT archived, forge, host FROM tool_github WHERE tool_id = 1",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert!(!archived);
+        // Rows from before other forges were supported are GitHub's
+        assert_eq!((forge.as_str(), host.as_str()), ("github", "github.com"));
+
+        Ok(())
+    }
+
     // ==================== Usage Tests ====================
 
     #[test]
@@ -383,6 +674,13 @@ mod tests {
         assert_eq!(usage.use_count, 15);
         assert_eq!(usage.last_used, Some("2024-01-02T00:00:00Z".to_string()));
 
+        // Older history imported later doesn't move last_used back, but extends first_seen
+        db.record_usage_on("test", 1, Some("2023-12-01T00:00:00Z"), "2023-12-01")?;
+        let usage = db.get_usage("test")?.unwrap();
+        assert_eq!(usage.use_count, 16);
+        assert_eq!(usage.last_used, Some("2024-01-02T00:00:00Z".to_string()));
+        assert_eq!(usage.first_seen, "2023-12-01T00:00:00Z");
+
         Ok(())
     }
 
@@ -461,6 +759,363 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_record_usage_on_date() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("test").installed())?;
+        let yesterday = (chrono::Utc::now() - chrono::Duration::days(1))
+            .format("%Y-%m-%d")
+            .to_string();
+        db.record_usage_on("test", 4, None, &yesterday)?;
+        db.record_usage("test", 1, None)?;
+
+        let daily = db.get_daily_usage("test", 7)?;
+        assert_eq!(daily[5], 4);
+        assert_eq!(daily[6], 1);
+        assert_eq!(db.get_usage("test")?.unwrap().use_count, 5);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_subcommand_usage() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("git").installed())?;
+        db.record_subcommand_usage("git", "status", 3, Some("2024-01-02T00:00:00+00:00"))?;
+        db.record_subcommand_usage("git", "rebase", 1, None)?;
+        db.record_subcommand_usage("git", "status", 2, Some("2024-01-01T00:00:00+00:00"))?;
+        assert!(!db.record_subcommand_usage("missing", "status", 1, None)?);
+        // Arguments are never stored as subcommands
+        assert!(!db.record_subcommand_usage("git", "https://token@host/repo", 1, None)?);
+
+        let subcommands = db.get_subcommand_usage("git")?;
+        assert_eq!(subcommands.len(), 2);
+        assert_eq!(subcommands[0].subcommand, "status");
+        assert_eq!(subcommands[0].use_count, 5);
+        assert_eq!(
+            subcommands[0].last_used.as_deref(),
+            Some("2024-01-02T00:00:00+00:00")
+        );
+
+        assert_eq!(db.get_all_subcommand_usage()?.len(), 2);
+        assert!(db.delete_subcommand_usage("git", "rebase")?);
+        assert_eq!(db.get_subcommand_usage("git")?.len(), 1);
+
+        db.clear_usage()?;
+        assert!(db.get_subcommand_usage("git")?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_usage_context() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("cargo").installed())?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+        let t1 = "2024-01-01T00:00:00+00:00";
+        let t2 = "2024-01-02T00:00:00+00:00";
+        db.record_usage_context("cargo", "/src/app", Some("/src/app"), t1)?;
+        db.record_usage_context("cargo", "/src/app/sub", Some("/src/app"), t2)?;
+        db.record_usage_context("rg", "/src/app", Some("/src/app"), t1)?;
+        db.record_usage_context("rg", "/src/lib", Some("/src/lib"), t1)?;
+        db.record_usage_context("rg", "/tmp", None, t1)?;
+        assert!(!db.record_usage_context("missing", "/tmp", None, t1)?);
+
+        let app = db.get_project_usage("/src/app")?;
+        assert_eq!(app.len(), 2);
+        assert_eq!(app[0].tool, "cargo");
+        assert_eq!(app[0].use_count, 2);
+        assert_eq!(app[0].last_used.as_deref(), Some(t2));
+        assert_eq!(db.get_project_usage("/tmp")?.len(), 1);
+
+        let projects = db.get_usage_by_project()?;
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].0, "/src/app");
+
+        db.clear_usage()?;
+        assert!(db.get_usage_by_project()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_usage_outcomes() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("cargo").installed())?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+        let t1 = "2024-01-01T00:00:00+00:00";
+        let t2 = "2024-01-02T00:00:00+00:00";
+        for ms in [100, 110, 120, 130, 5000] {
+            db.record_outcome("cargo", Some(0), Some(ms), t1)?;
+        }
+        db.record_outcome("cargo", Some(101), Some(900), t2)?;
+        // Interrupted or cut off by a closed pipe, not failed
+        db.record_outcome("cargo", Some(130), None, t1)?;
+        db.record_outcome("rg", None, Some(20), t1)?;
+        assert!(!db.record_outcome("missing", Some(1), Some(1), t1)?);
+
+        let cargo = db.get_outcome_stats("cargo")?.unwrap();
+        assert_eq!(cargo.runs, 7);
+        assert_eq!(cargo.failures, 1);
+        assert_eq!(cargo.last_failure.as_deref(), Some(t2));
+        assert!(!cargo.is_failing());
+        assert_eq!(format_duration_ms(340), "340ms");
+        assert_eq!(format_duration_ms(1250), "1.2s");
+        assert_eq!(format_duration_ms(192_000), "3m 12s");
+        let p50 = cargo.p50_ms.unwrap();
+        assert!((100..=150).contains(&p50), "p50 was {p50}");
+        let p95 = cargo.p95_ms.unwrap();
+        assert!((4000..=6000).contains(&p95), "p95 was {p95}");
+
+        // A duration alone doesn't make a run with a known status
+        let rg = db.get_outcome_stats("rg")?.unwrap();
+        assert_eq!(rg.runs, 0);
+        assert_eq!(rg.success_rate(), None);
+        assert!(rg.p50_ms.is_some());
+
+        let all = db.get_all_outcome_stats()?;
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].0, "cargo");
+
+        db.clear_usage()?;
+        assert!(db.get_all_outcome_stats()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_usage_snapshots() -> Result<()> {
+        let laptop = Database::open_in_memory()?;
+        laptop.insert_tool(&Tool::new("rg").installed())?;
+        laptop.insert_tool(&Tool::new("jq").installed())?;
+        laptop.record_usage_on("rg", 5, Some("2024-01-02T00:00:00+00:00"), "2024-01-02")?;
+        laptop.record_usage_on("jq", 1, Some("2024-01-01T00:00:00+00:00"), "2024-01-01")?;
+
+        let snapshot = laptop.export_usage_snapshot("laptop")?;
+        assert_eq!(snapshot.host, "laptop");
+        assert_eq!(snapshot.tools.len(), 2);
+        assert_eq!(snapshot.tools[1].daily.get("2024-01-02"), Some(&5));
+
+        let desktop = Database::open_in_memory()?;
+        desktop.insert_tool(&Tool::new("rg").installed())?;
+        desktop.insert_tool(&Tool::new("fd").installed())?;
+        desktop.record_usage_on("rg", 2, Some("2024-01-03T00:00:00+00:00"), "2024-01-03")?;
+
+        // Importing again replaces the host's snapshot instead of adding to it
+        assert_eq!(desktop.import_usage_snapshot(&snapshot, false)?, 2);
+        assert_eq!(desktop.import_usage_snapshot(&snapshot, false)?, 2);
+
+        // An older snapshot doesn't replace a newer one unless forced
+        let older = UsageSnapshot {
+            exported_at: "2024-01-01T00:00:00+00:00".to_string(),
+            tools: Vec::new(),
+            ..snapshot.clone()
+        };
+        assert!(desktop.import_usage_snapshot(&older, false).is_err());
+        assert_eq!(desktop.get_hosts()?[0].exported_at, snapshot.exported_at);
+        assert_eq!(desktop.import_usage_snapshot(&older, true)?, 0);
+        assert_eq!(desktop.import_usage_snapshot(&snapshot, false)?, 2);
+
+        let hosts = desktop.get_hosts()?;
+        assert_eq!(hosts.len(), 1);
+        assert_eq!((hosts[0].tools, hosts[0].uses), (2, 6));
+
+        // Local usage is untouched, the merged view adds hosts up
+        assert_eq!(desktop.get_usage("rg")?.unwrap().use_count, 2);
+        let merged = desktop.get_all_usage_all_hosts()?;
+        assert_eq!(merged[0].0, "rg");
+        assert_eq!(merged[0].1.use_count, 7);
+        assert_eq!(
+            merged[0].1.last_used.as_deref(),
+            Some("2024-01-03T00:00:00+00:00")
+        );
+        assert!(merged.iter().any(|(name, _)| name == "jq"));
+        assert_eq!(desktop.get_tool_usage_by_host("rg")?[0].usage.use_count, 5);
+
+        // fd is unused everywhere, jq isn't tracked here, rg is used
+        assert_eq!(desktop.get_unused_tools()?.len(), 1);
+        desktop.insert_tool(&Tool::new("jq").installed())?;
+        assert_eq!(desktop.get_unused_tools()?.len(), 2);
+        let unused: Vec<_> = desktop
+            .get_unused_tools_all_hosts()?
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(unused, vec!["fd"]);
+
+        assert!(desktop.delete_host_usage("laptop")?);
+        assert!(desktop.get_hosts()?.is_empty());
+        assert_eq!(desktop.get_all_usage_all_hosts()?.len(), 1);
+
+        let future = UsageSnapshot {
+            version: SNAPSHOT_VERSION + 1,
+            ..snapshot
+        };
+        assert!(desktop.import_usage_snapshot(&future, false).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_purge_usage() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("git").installed())?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+        let t1 = "2024-01-01T10:00:00+00:00";
+        let t2 = "2024-01-05T10:00:00+00:00";
+        db.record_usage_on("git", 3, Some(t1), "2024-01-01")?;
+        db.record_usage_on("git", 2, Some(t2), "2024-01-05")?;
+        db.record_subcommand_usage("git", "push", 2, Some(t2))?;
+        db.record_subcommand_usage("git", "status", 3, Some(t1))?;
+        db.record_usage_context("git", "/src/app", None, t2)?;
+        db.record_outcome("git", Some(0), Some(100), t2)?;
+        db.record_usage_on("rg", 4, Some(t2), "2024-01-05")?;
+
+        // Only the days since are rolled back
+        assert_eq!(db.purge_usage(Some("git"), Some("2024-01-03"))?, 2);
+        let git = db.get_usage("git")?.unwrap();
+        assert_eq!(git.use_count, 3);
+        assert_eq!(git.last_used.as_deref(), Some("2024-01-01T00:00:00+00:00"));
+        assert_eq!(db.get_subcommand_usage("git")?.len(), 1);
+        assert!(db.get_project_usage("/src/app")?.is_empty());
+        assert_eq!(db.get_outcome_stats("git")?, None);
+        assert_eq!(db.get_usage("rg")?.unwrap().use_count, 4);
+
+        // Tools left without uses disappear
+        assert_eq!(db.purge_usage(None, Some("2024-01-03"))?, 4);
+        assert!(db.get_usage("rg")?.is_none());
+
+        // A tool alone is forgotten entirely
+        assert_eq!(db.purge_usage(Some("git"), None)?, 3);
+        assert!(db.get_all_usage()?.is_empty());
+        assert!(db.get_subcommand_usage("git")?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_concurrent_writers_wait_for_lock() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("hoards.db");
+        let db = Database::open_at(&path)?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+
+        // Another process holds the write lock for a moment
+        let locked = std::sync::Barrier::new(2);
+        std::thread::scope(|s| -> Result<()> {
+            s.spawn(|| {
+                let other = Database::open_at(&path).unwrap();
+                other
+                    .transaction(|_| {
+                        locked.wait();
+                        std::thread::sleep(Duration::from_millis(300));
+                        Ok(())
+                    })
+                    .unwrap();
+            });
+            locked.wait();
+            assert!(db.record_usage("rg", 1, None)?);
+            Ok(())
+        })?;
+
+        assert_eq!(db.get_usage("rg")?.unwrap().use_count, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_data_version_tracks_external_writes() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("hoards.db");
+        let db = Database::open_at(&path)?;
+        let other = Database::open_at(&path)?;
+
+        let before = db.data_version()?;
+        db.insert_tool(&Tool::new("own").installed())?;
+        assert_eq!(db.data_version()?, before);
+
+        other.insert_tool(&Tool::new("external").installed())?;
+        assert_ne!(db.data_version()?, before);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_transaction() -> Result<()> {
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+
+        db.transaction(|db| {
+            db.record_usage("rg", 2, None)?;
+            db.record_usage("rg", 1, None)
+        })?;
+        assert_eq!(db.get_usage("rg")?.unwrap().use_count, 3);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_history_checkpoints() -> Result<()> {
+        use crate::history::{HistoryCheckpoint, TailFingerprint};
+
+        let db = Database::open_in_memory()?;
+        assert!(db.get_history_checkpoint("/h")?.is_none());
+
+        let checkpoint = HistoryCheckpoint {
+            inode: 42,
+            offset: 1024,
+            last_timestamp: Some(1704067200),
+            tail: TailFingerprint::of(b"git status\n"),
+        };
+        db.set_history_checkpoint("/h", &checkpoint)?;
+        assert_eq!(db.get_history_checkpoint("/h")?, Some(checkpoint.clone()));
+        assert_eq!(db.count_raw_history_checkpoints()?, 0);
+
+        // Older versions stored the history bytes themselves
+        db.conn.execute(
+            "UPDATE history_checkpoints SET tail = ?1",
+            [b"git commit -m secret\n".to_vec()],
+        )?;
+        assert_eq!(db.count_raw_history_checkpoints()?, 1);
+        assert_eq!(
+            db.get_history_checkpoint("/h")?.unwrap().tail,
+            TailFingerprint::of(b"git commit -m secret\n")
+        );
+        assert_eq!(db.fingerprint_history_checkpoints()?, 1);
+        assert_eq!(db.count_raw_history_checkpoints()?, 0);
+        assert_eq!(
+            db.get_history_checkpoint("/h")?.unwrap().tail,
+            TailFingerprint::of(b"git commit -m secret\n")
+        );
+
+        db.clear_history_checkpoints()?;
+        assert!(db.get_history_checkpoint("/h")?.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_disk_usage() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        db.insert_tool(&Tool::new("ripgrep").installed())?;
+
+        assert!(db.set_disk_usage("ripgrep", 4096)?);
+        assert!(!db.set_disk_usage("nonexistent", 1)?);
+
+        // Re-measuring replaces the previous value
+        db.set_disk_usage("ripgrep", 8192)?;
+        let sizes = db.get_all_disk_usage()?;
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(sizes["ripgrep"], 8192);
+
+        Ok(())
+    }
+
     // ==================== Search Tests ====================
 
     #[test]
@@ -537,9 +1192,7 @@ mod tests {
         assert_eq!(daily[6], 8);
 
         // Previous days should be 0
-        for i in 0..6 {
-            assert_eq!(daily[i], 0);
-        }
+        assert!(daily[..6].iter().all(|&x| x == 0));
 
         Ok(())
     }
@@ -577,4 +1230,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_release_notes_cache() -> Result<()> {
+        let db = Database::open_in_memory()?;
+
+        assert_eq!(db.get_release_notes("ripgrep", "13.0.0", "14.1.0")?, None);
+        db.cache_release_notes("ripgrep", "13.0.0", "14.0.0", "old")?;
+        db.cache_release_notes("ripgrep", "13.0.0", "14.1.0", "new")?;
+        db.cache_release_notes("fd", "9.0.0", "10.0.0", "fd")?;
+
+        // Only the latest range is kept per tool
+        assert_eq!(db.get_release_notes("ripgrep", "13.0.0", "14.0.0")?, None);
+        assert_eq!(
+            db.get_release_notes("ripgrep", "13.0.0", "14.1.0")?
+                .as_deref(),
+            Some("new")
+        );
+        assert_eq!(
+            db.get_release_notes("fd", "9.0.0", "10.0.0")?.as_deref(),
+            Some("fd")
+        );
+
+        Ok(())
+    }
 }
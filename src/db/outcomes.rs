@@ -0,0 +1,219 @@
+//! Exit status and duration database operations
+
+use anyhow::Result;
+use rusqlite::params;
+
+use super::{Database, retry_busy};
+
+/// Duration buckets per doubling; 4 keeps percentiles within ~20%
+const BUCKETS_PER_OCTAVE: f64 = 4.0;
+
+/// Exit codes that don't mean the tool failed: Ctrl-C (SIGINT) and a closed pipe (SIGPIPE)
+const NOT_FAILURES: [i32; 2] = [130, 141];
+
+/// Runs needed before a tool can be called failing
+const FAILING_MIN_RUNS: i64 = 5;
+
+/// Share of failed runs from which a tool is called failing
+const FAILING_RATE: f64 = 0.25;
+
+/// How a tool's runs ended, as reported by the shell hook
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct OutcomeStats {
+    /// Runs with a known exit status
+    pub runs: i64,
+    /// Runs that exited non-zero
+    pub failures: i64,
+    pub last_failure: Option<String>,
+    /// Median wall-clock duration
+    pub p50_ms: Option<u64>,
+    pub p95_ms: Option<u64>,
+}
+
+impl OutcomeStats {
+    /// Share of runs that succeeded, if any run was recorded
+    pub fn success_rate(&self) -> Option<f64> {
+        (self.runs > 0).then(|| (self.runs - self.failures) as f64 / self.runs as f64)
+    }
+
+    /// Share of runs that failed (0.0 when nothing was recorded)
+    pub fn failure_rate(&self) -> f64 {
+        self.success_rate().map_or(0.0, |rate| 1.0 - rate)
+    }
+
+    /// Whether the tool fails often enough, over enough runs, to be worth flagging
+    pub fn is_failing(&self) -> bool {
+        self.runs >= FAILING_MIN_RUNS && self.failure_rate() >= FAILING_RATE
+    }
+}
+
+/// Whether an exit status counts as a failure
+pub fn is_failure(status: i32) -> bool {
+    status != 0 && !NOT_FAILURES.contains(&status)
+}
+
+/// Human-readable duration: `340ms`, `1.2s`, `3m 12s`
+pub fn format_duration_ms(ms: u64) -> String {
+    match ms {
+        0..1_000 => format!("{}ms", ms),
+        1_000..60_000 => format!("{:.1}s", ms as f64 / 1000.0),
+        _ => format!("{}m {}s", ms / 60_000, (ms % 60_000) / 1000),
+    }
+}
+
+fn duration_bucket(ms: u64) -> i64 {
+    ((ms as f64 + 1.0).log2() * BUCKETS_PER_OCTAVE).floor() as i64
+}
+
+/// Representative duration of a bucket (its geometric midpoint)
+fn bucket_ms(bucket: i64) -> u64 {
+    ((2f64.powf((bucket as f64 + 0.5) / BUCKETS_PER_OCTAVE) - 1.0).round()).max(0.0) as u64
+}
+
+/// Duration at a percentile of `(bucket, count)` pairs sorted by bucket
+fn percentile(buckets: &[(i64, i64)], pct: f64) -> Option<u64> {
+    let total: i64 = buckets.iter().map(|(_, count)| count).sum();
+    if total == 0 {
+        return None;
+    }
+    let rank = ((total as f64 * pct).ceil() as i64).max(1);
+    let mut seen = 0;
+    for (bucket, count) in buckets {
+        seen += count;
+        if seen >= rank {
+            return Some(bucket_ms(*bucket));
+        }
+    }
+    None
+}
+
+impl Database {
+    // ==================== Usage Outcomes ====================
+
+    /// Record how one run of a tool ended
+    ///
+    /// Either half may be missing: some shells report no duration, and a status
+    /// without a duration still counts towards the success rate.
+    pub fn record_outcome(
+        &self,
+        tool_name: &str,
+        status: Option<i32>,
+        duration_ms: Option<u64>,
+        when: &str,
+    ) -> Result<bool> {
+        let mut recorded = false;
+
+        if let Some(status) = status {
+            let failed = is_failure(status);
+            let updated = retry_busy(|| {
+                Ok(self.conn.execute(
+                    "INSERT INTO usage_outcomes (tool_id, runs, failures, last_failure)
+                     SELECT id, 1, ?2, CASE WHEN ?2 THEN ?3 END FROM tools WHERE name = ?1
+                     ON CONFLICT(tool_id) DO UPDATE SET
+                        runs = runs + 1,
+                        failures = failures + ?2,
+                        last_failure = CASE
+                            WHEN ?2 AND (last_failure IS NULL OR ?3 > last_failure) THEN ?3
+                            ELSE last_failure END",
+                    params![tool_name, failed, when],
+                )?)
+            })?;
+            recorded |= updated > 0;
+        }
+
+        if let Some(ms) = duration_ms {
+            let updated = retry_busy(|| {
+                Ok(self.conn.execute(
+                    "INSERT INTO usage_durations (tool_id, bucket, count)
+                     SELECT id, ?2, 1 FROM tools WHERE name = ?1
+                     ON CONFLICT(tool_id, bucket) DO UPDATE SET count = count + 1",
+                    params![tool_name, duration_bucket(ms)],
+                )?)
+            })?;
+            recorded |= updated > 0;
+        }
+
+        Ok(recorded)
+    }
+
+    /// Success rate and duration percentiles of a tool, if any run was recorded
+    pub fn get_outcome_stats(&self, tool_name: &str) -> Result<Option<OutcomeStats>> {
+        Ok(self
+            .outcome_stats(Some(tool_name))?
+            .into_iter()
+            .next()
+            .map(|(_, stats)| stats))
+    }
+
+    /// Outcome statistics for every tool with recorded runs, by name
+    pub fn get_all_outcome_stats(&self) -> Result<Vec<(String, OutcomeStats)>> {
+        self.outcome_stats(None)
+    }
+
+    fn outcome_stats(&self, tool_name: Option<&str>) -> Result<Vec<(String, OutcomeStats)>> {
+        let mut stats: Vec<(String, OutcomeStats)> = Vec::new();
+
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, uo.runs, uo.failures, uo.last_failure
+             FROM usage_outcomes uo
+             INNER JOIN tools t ON uo.tool_id = t.id
+             WHERE ?1 IS NULL OR t.name = ?1
+             ORDER BY t.name",
+        )?;
+        let outcomes = stmt
+            .query_map([tool_name], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    OutcomeStats {
+                        runs: row.get(1)?,
+                        failures: row.get(2)?,
+                        last_failure: row.get(3)?,
+                        ..Default::default()
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        stats.extend(outcomes);
+
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, ud.bucket, ud.count
+             FROM usage_durations ud
+             INNER JOIN tools t ON ud.tool_id = t.id
+             WHERE ?1 IS NULL OR t.name = ?1
+             ORDER BY t.name, ud.bucket",
+        )?;
+        let rows = stmt
+            .query_map([tool_name], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut durations: Vec<(String, Vec<(i64, i64)>)> = Vec::new();
+        for (name, bucket, count) in rows {
+            match durations.last_mut() {
+                Some((last, buckets)) if *last == name => buckets.push((bucket, count)),
+                _ => durations.push((name, vec![(bucket, count)])),
+            }
+        }
+
+        for (name, buckets) in durations {
+            let index = match stats.iter().position(|(n, _)| *n == name) {
+                Some(index) => index,
+                None => {
+                    stats.push((name, OutcomeStats::default()));
+                    stats.len() - 1
+                }
+            };
+            let entry = &mut stats[index].1;
+            entry.p50_ms = percentile(&buckets, 0.50);
+            entry.p95_ms = percentile(&buckets, 0.95);
+        }
+
+        stats.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(stats)
+    }
+}
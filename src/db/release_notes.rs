@@ -0,0 +1,52 @@
+//! Release notes cached per tool and version range
+
+use anyhow::Result;
+use chrono::Utc;
+use rusqlite::{OptionalExtension, params};
+
+use super::Database;
+
+impl Database {
+    // ==================== Release Notes ====================
+
+    /// Cached release notes (JSON) for an update from one version to another
+    pub fn get_release_notes(
+        &self,
+        tool_name: &str,
+        from: &str,
+        to: &str,
+    ) -> Result<Option<String>> {
+        let content = self
+            .conn
+            .query_row(
+                "SELECT content FROM release_notes_cache
+                 WHERE tool_name = ?1 AND from_version = ?2 AND to_version = ?3",
+                params![tool_name, from, to],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(content)
+    }
+
+    /// Cache release notes, dropping those of older ranges for the tool
+    pub fn cache_release_notes(
+        &self,
+        tool_name: &str,
+        from: &str,
+        to: &str,
+        content: &str,
+    ) -> Result<()> {
+        self.transaction(|db| {
+            db.conn.execute(
+                "DELETE FROM release_notes_cache WHERE tool_name = ?1",
+                [tool_name],
+            )?;
+            db.conn.execute(
+                "INSERT INTO release_notes_cache (tool_name, from_version, to_version, content, fetched_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![tool_name, from, to, content, Utc::now().to_rfc3339()],
+            )?;
+            Ok(())
+        })
+    }
+}
@@ -73,9 +73,31 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
             stars INTEGER DEFAULT 0,
             language TEXT,
             homepage TEXT,
+            license TEXT,
+            archived INTEGER NOT NULL DEFAULT 0,
+            pushed_at TEXT,
+            latest_release TEXT,
+            open_issues INTEGER,
+            forge TEXT NOT NULL DEFAULT 'github',
+            host TEXT NOT NULL DEFAULT 'github.com',
             updated_at TEXT NOT NULL
         );
 
+        -- Star count per tool and day, recorded whenever GitHub info is stored
+        CREATE TABLE IF NOT EXISTS github_star_history (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            date TEXT NOT NULL,  -- YYYY-MM-DD format
+            stars INTEGER NOT NULL,
+            PRIMARY KEY (tool_id, date)
+        );
+
+        -- Repository topics, shown alongside labels
+        CREATE TABLE IF NOT EXISTS tool_github_topics (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            topic TEXT NOT NULL,
+            PRIMARY KEY (tool_id, topic)
+        );
+
         CREATE TABLE IF NOT EXISTS tool_usage (
             tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
             use_count INTEGER NOT NULL DEFAULT 0,
@@ -94,6 +116,88 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_usage_daily_date ON usage_daily(date);
 
+        -- Per-subcommand usage for tools opted into subcommand tracking
+        CREATE TABLE IF NOT EXISTS usage_subcommands (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            subcommand TEXT NOT NULL,
+            use_count INTEGER NOT NULL DEFAULT 0,
+            last_used TEXT,
+            PRIMARY KEY (tool_id, subcommand)
+        );
+
+        -- Where tools are used: working directory and its git repository (from the shell hook)
+        CREATE TABLE IF NOT EXISTS usage_context (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            directory TEXT NOT NULL,
+            git_root TEXT,
+            use_count INTEGER NOT NULL DEFAULT 0,
+            last_used TEXT,
+            PRIMARY KEY (tool_id, directory)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_usage_context_git_root ON usage_context(git_root);
+
+        -- Exit status of tracked tools (from the shell hook)
+        CREATE TABLE IF NOT EXISTS usage_outcomes (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            runs INTEGER NOT NULL DEFAULT 0,
+            failures INTEGER NOT NULL DEFAULT 0,
+            last_failure TEXT
+        );
+
+        -- Wall-clock durations of tracked tools, in log-scale buckets
+        CREATE TABLE IF NOT EXISTS usage_durations (
+            tool_id INTEGER NOT NULL REFERENCES tools(id) ON DELETE CASCADE,
+            bucket INTEGER NOT NULL,
+            count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (tool_id, bucket)
+        );
+
+        -- API responses kept for conditional requests (ETag / If-None-Match)
+        CREATE TABLE IF NOT EXISTS http_cache (
+            url TEXT PRIMARY KEY,
+            etag TEXT NOT NULL,
+            body TEXT NOT NULL,
+            fetched_at TEXT NOT NULL
+        );
+
+        -- Machines whose usage was imported (`hoards usage import`)
+        CREATE TABLE IF NOT EXISTS usage_hosts (
+            host TEXT PRIMARY KEY,
+            exported_at TEXT NOT NULL,
+            imported_at TEXT NOT NULL
+        );
+
+        -- Their usage, by tool name since the tool may not be tracked here
+        CREATE TABLE IF NOT EXISTS host_usage (
+            host TEXT NOT NULL REFERENCES usage_hosts(host) ON DELETE CASCADE,
+            tool_name TEXT NOT NULL,
+            use_count INTEGER NOT NULL DEFAULT 0,
+            last_used TEXT,
+            first_seen TEXT,
+            PRIMARY KEY (host, tool_name)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_host_usage_tool ON host_usage(tool_name);
+
+        CREATE TABLE IF NOT EXISTS host_usage_daily (
+            host TEXT NOT NULL REFERENCES usage_hosts(host) ON DELETE CASCADE,
+            tool_name TEXT NOT NULL,
+            date TEXT NOT NULL,  -- YYYY-MM-DD format
+            count INTEGER NOT NULL DEFAULT 0,
+            PRIMARY KEY (host, tool_name, date)
+        );
+
+        -- Where the last history scan stopped in each shell history file
+        CREATE TABLE IF NOT EXISTS history_checkpoints (
+            path TEXT PRIMARY KEY,
+            inode INTEGER NOT NULL,
+            byte_offset INTEGER NOT NULL,
+            last_timestamp INTEGER,
+            tail BLOB NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS extraction_cache (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             repo_owner TEXT NOT NULL,
@@ -111,15 +215,74 @@ pub fn init_schema(conn: &Connection) -> Result<()> {
 
         CREATE INDEX IF NOT EXISTS idx_bundles_name ON bundles(name);
         CREATE INDEX IF NOT EXISTS idx_tool_labels_label ON tool_labels(label);
+        CREATE INDEX IF NOT EXISTS idx_tool_github_topics_topic ON tool_github_topics(topic);
         CREATE INDEX IF NOT EXISTS idx_extraction_cache_repo ON extraction_cache(repo_owner, repo_name);
 
+        -- Last measured disk footprint per tool (see `insights disk`)
+        CREATE TABLE IF NOT EXISTS tool_disk_usage (
+            tool_id INTEGER PRIMARY KEY REFERENCES tools(id) ON DELETE CASCADE,
+            bytes INTEGER NOT NULL DEFAULT 0,
+            measured_at TEXT NOT NULL
+        );
+
         CREATE TABLE IF NOT EXISTS ai_cache (
             cache_key TEXT PRIMARY KEY,
             content TEXT NOT NULL,
             created_at TEXT NOT NULL
         );
+
+        -- Release notes between two versions of a tool (JSON), see `updates --notes`
+        CREATE TABLE IF NOT EXISTS release_notes_cache (
+            tool_name TEXT NOT NULL,
+            from_version TEXT NOT NULL,
+            to_version TEXT NOT NULL,
+            content TEXT NOT NULL,
+            fetched_at TEXT NOT NULL,
+            PRIMARY KEY (tool_name, from_version, to_version)
+        );
         "#,
     )?;
 
+    // Columns added after their table was first created
+    add_column(conn, "tool_github", "license", "TEXT")?;
+    add_column(
+        conn,
+        "tool_github",
+        "archived",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    add_column(conn, "tool_github", "pushed_at", "TEXT")?;
+    add_column(conn, "tool_github", "latest_release", "TEXT")?;
+    add_column(conn, "tool_github", "open_issues", "INTEGER")?;
+    add_column(
+        conn,
+        "tool_github",
+        "forge",
+        "TEXT NOT NULL DEFAULT 'github'",
+    )?;
+    add_column(
+        conn,
+        "tool_github",
+        "host",
+        "TEXT NOT NULL DEFAULT 'github.com'",
+    )?;
+
+    Ok(())
+}
+
+/// Add a column to an existing table unless it is already there
+fn add_column(conn: &Connection, table: &str, column: &str, definition: &str) -> Result<()> {
+    let exists = conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1",
+            table
+        ))?
+        .exists([column])?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {} ADD COLUMN {} {}",
+            table, column, definition
+        ))?;
+    }
     Ok(())
 }
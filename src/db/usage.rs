@@ -4,10 +4,11 @@ use anyhow::Result;
 use chrono::Utc;
 use rusqlite::{OptionalExtension, params};
 
+use crate::cmdline::looks_like_subcommand;
 use crate::models::Tool;
 
-use super::Database;
 use super::tools::tool_from_row;
+use super::{Database, retry_busy};
 
 /// Tool usage statistics
 #[derive(Debug, Clone)]
@@ -17,6 +18,14 @@ pub struct ToolUsage {
     pub first_seen: String,
 }
 
+/// Usage statistics for one subcommand of a tool
+#[derive(Debug, Clone)]
+pub struct SubcommandUsage {
+    pub subcommand: String,
+    pub use_count: i64,
+    pub last_used: Option<String>,
+}
+
 impl Database {
     // ==================== Usage Tracking ====================
 
@@ -28,42 +37,137 @@ impl Database {
         count: i64,
         last_used: Option<&str>,
     ) -> Result<bool> {
-        let tool_id: i64 =
-            match self
-                .conn
-                .query_row("SELECT id FROM tools WHERE name = ?1", [tool_name], |row| {
-                    row.get(0)
-                }) {
-                Ok(id) => id,
-                Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(false),
-                Err(e) => return Err(e.into()),
-            };
-
-        let now = Utc::now();
-        let now_str = now.to_rfc3339();
-        let today = now.format("%Y-%m-%d").to_string();
+        let today = Utc::now().format("%Y-%m-%d").to_string();
+        self.record_usage_on(tool_name, count, last_used, &today)
+    }
 
-        // Try to update existing record, or insert new one
-        let updated = self.conn.execute(
-            "UPDATE tool_usage SET use_count = use_count + ?1, last_used = COALESCE(?2, last_used), updated_at = ?3 WHERE tool_id = ?4",
-            params![count, last_used, now_str, tool_id],
-        )?;
+    /// Record tool usage that happened on a given day (`YYYY-MM-DD`)
+    pub fn record_usage_on(
+        &self,
+        tool_name: &str,
+        count: i64,
+        last_used: Option<&str>,
+        date: &str,
+    ) -> Result<bool> {
+        // One transaction, so a retry after SQLITE_BUSY never counts twice
+        self.transaction(|db| {
+            let tool_id: i64 =
+                match db
+                    .conn
+                    .query_row("SELECT id FROM tools WHERE name = ?1", [tool_name], |row| {
+                        row.get(0)
+                    }) {
+                    Ok(id) => id,
+                    Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(false),
+                    Err(e) => return Err(e.into()),
+                };
+
+            let now_str = Utc::now().to_rfc3339();
+
+            // Try to update existing record, or insert new one.
+            // History imports can arrive out of order, so last_used/first_seen only ever widen.
+            let updated = db.conn.execute(
+                "UPDATE tool_usage SET use_count = use_count + ?1,
+                    last_used = CASE WHEN last_used IS NULL OR ?2 > last_used THEN COALESCE(?2, last_used) ELSE last_used END,
+                    first_seen = CASE WHEN ?2 IS NOT NULL AND ?2 < first_seen THEN ?2 ELSE first_seen END,
+                    updated_at = ?3
+                 WHERE tool_id = ?4",
+                params![count, last_used, now_str, tool_id],
+            )?;
 
-        if updated == 0 {
-            self.conn.execute(
-                "INSERT INTO tool_usage (tool_id, use_count, last_used, first_seen, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
-                params![tool_id, count, last_used, now_str, now_str],
+            if updated == 0 {
+                db.conn.execute(
+                    "INSERT INTO tool_usage (tool_id, use_count, last_used, first_seen, updated_at) VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![tool_id, count, last_used, last_used.unwrap_or(&now_str), now_str],
+                )?;
+            }
+
+            // Track daily usage for sparklines
+            db.conn.execute(
+                "INSERT INTO usage_daily (tool_id, date, count) VALUES (?1, ?2, ?3)
+                 ON CONFLICT(tool_id, date) DO UPDATE SET count = count + ?3",
+                params![tool_id, date, count],
             )?;
+
+            Ok(true)
+        })
+    }
+
+    /// Record uses of a tool's subcommand (e.g. `rebase` for git)
+    ///
+    /// Anything that doesn't look like a subcommand name is refused, so arguments
+    /// (paths, URLs, tokens) never end up in the database.
+    pub fn record_subcommand_usage(
+        &self,
+        tool_name: &str,
+        subcommand: &str,
+        count: i64,
+        last_used: Option<&str>,
+    ) -> Result<bool> {
+        if !looks_like_subcommand(subcommand) {
+            return Ok(false);
         }
 
-        // Track daily usage for sparklines
-        self.conn.execute(
-            "INSERT INTO usage_daily (tool_id, date, count) VALUES (?1, ?2, ?3)
-             ON CONFLICT(tool_id, date) DO UPDATE SET count = count + ?3",
-            params![tool_id, today, count],
+        let inserted = retry_busy(|| {
+            Ok(self.conn.execute(
+                "INSERT INTO usage_subcommands (tool_id, subcommand, use_count, last_used)
+                 SELECT id, ?2, ?3, ?4 FROM tools WHERE name = ?1
+                 ON CONFLICT(tool_id, subcommand) DO UPDATE SET
+                    use_count = use_count + ?3,
+                    last_used = CASE WHEN last_used IS NULL OR ?4 > last_used THEN COALESCE(?4, last_used) ELSE last_used END",
+                params![tool_name, subcommand, count, last_used],
+            )?)
+        })?;
+        Ok(inserted > 0)
+    }
+
+    /// Get subcommand usage for a tool, most used first
+    pub fn get_subcommand_usage(&self, tool_name: &str) -> Result<Vec<SubcommandUsage>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT us.subcommand, us.use_count, us.last_used
+             FROM usage_subcommands us
+             INNER JOIN tools t ON us.tool_id = t.id
+             WHERE t.name = ?1
+             ORDER BY us.use_count DESC, us.subcommand",
         )?;
 
-        Ok(true)
+        let results = stmt
+            .query_map([tool_name], |row| {
+                Ok(SubcommandUsage {
+                    subcommand: row.get(0)?,
+                    use_count: row.get(1)?,
+                    last_used: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// Every stored subcommand as (tool, binary, subcommand)
+    pub fn get_all_subcommand_usage(&self) -> Result<Vec<(String, String, String)>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT t.name, COALESCE(t.binary_name, t.name), us.subcommand
+             FROM usage_subcommands us
+             INNER JOIN tools t ON us.tool_id = t.id
+             ORDER BY t.name, us.subcommand",
+        )?;
+
+        let results = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(results)
+    }
+
+    /// Forget one subcommand of a tool
+    pub fn delete_subcommand_usage(&self, tool_name: &str, subcommand: &str) -> Result<bool> {
+        let deleted = self.conn.execute(
+            "DELETE FROM usage_subcommands
+             WHERE tool_id = (SELECT id FROM tools WHERE name = ?1) AND subcommand = ?2",
+            params![tool_name, subcommand],
+        )?;
+        Ok(deleted > 0)
     }
 
     /// Match a command to a tracked tool by binary or name
@@ -148,9 +252,94 @@ impl Database {
     /// Clear all usage data
     pub fn clear_usage(&self) -> Result<()> {
         self.conn.execute("DELETE FROM tool_usage", [])?;
+        self.conn.execute("DELETE FROM usage_daily", [])?;
+        self.conn.execute("DELETE FROM usage_subcommands", [])?;
+        self.conn.execute("DELETE FROM usage_context", [])?;
+        self.conn.execute("DELETE FROM usage_outcomes", [])?;
+        self.conn.execute("DELETE FROM usage_durations", [])?;
         Ok(())
     }
 
+    /// Forget usage of one tool and/or since a day (`YYYY-MM-DD`), returning the uses removed
+    ///
+    /// Counts are rolled back by the daily history. Subcommand and directory
+    /// records touched since then go as a whole, and so do the exit statuses
+    /// and durations of affected tools, which aren't kept per day.
+    pub fn purge_usage(&self, tool_name: Option<&str>, since: Option<&str>) -> Result<i64> {
+        const TOOL_FILTER: &str =
+            "(?1 IS NULL OR tool_id = (SELECT id FROM tools WHERE name = ?1))";
+
+        self.transaction(|db| {
+            let Some(since) = since else {
+                // Everything about the tool
+                let removed: i64 = db.conn.query_row(
+                    &format!(
+                        "SELECT COALESCE(SUM(use_count), 0) FROM tool_usage WHERE {TOOL_FILTER}"
+                    ),
+                    params![tool_name],
+                    |row| row.get(0),
+                )?;
+                for table in [
+                    "tool_usage",
+                    "usage_daily",
+                    "usage_subcommands",
+                    "usage_context",
+                    "usage_outcomes",
+                    "usage_durations",
+                ] {
+                    db.conn.execute(
+                        &format!("DELETE FROM {table} WHERE {TOOL_FILTER}"),
+                        params![tool_name],
+                    )?;
+                }
+                return Ok(removed);
+            };
+
+            let mut stmt = db.conn.prepare(&format!(
+                "SELECT tool_id, SUM(count) FROM usage_daily
+                 WHERE date >= ?2 AND {TOOL_FILTER} GROUP BY tool_id"
+            ))?;
+            let affected = stmt
+                .query_map(params![tool_name, since], |row| {
+                    Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+
+            for (tool_id, count) in &affected {
+                db.conn.execute(
+                    "UPDATE tool_usage SET use_count = use_count - ?2,
+                        last_used = CASE WHEN last_used >= ?3 THEN
+                            (SELECT MAX(date) || 'T00:00:00+00:00' FROM usage_daily
+                             WHERE tool_id = ?1 AND date < ?3)
+                            ELSE last_used END
+                     WHERE tool_id = ?1",
+                    params![tool_id, count, since],
+                )?;
+                db.conn.execute(
+                    "DELETE FROM tool_usage WHERE tool_id = ?1 AND use_count <= 0",
+                    [tool_id],
+                )?;
+                db.conn
+                    .execute("DELETE FROM usage_outcomes WHERE tool_id = ?1", [tool_id])?;
+                db.conn
+                    .execute("DELETE FROM usage_durations WHERE tool_id = ?1", [tool_id])?;
+            }
+
+            db.conn.execute(
+                &format!("DELETE FROM usage_daily WHERE date >= ?2 AND {TOOL_FILTER}"),
+                params![tool_name, since],
+            )?;
+            for table in ["usage_subcommands", "usage_context"] {
+                db.conn.execute(
+                    &format!("DELETE FROM {table} WHERE last_used >= ?2 AND {TOOL_FILTER}"),
+                    params![tool_name, since],
+                )?;
+            }
+
+            Ok(affected.iter().map(|(_, count)| count).sum())
+        })
+    }
+
     /// Count orphaned usage records (tool_id doesn't exist in tools)
     pub fn count_orphaned_usage(&self) -> Result<usize> {
         let count: i64 = self.conn.query_row(
@@ -0,0 +1,123 @@
+//! Disk usage helpers
+//!
+//! Measures files and directories on disk and formats byte counts for display.
+
+use std::fs;
+use std::path::Path;
+
+/// Size of a single file in bytes (following symlinks), or None if missing
+pub fn file_size(path: &Path) -> Option<u64> {
+    fs::metadata(path)
+        .ok()
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+}
+
+/// Recursively sum the size of all files under a directory
+///
+/// Symlinks are not followed, so shared targets are not counted twice.
+/// Unreadable entries are skipped.
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+
+    if meta.is_file() {
+        return meta.len();
+    }
+    if !meta.is_dir() {
+        return 0;
+    }
+
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| dir_size(&e.path()))
+        .sum()
+}
+
+/// Size of the binary a command resolves to on PATH
+pub fn binary_size(binary: &str) -> Option<u64> {
+    which::which(binary).ok().and_then(|p| file_size(&p))
+}
+
+/// Format a byte count as a human-readable string (e.g., "12.3 MB")
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Parse a human-readable size (e.g., "12.3 MB", "1,2 GB", "512 bytes") into bytes
+///
+/// Used for package manager output such as `flatpak info`.
+pub fn parse_size(s: &str) -> Option<u64> {
+    let s = s.trim().replace('\u{a0}', " ");
+    let split = s.find(|c: char| c.is_alphabetic()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+
+    let number: f64 = number.trim().replace(',', ".").parse().ok()?;
+    let multiplier: f64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" | "byte" | "bytes" => 1.0,
+        "k" | "kb" | "kib" => 1024.0,
+        "m" | "mb" | "mib" => 1024.0 * 1024.0,
+        "g" | "gb" | "gib" => 1024.0 * 1024.0 * 1024.0,
+        "t" | "tb" | "tib" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+
+    Some((number * multiplier) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GB");
+    }
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("512 bytes"), Some(512));
+        assert_eq!(parse_size("1.5 kB"), Some(1536));
+        assert_eq!(parse_size("2 MB"), Some(2 * 1024 * 1024));
+        assert_eq!(parse_size("1,0 GB"), Some(1024 * 1024 * 1024));
+        assert_eq!(parse_size("12 parsecs"), None);
+        assert_eq!(parse_size(""), None);
+    }
+
+    #[test]
+    fn test_dir_size() -> anyhow::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let mut a = fs::File::create(dir.path().join("a"))?;
+        a.write_all(&[0u8; 100])?;
+        fs::create_dir(dir.path().join("sub"))?;
+        let mut b = fs::File::create(dir.path().join("sub").join("b"))?;
+        b.write_all(&[0u8; 50])?;
+
+        assert_eq!(dir_size(dir.path()), 150);
+        assert_eq!(dir_size(&dir.path().join("missing")), 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+//! Gitea and Forgejo (including Codeberg) through the v1 REST API
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{
+    Api, Forge, ForgeKind, MAX_RELEASES, Release, host_token_var, token_from_env, token_hint,
+    url_host,
+};
+use crate::db::Database;
+use crate::github::{RepoInfo, RepoLicense, RepoOwner, Token};
+
+/// README names tried in order, since the API has no README endpoint
+const README_FILES: &[&str] = &["README.md", "README", "README.rst", "readme.md"];
+
+#[derive(Debug, Deserialize)]
+struct Repo {
+    name: String,
+    full_name: String,
+    #[serde(default)]
+    description: String,
+    stars_count: i64,
+    #[serde(default)]
+    language: String,
+    #[serde(default)]
+    website: String,
+    topics: Option<Vec<String>>,
+    owner: RepoOwner,
+    /// SPDX identifiers (Gitea 1.22+)
+    licenses: Option<Vec<String>>,
+    #[serde(default)]
+    archived: bool,
+    updated_at: Option<String>,
+    open_issues_count: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    sha: String,
+}
+
+/// Client for a Gitea or Forgejo server
+pub struct GiteaClient<'a> {
+    api: Api<'a>,
+}
+
+impl<'a> GiteaClient<'a> {
+    /// Client for the server at `base_url` (e.g. https://codeberg.org)
+    pub fn new(base_url: &str, token: Option<Token>) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        Self {
+            api: Api {
+                name: "Gitea",
+                base_url: format!("{}/api/v1", base_url),
+                host: url_host(base_url),
+                token,
+                auth: ("Authorization", |token| format!("token {}", token)),
+                token_hint: "GITEA_TOKEN".to_string(),
+                cache: None,
+            },
+        }
+    }
+
+    /// Client for a host, with a token when `listed` in the config or a public instance
+    pub fn for_host(host: &str, listed: bool) -> Self {
+        let vars = token_vars(host, listed);
+        let mut client = Self::new(&format!("https://{}", host), token_from_env(&vars));
+        client.api.token_hint = token_hint("GITEA_TOKEN", host, &vars);
+        client
+    }
+
+    /// Keep responses in the database and revalidate them with their ETag
+    pub fn with_cache(mut self, db: &'a Database) -> Self {
+        self.api.cache = Some(db);
+        self
+    }
+}
+
+/// Environment variables a host's token may come from
+///
+/// Codeberg reads `CODEBERG_TOKEN` and gitea.com `GITEA_TOKEN`. A self-hosted
+/// server needs its own variable and an entry under `forges` in the config.
+fn token_vars(host: &str, listed: bool) -> Vec<String> {
+    match host {
+        "codeberg.org" => vec!["CODEBERG_TOKEN".to_string()],
+        "gitea.com" => vec!["GITEA_TOKEN".to_string()],
+        _ if listed => vec![host_token_var("GITEA_TOKEN", host)],
+        _ => Vec::new(),
+    }
+}
+
+impl Forge for GiteaClient<'_> {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::Gitea
+    }
+
+    fn host(&self) -> String {
+        self.api.host.clone()
+    }
+
+    fn get_repo_details(&self, owner: &str, repo: &str) -> Result<Option<RepoInfo>> {
+        let Some(info) = self
+            .api
+            .get_json::<Repo>(&format!("repos/{}/{}", owner, repo))?
+        else {
+            return Ok(None);
+        };
+        let release: Option<Release> = self
+            .api
+            .get_json(&format!("repos/{}/{}/releases/latest", owner, repo))?;
+
+        let non_empty = |s: String| Some(s).filter(|s| !s.trim().is_empty());
+        Ok(Some(RepoInfo {
+            name: info.name,
+            full_name: info.full_name,
+            description: non_empty(info.description),
+            stars: info.stars_count,
+            language: non_empty(info.language),
+            homepage: non_empty(info.website),
+            topics: info.topics.unwrap_or_default(),
+            owner: info.owner,
+            license: info
+                .licenses
+                .and_then(|l| l.into_iter().next())
+                .map(|id| RepoLicense { spdx_id: Some(id) }),
+            archived: info.archived,
+            // Gitea has no push time; `updated_at` also moves on settings and
+            // description edits, so the repository may look more active than it is
+            pushed_at: info.updated_at,
+            open_issues: info.open_issues_count,
+            latest_release: release.map(|r| r.tag_name),
+        }))
+    }
+
+    fn readme(&self, owner: &str, repo: &str) -> Result<String> {
+        for file in README_FILES {
+            if let Some(readme) = self
+                .api
+                .get(&format!("repos/{}/{}/raw/{}", owner, repo, file))?
+            {
+                return Ok(readme);
+            }
+        }
+        anyhow::bail!("{}/{} has no README", owner, repo)
+    }
+
+    fn head_commit(&self, owner: &str, repo: &str) -> Result<String> {
+        let commits: Vec<Commit> = self
+            .api
+            .get_json(&format!(
+                "repos/{}/{}/commits?limit=1&stat=false&verification=false&files=false",
+                owner, repo
+            ))?
+            .with_context(|| format!("Repository {}/{} not found", owner, repo))?;
+        commits
+            .into_iter()
+            .next()
+            .map(|c| c.sha)
+            .with_context(|| format!("{}/{} has no commits", owner, repo))
+    }
+
+    fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        Ok(self
+            .api
+            .get_json(&format!(
+                "repos/{}/{}/releases?limit={}",
+                owner, repo, MAX_RELEASES
+            ))?
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock_server;
+
+    #[test]
+    fn test_gitea_repo_details_and_readme() {
+        let (url, server) = mock_server(vec![
+            r#"HTTP/1.1 200 OK
+
+{"name": "tool", "full_name": "someone/tool", "description": "A tool", "stars_count": 7,
+ "language": "Go", "website": "", "topics": null, "owner": {"login": "someone"},
+ "licenses": ["GPL-3.0-or-later"], "archived": true, "updated_at": "2025-01-02T03:04:05Z",
+ "open_issues_count": 1}"#,
+            "HTTP/1.1 404 Not Found\n\n{\"message\": \"release not found\"}",
+            "HTTP/1.1 404 Not Found",
+            "HTTP/1.1 200 OK\n\ntool readme",
+        ]);
+        let client = GiteaClient::new(&url, None);
+
+        let info = client.get_repo_details("someone", "tool").unwrap().unwrap();
+        assert_eq!(info.description.as_deref(), Some("A tool"));
+        assert_eq!(info.homepage, None);
+        assert!(info.topics.is_empty());
+        assert_eq!(info.license_id(), Some("GPL-3.0-or-later"));
+        assert_eq!(info.latest_release, None);
+        assert!(info.archived);
+
+        // Falls back through README names
+        assert_eq!(client.readme("someone", "tool").unwrap(), "tool readme");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /api/v1/repos/someone/tool "));
+        assert!(requests[2].starts_with("get /api/v1/repos/someone/tool/raw/readme.md "));
+        assert!(requests[3].starts_with("get /api/v1/repos/someone/tool/raw/readme "));
+    }
+
+    #[test]
+    fn test_token_vars() {
+        assert_eq!(token_vars("codeberg.org", false), ["CODEBERG_TOKEN"]);
+        assert_eq!(token_vars("gitea.com", false), ["GITEA_TOKEN"]);
+        assert_eq!(
+            token_vars("code.example.org", true),
+            ["GITEA_TOKEN_CODE_EXAMPLE_ORG"]
+        );
+        assert!(token_vars("forgejo.example.net", false).is_empty());
+    }
+}
@@ -0,0 +1,397 @@
+//! GitLab (gitlab.com or self-hosted) through the v4 REST API
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::{
+    Api, Forge, ForgeKind, MAX_RELEASES, Release, host_token_var, token_from_env, token_hint,
+    url_host,
+};
+use crate::db::Database;
+use crate::github::{RepoInfo, RepoLicense, RepoOwner, Token, percent_encode};
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    path: String,
+    path_with_namespace: String,
+    description: Option<String>,
+    star_count: i64,
+    #[serde(default)]
+    topics: Vec<String>,
+    namespace: Namespace,
+    license: Option<License>,
+    #[serde(default)]
+    archived: bool,
+    last_activity_at: Option<String>,
+    open_issues_count: Option<i64>,
+    /// Branch the README is read from
+    default_branch: Option<String>,
+    /// Web URL of the README blob on the default branch (`.../-/blob/<branch>/<path>`)
+    readme_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Namespace {
+    full_path: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct License {
+    /// Licensee's key (`mit`, `apache-2.0`, `bsd-3-clause`), see [`spdx_id`]
+    key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabRelease {
+    tag_name: String,
+    name: Option<String>,
+    description: Option<String>,
+    released_at: Option<String>,
+    #[serde(default)]
+    upcoming_release: bool,
+    #[serde(rename = "_links")]
+    links: Option<ReleaseLinks>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseLinks {
+    #[serde(rename = "self")]
+    web: Option<String>,
+}
+
+impl From<GitLabRelease> for Release {
+    fn from(release: GitLabRelease) -> Self {
+        Release {
+            tag_name: release.tag_name,
+            name: release.name,
+            body: release.description,
+            html_url: release.links.and_then(|l| l.web),
+            published_at: release.released_at,
+            prerelease: release.upcoming_release,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Commit {
+    id: String,
+}
+
+/// Client for a GitLab server
+pub struct GitLabClient<'a> {
+    api: Api<'a>,
+}
+
+impl<'a> GitLabClient<'a> {
+    /// Client for the server at `base_url` (e.g. https://gitlab.com)
+    pub fn new(base_url: &str, token: Option<Token>) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        Self {
+            api: Api {
+                name: "GitLab",
+                base_url: format!("{}/api/v4", base_url),
+                host: url_host(base_url),
+                token,
+                auth: ("PRIVATE-TOKEN", str::to_string),
+                token_hint: "GITLAB_TOKEN".to_string(),
+                cache: None,
+            },
+        }
+    }
+
+    /// Client for a host, with a token when `listed` in the config or gitlab.com
+    pub fn for_host(host: &str, listed: bool) -> Self {
+        let vars = token_vars(host, listed);
+        let mut client = Self::new(&format!("https://{}", host), token_from_env(&vars));
+        client.api.token_hint = token_hint("GITLAB_TOKEN", host, &vars);
+        client
+    }
+
+    /// Keep responses in the database and revalidate them with their ETag
+    pub fn with_cache(mut self, db: &'a Database) -> Self {
+        self.api.cache = Some(db);
+        self
+    }
+
+    fn project(&self, owner: &str, repo: &str) -> Result<Option<Project>> {
+        self.api
+            .get_json(&format!("{}?license=true", project_path(owner, repo)))
+    }
+
+    /// Tag of the project's most recent release
+    fn latest_release(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        let releases: Option<Vec<GitLabRelease>> = self.api.get_json(&format!(
+            "{}/releases?per_page=1",
+            project_path(owner, repo)
+        ))?;
+        Ok(releases
+            .and_then(|r| r.into_iter().next())
+            .map(|r| r.tag_name))
+    }
+}
+
+/// Environment variables a host's token may come from
+///
+/// Only gitlab.com reads `GITLAB_TOKEN`. A self-hosted server needs its own
+/// variable and an entry under `forges` in the config, so a repository URL on
+/// some other `gitlab.*` host never receives the user's token.
+fn token_vars(host: &str, listed: bool) -> Vec<String> {
+    match host {
+        "gitlab.com" => vec!["GITLAB_TOKEN".to_string()],
+        _ if listed => vec![host_token_var("GITLAB_TOKEN", host)],
+        _ => Vec::new(),
+    }
+}
+
+/// SPDX identifier of a GitLab license key
+///
+/// GitLab names licenses with licensee's keys, which are lowercase and don't
+/// always match SPDX (`lgpl-3.0` is `LGPL-3.0`, `0bsd` is `0BSD`, `other` is none).
+fn spdx_id(key: &str) -> Option<&'static str> {
+    Some(match key {
+        "0bsd" => "0BSD",
+        "afl-3.0" => "AFL-3.0",
+        "agpl-3.0" => "AGPL-3.0",
+        "apache-2.0" => "Apache-2.0",
+        "artistic-2.0" => "Artistic-2.0",
+        "bsd-2-clause" => "BSD-2-Clause",
+        "bsd-3-clause" => "BSD-3-Clause",
+        "bsd-3-clause-clear" => "BSD-3-Clause-Clear",
+        "bsl-1.0" => "BSL-1.0",
+        "cc-by-4.0" => "CC-BY-4.0",
+        "cc-by-sa-4.0" => "CC-BY-SA-4.0",
+        "cc0-1.0" => "CC0-1.0",
+        "ecl-2.0" => "ECL-2.0",
+        "epl-1.0" => "EPL-1.0",
+        "epl-2.0" => "EPL-2.0",
+        "eupl-1.1" => "EUPL-1.1",
+        "eupl-1.2" => "EUPL-1.2",
+        "gpl-2.0" => "GPL-2.0",
+        "gpl-3.0" => "GPL-3.0",
+        "isc" => "ISC",
+        "lgpl-2.1" => "LGPL-2.1",
+        "lgpl-3.0" => "LGPL-3.0",
+        "lppl-1.3c" => "LPPL-1.3c",
+        "mit" => "MIT",
+        "mit-0" => "MIT-0",
+        "mpl-2.0" => "MPL-2.0",
+        "ms-pl" => "MS-PL",
+        "ms-rl" => "MS-RL",
+        "mulanpsl-2.0" => "MulanPSL-2.0",
+        "ncsa" => "NCSA",
+        "odbl-1.0" => "ODbL-1.0",
+        "ofl-1.1" => "OFL-1.1",
+        "osl-3.0" => "OSL-3.0",
+        "postgresql" => "PostgreSQL",
+        "unlicense" => "Unlicense",
+        "upl-1.0" => "UPL-1.0",
+        "vim" => "Vim",
+        "wtfpl" => "WTFPL",
+        "zlib" => "Zlib",
+        _ => return None,
+    })
+}
+
+/// API path of a project (its full path, URL-encoded, is its id)
+fn project_path(owner: &str, repo: &str) -> String {
+    format!(
+        "projects/{}",
+        percent_encode(&format!("{}/{}", owner, repo))
+    )
+}
+
+impl Forge for GitLabClient<'_> {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::GitLab
+    }
+
+    fn host(&self) -> String {
+        self.api.host.clone()
+    }
+
+    fn get_repo_details(&self, owner: &str, repo: &str) -> Result<Option<RepoInfo>> {
+        let Some(project) = self.project(owner, repo)? else {
+            return Ok(None);
+        };
+        let latest_release = self.latest_release(owner, repo)?;
+
+        Ok(Some(RepoInfo {
+            name: project.path,
+            full_name: project.path_with_namespace,
+            description: project.description.filter(|d| !d.trim().is_empty()),
+            stars: project.star_count,
+            language: None,
+            homepage: None,
+            topics: project.topics,
+            owner: RepoOwner {
+                login: project.namespace.full_path,
+            },
+            license: project.license.map(|l| RepoLicense {
+                spdx_id: spdx_id(&l.key).map(str::to_string),
+            }),
+            archived: project.archived,
+            pushed_at: project.last_activity_at,
+            open_issues: project.open_issues_count,
+            latest_release,
+        }))
+    }
+
+    fn readme(&self, owner: &str, repo: &str) -> Result<String> {
+        let project = self
+            .project(owner, repo)?
+            .with_context(|| format!("Repository {}/{} not found", owner, repo))?;
+        // Branch names may contain `/`, so the path is what follows the known branch
+        let (branch, file) = project
+            .default_branch
+            .as_deref()
+            .zip(project.readme_url.as_deref())
+            .and_then(|(branch, url)| {
+                let (_, rest) = url.split_once("/-/blob/")?;
+                Some((branch, rest.strip_prefix(branch)?.strip_prefix('/')?))
+            })
+            .with_context(|| format!("{}/{} has no README", owner, repo))?;
+
+        self.api
+            .get(&format!(
+                "{}/repository/files/{}/raw?ref={}",
+                project_path(owner, repo),
+                percent_encode(file),
+                percent_encode(branch)
+            ))?
+            .with_context(|| format!("{}/{} has no README", owner, repo))
+    }
+
+    fn head_commit(&self, owner: &str, repo: &str) -> Result<String> {
+        let commits: Vec<Commit> = self
+            .api
+            .get_json(&format!(
+                "{}/repository/commits?per_page=1",
+                project_path(owner, repo)
+            ))?
+            .with_context(|| format!("Repository {}/{} not found", owner, repo))?;
+        commits
+            .into_iter()
+            .next()
+            .map(|c| c.id)
+            .with_context(|| format!("{}/{} has no commits", owner, repo))
+    }
+
+    fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        let releases: Option<Vec<GitLabRelease>> = self.api.get_json(&format!(
+            "{}/releases?per_page={}",
+            project_path(owner, repo),
+            MAX_RELEASES
+        ))?;
+        Ok(releases
+            .unwrap_or_default()
+            .into_iter()
+            .map(Release::from)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock_server;
+
+    #[test]
+    fn test_gitlab_repo_details_and_readme() {
+        let project = r#"{"path": "tool", "path_with_namespace": "group/sub/tool",
+            "description": "", "star_count": 42, "topics": ["cli"],
+            "namespace": {"full_path": "group/sub"}, "license": {"key": "bsd-3-clause", "name": "BSD 3-Clause \"New\" or \"Revised\" License"},
+            "archived": false, "last_activity_at": "2026-09-30T10:00:00Z", "open_issues_count": 3,
+            "default_branch": "release/1.x",
+            "readme_url": "https://gitlab.example.com/group/sub/tool/-/blob/release/1.x/docs/README.md"}"#;
+        let (url, server) = mock_server(vec![
+            format!("HTTP/1.1 200 OK\n\n{}", project),
+            r#"HTTP/1.1 200 OK
+
+[{"tag_name": "v1.2.0"}]"#
+                .to_string(),
+            format!("HTTP/1.1 200 OK\n\n{}", project),
+            "HTTP/1.1 200 OK\n\n# tool".to_string(),
+        ]);
+        let token = Token {
+            value: "secret".to_string(),
+            source: "GITLAB_TOKEN".to_string(),
+        };
+        let client = GitLabClient::new(&url, Some(token));
+
+        let info = client
+            .get_repo_details("group/sub", "tool")
+            .unwrap()
+            .unwrap();
+        assert_eq!(info.full_name, "group/sub/tool");
+        assert_eq!(info.owner.login, "group/sub");
+        assert_eq!(info.description, None);
+        assert_eq!(info.stars, 42);
+        assert_eq!(info.license_id(), Some("BSD-3-Clause"));
+        assert_eq!(info.latest_release.as_deref(), Some("v1.2.0"));
+        assert_eq!(client.readme("group/sub", "tool").unwrap(), "# tool");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /api/v4/projects/group%2fsub%2ftool?license=true "));
+        assert!(requests[0].contains("private-token: secret"));
+        assert!(requests[3].starts_with(
+            "get /api/v4/projects/group%2fsub%2ftool/repository/files/docs%2freadme.md/raw?ref=release%2f1.x "
+        ));
+    }
+
+    #[test]
+    fn test_spdx_id() {
+        assert_eq!(spdx_id("apache-2.0"), Some("Apache-2.0"));
+        assert_eq!(spdx_id("unlicense"), Some("Unlicense"));
+        assert_eq!(spdx_id("0bsd"), Some("0BSD"));
+        assert_eq!(spdx_id("other"), None);
+    }
+
+    #[test]
+    fn test_token_vars() {
+        assert_eq!(token_vars("gitlab.com", false), ["GITLAB_TOKEN"]);
+        assert_eq!(
+            token_vars("git.example.com", true),
+            ["GITLAB_TOKEN_GIT_EXAMPLE_COM"]
+        );
+        // Recognized by name only: could be anyone's server
+        assert!(token_vars("gitlab.example.net", false).is_empty());
+
+        let client = GitLabClient::for_host("gitlab.example.net", false);
+        assert!(client.api.token.is_none());
+        assert_eq!(
+            client.api.token_hint,
+            "GITLAB_TOKEN_GITLAB_EXAMPLE_NET and add gitlab.example.net to \"forges\" in the config"
+        );
+    }
+
+    #[test]
+    fn test_gitlab_releases() {
+        let (url, server) = mock_server(vec![
+            r#"HTTP/1.1 200 OK
+
+[{"tag_name": "v2.0.0", "name": "Two", "description": "- New things",
+  "released_at": "2026-10-01T00:00:00Z", "upcoming_release": false,
+  "_links": {"self": "https://gitlab.example.com/group/tool/-/releases/v2.0.0"}}]"#
+                .to_string(),
+        ]);
+        let client = GitLabClient::new(&url, None);
+
+        let releases = client.releases("group", "tool").unwrap();
+        assert_eq!(
+            releases,
+            vec![Release {
+                tag_name: "v2.0.0".to_string(),
+                name: Some("Two".to_string()),
+                body: Some("- New things".to_string()),
+                html_url: Some(
+                    "https://gitlab.example.com/group/tool/-/releases/v2.0.0".to_string()
+                ),
+                published_at: Some("2026-10-01T00:00:00Z".to_string()),
+                prerelease: false,
+            }]
+        );
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /api/v4/projects/group%2ftool/releases?per_page=50 "));
+    }
+}
@@ -0,0 +1,494 @@
+//! Code forges hosting tool repositories
+//!
+//! GitHub, GitLab (gitlab.com or self-hosted) and Gitea-compatible forges
+//! (Gitea, Forgejo, Codeberg) all answer the same questions for a tool:
+//! repository metadata, README and latest release. [`Forge`] is that common
+//! interface and [`RepoRef`] names a repository on any of them.
+
+mod gitea;
+mod gitlab;
+
+pub use gitea::GiteaClient;
+pub use gitlab::GitLabClient;
+
+use anyhow::{Context, Result, bail};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::db::Database;
+use crate::github::{GitHubClient, RepoInfo, Token};
+use crate::http::agent;
+
+/// Releases fetched per repository, enough to span most pending updates
+pub(crate) const MAX_RELEASES: usize = 50;
+
+/// API flavor of a forge
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForgeKind {
+    GitHub,
+    GitLab,
+    /// Gitea and its fork Forgejo (which runs Codeberg)
+    Gitea,
+}
+
+impl ForgeKind {
+    /// Name shown to users
+    pub fn label(self) -> &'static str {
+        match self {
+            ForgeKind::GitHub => "GitHub",
+            ForgeKind::GitLab => "GitLab",
+            ForgeKind::Gitea => "Gitea",
+        }
+    }
+
+    /// Forge of a host recognizable by name
+    pub fn detect(host: &str) -> Option<Self> {
+        match host {
+            "github.com" => Some(ForgeKind::GitHub),
+            "gitlab.com" => Some(ForgeKind::GitLab),
+            "codeberg.org" | "gitea.com" => Some(ForgeKind::Gitea),
+            _ if host.starts_with("gitlab.") => Some(ForgeKind::GitLab),
+            _ if host.starts_with("gitea.") || host.starts_with("forgejo.") => {
+                Some(ForgeKind::Gitea)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ForgeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = match self {
+            ForgeKind::GitHub => "github",
+            ForgeKind::GitLab => "gitlab",
+            ForgeKind::Gitea => "gitea",
+        };
+        write!(f, "{}", id)
+    }
+}
+
+impl FromStr for ForgeKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "github" => Ok(ForgeKind::GitHub),
+            "gitlab" => Ok(ForgeKind::GitLab),
+            "gitea" | "forgejo" | "codeberg" => Ok(ForgeKind::Gitea),
+            _ => bail!("Unknown forge: {} (expected github, gitlab or gitea)", s),
+        }
+    }
+}
+
+/// A repository on some forge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoRef {
+    pub forge: ForgeKind,
+    /// Web host, e.g. `gitlab.com`
+    pub host: String,
+    /// Owner, or the full group path on GitLab (`group/subgroup`)
+    pub owner: String,
+    pub repo: String,
+}
+
+impl RepoRef {
+    /// Parse a repository URL (https or ssh), `host/owner/repo`, or GitHub's `owner/repo`
+    ///
+    /// `hosts` names the forge of self-hosted servers that [`ForgeKind::detect`] can't tell.
+    pub fn parse(input: &str, hosts: &BTreeMap<String, ForgeKind>) -> Result<Self> {
+        let input = input.trim();
+        let (host, path) = if let Some(rest) = input
+            .strip_prefix("https://")
+            .or_else(|| input.strip_prefix("http://"))
+        {
+            rest.split_once('/').unwrap_or((rest, ""))
+        } else if let Some(rest) = input.strip_prefix("ssh://") {
+            let rest = rest.split_once('@').map_or(rest, |(_, r)| r);
+            rest.split_once('/').unwrap_or((rest, ""))
+        } else if let Some((user_host, path)) = input.split_once(':') {
+            // scp-like ssh: git@host:owner/repo.git
+            (user_host.rsplit('@').next().unwrap_or(user_host), path)
+        } else {
+            match input.split_once('/') {
+                Some((first, rest)) if first.contains('.') => (first, rest),
+                _ => ("github.com", input),
+            }
+        };
+
+        let host = host.split(':').next().unwrap_or(host).to_lowercase();
+        let host = host.strip_prefix("www.").unwrap_or(&host).to_string();
+        let forge = hosts
+            .get(&host)
+            .copied()
+            .or_else(|| ForgeKind::detect(&host))
+            .with_context(|| {
+                format!(
+                    "Unknown forge at {}: add it to \"forges\" in the config (github, gitlab or gitea)",
+                    host
+                )
+            })?;
+
+        // Drop GitLab's /-/ pages, query strings and fragments
+        let path = path.split("/-/").next().unwrap_or(path);
+        let path = path.split(['?', '#']).next().unwrap_or(path);
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+        let (owner, repo) = match (forge, segments.as_slice()) {
+            (_, [] | [_]) => bail!("Not a repository URL: {}", input),
+            (ForgeKind::GitLab, [groups @ .., repo]) => (groups.join("/"), *repo),
+            (_, [owner, repo, ..]) => (owner.to_string(), *repo),
+        };
+
+        Ok(Self {
+            forge,
+            host,
+            owner,
+            repo: repo.trim_end_matches(".git").to_string(),
+        })
+    }
+
+    /// Web page of the repository
+    pub fn url(&self) -> String {
+        format!("https://{}/{}/{}", self.host, self.owner, self.repo)
+    }
+
+    /// Web page listing the repository's releases
+    pub fn releases_url(&self) -> String {
+        match self.forge {
+            ForgeKind::GitLab => format!("{}/-/releases", self.url()),
+            ForgeKind::GitHub | ForgeKind::Gitea => format!("{}/releases", self.url()),
+        }
+    }
+}
+
+impl fmt::Display for RepoRef {
+    /// `owner/repo` on github.com, `host/owner/repo` elsewhere (both parse back)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.host == "github.com" && !self.owner.contains('.') {
+            write!(f, "{}/{}", self.owner, self.repo)
+        } else {
+            write!(f, "{}/{}/{}", self.host, self.owner, self.repo)
+        }
+    }
+}
+
+/// Forge kinds of self-hosted servers, from the user's config
+pub fn configured_hosts() -> BTreeMap<String, ForgeKind> {
+    crate::config::HoardConfig::load()
+        .map(|c| c.forges)
+        .unwrap_or_default()
+}
+
+/// A published release of a repository
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    pub name: Option<String>,
+    /// Release notes (Markdown)
+    pub body: Option<String>,
+    pub html_url: Option<String>,
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub prerelease: bool,
+}
+
+/// What sync, fetch, `ai extract` and release notes need from a forge
+pub trait Forge {
+    fn kind(&self) -> ForgeKind;
+
+    /// Web host of the forge, e.g. `gitlab.com`
+    fn host(&self) -> String;
+
+    /// Repository info with its latest release, `None` if it doesn't exist
+    fn get_repo_details(&self, owner: &str, repo: &str) -> Result<Option<RepoInfo>>;
+
+    /// The repository's README as text
+    fn readme(&self, owner: &str, repo: &str) -> Result<String>;
+
+    /// Latest commit on the default branch (used for cache versioning)
+    fn head_commit(&self, owner: &str, repo: &str) -> Result<String>;
+
+    /// Recent releases, newest first
+    fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>>;
+}
+
+/// Client for a forge, caching responses in the database
+///
+/// github.com and the configured GitHub Enterprise server use their usual
+/// client; other GitHub hosts are assumed to be Enterprise servers. GitLab and
+/// Gitea servers only get a token when they're public or listed in the config.
+pub fn client<'a>(forge: ForgeKind, host: &str, db: &'a Database) -> Box<dyn Forge + 'a> {
+    build_client(forge, host, db, true)
+}
+
+/// Client for a forge that never sends credentials
+///
+/// For repositories named by third parties, such as a package's registry
+/// metadata: any host could be listed there, including one collecting tokens.
+pub fn anonymous_client<'a>(forge: ForgeKind, host: &str, db: &'a Database) -> Box<dyn Forge + 'a> {
+    build_client(forge, host, db, false)
+}
+
+fn build_client<'a>(
+    forge: ForgeKind,
+    host: &str,
+    db: &'a Database,
+    credentials: bool,
+) -> Box<dyn Forge + 'a> {
+    use crate::github::{DEFAULT_API_URL, api_host};
+
+    let config = crate::config::HoardConfig::load().unwrap_or_default();
+    let listed = config.forges.contains_key(host);
+    match forge {
+        ForgeKind::GitHub => {
+            let configured = config.github.api_url.as_deref().unwrap_or(DEFAULT_API_URL);
+            let api_url = if api_host(configured) == host {
+                configured.to_string()
+            } else if host == "github.com" {
+                DEFAULT_API_URL.to_string()
+            } else {
+                format!("https://{}/api/v3", host)
+            };
+            let client = if credentials {
+                GitHubClient::for_api(&api_url)
+            } else {
+                GitHubClient::new(&api_url, None)
+            };
+            Box::new(client.with_cache(db))
+        }
+        ForgeKind::GitLab if credentials => {
+            Box::new(GitLabClient::for_host(host, listed).with_cache(db))
+        }
+        ForgeKind::GitLab => {
+            Box::new(GitLabClient::new(&format!("https://{}", host), None).with_cache(db))
+        }
+        ForgeKind::Gitea if credentials => {
+            Box::new(GiteaClient::for_host(host, listed).with_cache(db))
+        }
+        ForgeKind::Gitea => {
+            Box::new(GiteaClient::new(&format!("https://{}", host), None).with_cache(db))
+        }
+    }
+}
+
+/// Environment variable holding the token for a self-hosted server
+/// (`GITLAB_TOKEN_GIT_EXAMPLE_COM` for git.example.com)
+pub fn host_token_var(prefix: &str, host: &str) -> String {
+    let host: String = host
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}_{}", prefix, host)
+}
+
+/// What to suggest when a host asks for a token, given the variables it reads
+fn token_hint(prefix: &str, host: &str, vars: &[String]) -> String {
+    match vars.first() {
+        Some(var) => var.clone(),
+        None => format!(
+            "{} and add {} to \"forges\" in the config",
+            host_token_var(prefix, host),
+            host
+        ),
+    }
+}
+
+/// First token set in the given environment variables
+fn token_from_env(vars: &[String]) -> Option<Token> {
+    vars.iter().find_map(|var| {
+        std::env::var(var)
+            .ok()
+            .filter(|v| !v.trim().is_empty())
+            .map(|value| Token {
+                value: value.trim().to_string(),
+                source: var.to_string(),
+            })
+    })
+}
+
+/// Host part of a base URL
+fn url_host(url: &str) -> String {
+    let rest = url.split_once("://").map_or(url, |(_, r)| r);
+    rest.split('/').next().unwrap_or(rest).to_string()
+}
+
+/// Plain REST access shared by the GitLab and Gitea clients
+///
+/// Responses are cached with their ETag like GitHub's. These forges don't
+/// publish quotas the way GitHub does, so a 429 just fails the request.
+struct Api<'a> {
+    /// Forge name for messages
+    name: &'static str,
+    base_url: String,
+    host: String,
+    token: Option<Token>,
+    /// Header carrying the token, and how its value is formatted
+    auth: (&'static str, fn(&str) -> String),
+    /// Environment variable suggested when a token is required
+    token_hint: String,
+    cache: Option<&'a Database>,
+}
+
+impl Api<'_> {
+    fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        match self.get(path)? {
+            Some(body) => Ok(Some(serde_json::from_str(&body).with_context(|| {
+                format!("Failed to parse {} API response", self.name)
+            })?)),
+            None => Ok(None),
+        }
+    }
+
+    /// GET an API path, `None` on 404
+    fn get(&self, path: &str) -> Result<Option<String>> {
+        let url = format!("{}/{}", self.base_url, path);
+        let cached = match self.cache {
+            Some(db) => db.get_cached_response(&url)?,
+            None => None,
+        };
+
+        let mut request = agent()
+            .get(&url)
+            .header("User-Agent", concat!("hoards/", env!("CARGO_PKG_VERSION")));
+        if let Some(token) = &self.token {
+            let (header, format) = self.auth;
+            request = request.header(header, format(&token.value));
+        }
+        if let Some(cached) = &cached {
+            request = request.header("If-None-Match", &cached.etag);
+        }
+
+        let mut response = request
+            .config()
+            .http_status_as_error(false)
+            .build()
+            .call()
+            .with_context(|| format!("Failed to reach {}", self.host))?;
+
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header("etag");
+        let retry_after = header("retry-after");
+
+        let status = response.status().as_u16();
+        if status == 304
+            && let Some(cached) = cached
+        {
+            return Ok(Some(cached.body));
+        }
+        if status == 404 {
+            return Ok(None);
+        }
+
+        let body = response
+            .body_mut()
+            .read_to_string()
+            .with_context(|| format!("Failed to read {} API response", self.name))?;
+
+        if (200..300).contains(&status) {
+            if let (Some(db), Some(etag)) = (self.cache, etag) {
+                db.cache_response(&url, &etag, &body)?;
+            }
+            return Ok(Some(body));
+        }
+
+        match status {
+            401 => match &self.token {
+                Some(token) => bail!("{} rejected the token from {}", self.host, token.source),
+                None => bail!("{} requires a token: set {}", self.host, self.token_hint),
+            },
+            429 => bail!(
+                "{} rate limit exceeded; retry in {} s",
+                self.host,
+                retry_after.as_deref().unwrap_or("a few")
+            ),
+            _ => {
+                let message = serde_json::from_str::<serde_json::Value>(&body)
+                    .ok()
+                    .and_then(|v| {
+                        let message = v.get("message").or_else(|| v.get("error"))?;
+                        Some(match message.as_str() {
+                            Some(s) => s.to_string(),
+                            None => message.to_string(),
+                        })
+                    })
+                    .unwrap_or(body);
+                bail!("{} API error {}: {}", self.name, status, message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(input: &str) -> Result<RepoRef> {
+        RepoRef::parse(input, &BTreeMap::new())
+    }
+
+    #[test]
+    fn test_parse_repo_ref() {
+        let rg = parse("https://github.com/BurntSushi/ripgrep/tree/master").unwrap();
+        assert_eq!(rg.forge, ForgeKind::GitHub);
+        assert_eq!(
+            (rg.owner.as_str(), rg.repo.as_str()),
+            ("BurntSushi", "ripgrep")
+        );
+        assert_eq!(rg, parse("BurntSushi/ripgrep").unwrap());
+        assert_eq!(rg, parse("git@github.com:BurntSushi/ripgrep.git").unwrap());
+        assert_eq!(rg.to_string(), "BurntSushi/ripgrep");
+
+        // GitLab keeps subgroups in the owner
+        let nested = parse("https://gitlab.com/group/sub/project/-/tree/main").unwrap();
+        assert_eq!(nested.forge, ForgeKind::GitLab);
+        assert_eq!(nested.owner, "group/sub");
+        assert_eq!(nested.repo, "project");
+        assert_eq!(nested.to_string(), "gitlab.com/group/sub/project");
+        assert_eq!(parse(&nested.to_string()).unwrap(), nested);
+        assert_eq!(
+            parse("ssh://git@gitlab.com:22/group/sub/project.git").unwrap(),
+            nested
+        );
+
+        let codeberg = parse("https://codeberg.org/forgejo/forgejo.git").unwrap();
+        assert_eq!(codeberg.forge, ForgeKind::Gitea);
+        assert_eq!(codeberg.url(), "https://codeberg.org/forgejo/forgejo");
+
+        // Self-hosted servers need their kind configured
+        assert!(parse("https://git.example.com/team/tool").is_err());
+        let hosts = BTreeMap::from([("git.example.com".to_string(), ForgeKind::GitLab)]);
+        let own = RepoRef::parse("https://git.example.com/team/tool", &hosts).unwrap();
+        assert_eq!(own.forge, ForgeKind::GitLab);
+
+        assert!(parse("not-a-url").is_err());
+        assert!(parse("https://gitlab.com/only-group").is_err());
+    }
+
+    #[test]
+    fn test_forge_kind() {
+        assert_eq!("forgejo".parse::<ForgeKind>().unwrap(), ForgeKind::Gitea);
+        assert_eq!(ForgeKind::GitLab.to_string(), "gitlab");
+        assert_eq!(
+            ForgeKind::detect("gitlab.gnome.org"),
+            Some(ForgeKind::GitLab)
+        );
+        assert_eq!(ForgeKind::detect("example.com"), None);
+        assert!("svn".parse::<ForgeKind>().is_err());
+    }
+}
@@ -1,11 +1,37 @@
 //! GitHub integration for fetching repo info, topics, and descriptions
 //!
-//! Uses the `gh` CLI to query GitHub's API for repository information.
-//! Includes rate limit awareness to avoid hitting GitHub API limits.
+//! Talks to GitHub's REST API directly (or a GitHub Enterprise server). The
+//! token comes from the environment or the `gh` CLI's login, responses are
+//! cached with their ETag so unchanged data doesn't spend quota, and the
+//! rate-limit headers of every response are honored. When the API can't be
+//! reached directly, requests go through `gh api` if it's installed.
 
 use anyhow::{Context, Result, bail};
-use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::{OnceCell, RefCell};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
+
+use crate::config::GitHubConfig;
+use crate::db::Database;
+use crate::forge::{Forge, ForgeKind, MAX_RELEASES, Release, RepoRef};
+use crate::http::agent;
+use crate::models::InstallSource;
+
+/// Default REST API endpoint
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// REST API version requested
+const API_VERSION: &str = "2022-11-28";
+
+/// Most pages of starred repositories fetched (100 per page)
+const MAX_STARRED_PAGES: usize = 20;
+
+/// Longest we wait for a rate limit to reset rather than failing (the search quota resets every minute)
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
 
 /// GitHub API rate limit info
 #[derive(Debug, Clone, Deserialize)]
@@ -19,11 +45,7 @@ pub struct RateLimit {
 impl RateLimit {
     /// Minutes until rate limit resets
     pub fn reset_minutes(&self) -> i64 {
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_secs() as i64)
-            .unwrap_or(0);
-        ((self.reset - now) / 60).max(0)
+        self.reset_seconds() / 60
     }
 
     /// Seconds until rate limit resets
@@ -48,63 +70,28 @@ pub struct RateLimits {
     pub search: RateLimit,
 }
 
-/// Get current GitHub API rate limit status (core API - 5000/hour)
-pub fn get_rate_limit() -> Result<RateLimit> {
-    let output = Command::new("gh")
-        .args(["api", "rate_limit", "--jq", ".rate"])
-        .output()
-        .context("Failed to run gh api rate_limit")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("gh api rate_limit failed: {}", stderr);
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let rate: RateLimit =
-        serde_json::from_str(&stdout).context("Failed to parse rate limit response")?;
-
-    Ok(rate)
-}
-
-/// Get Search API rate limit (30/minute - stricter!)
-pub fn get_search_rate_limit() -> Result<RateLimit> {
-    let output = Command::new("gh")
-        .args(["api", "rate_limit", "--jq", ".resources.search"])
-        .output()
-        .context("Failed to run gh api rate_limit")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("gh api rate_limit failed: {}", stderr);
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let rate: RateLimit =
-        serde_json::from_str(&stdout).context("Failed to parse search rate limit response")?;
-
-    Ok(rate)
-}
-
-/// Get both core and search rate limits
-pub fn get_all_rate_limits() -> Result<RateLimits> {
-    let core = get_rate_limit()?;
-    let search = get_search_rate_limit()?;
-    Ok(RateLimits { core, search })
-}
-
-/// Repository info from GitHub
+/// Repository info from GitHub (other forges' clients fill in the same fields)
 #[derive(Debug, Clone, Deserialize)]
 pub struct RepoInfo {
     pub name: String,
     pub full_name: String,
     pub description: Option<String>,
-    #[serde(rename = "stargazersCount")]
+    #[serde(rename = "stargazers_count")]
     pub stars: i64,
     pub language: Option<String>,
     pub homepage: Option<String>,
+    #[serde(default)]
     pub topics: Vec<String>,
     pub owner: RepoOwner,
+    pub license: Option<RepoLicense>,
+    #[serde(default)]
+    pub archived: bool,
+    pub pushed_at: Option<String>,
+    #[serde(rename = "open_issues_count")]
+    pub open_issues: Option<i64>,
+    /// Tag of the latest release (filled in by [`GitHubClient::get_repo_details`])
+    #[serde(skip)]
+    pub latest_release: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -112,30 +99,583 @@ pub struct RepoOwner {
     pub login: String,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct RepoLicense {
+    /// SPDX identifier, `NOASSERTION` when GitHub couldn't tell (a license name on GitLab)
+    pub spdx_id: Option<String>,
+}
+
+impl RepoInfo {
+    /// SPDX identifier of the license, if GitHub recognized it
+    pub fn license_id(&self) -> Option<&str> {
+        self.license
+            .as_ref()
+            .and_then(|l| l.spdx_id.as_deref())
+            .filter(|id| *id != "NOASSERTION")
+    }
+}
+
 /// Search result from GitHub
 #[derive(Debug, Clone, Deserialize)]
 pub struct SearchResult {
     pub name: String,
-    #[serde(rename = "fullName")]
     pub full_name: String,
     pub description: Option<String>,
-    #[serde(rename = "stargazersCount")]
+    #[serde(rename = "stargazers_count")]
     pub stars: i64,
-    pub owner: SearchOwner,
+    pub owner: RepoOwner,
 }
 
-#[derive(Debug, Clone, Deserialize)]
-pub struct SearchOwner {
-    pub login: String,
+/// An API token, and where it was found (for error messages)
+#[derive(Debug, Clone)]
+pub struct Token {
+    pub(crate) value: String,
+    pub source: String,
+}
+
+/// Client for GitHub's REST API
+pub struct GitHubClient<'a> {
+    api_url: String,
+    /// Looked up on the first request, as `gh auth token` takes a moment
+    token: OnceCell<Option<Token>>,
+    /// `gh` executable to send requests through when the API can't be reached
+    gh: Option<PathBuf>,
+    /// Where responses are kept for conditional requests
+    cache: Option<&'a Database>,
+    /// Latest rate limit seen per resource (core, search), from response headers
+    limits: RefCell<HashMap<String, RateLimit>>,
+}
+
+impl<'a> GitHubClient<'a> {
+    pub fn new(api_url: &str, token: Option<Token>) -> Self {
+        Self {
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: OnceCell::from(token),
+            gh: None,
+            cache: None,
+            limits: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Client for an API, with the first token found for its host and `gh` as
+    /// a fallback when installed
+    pub fn for_api(api_url: &str) -> Self {
+        Self {
+            token: OnceCell::new(),
+            gh: which::which("gh").ok(),
+            ..Self::new(api_url, None)
+        }
+    }
+
+    /// Client for the configured API
+    pub fn from_config(config: &GitHubConfig) -> Self {
+        Self::for_api(config.api_url.as_deref().unwrap_or(DEFAULT_API_URL))
+    }
+
+    /// Client for the API in the user's config
+    pub fn load() -> Self {
+        let config = crate::config::HoardConfig::load()
+            .map(|c| c.github)
+            .unwrap_or_default();
+        Self::from_config(&config)
+    }
+
+    /// Keep responses in the database and revalidate them with their ETag
+    pub fn with_cache(mut self, db: &'a Database) -> Self {
+        self.cache = Some(db);
+        self
+    }
+
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    /// Where the token came from, if there is one
+    pub fn token_source(&self) -> Option<&str> {
+        self.token().map(|t| t.source.as_str())
+    }
+
+    fn token(&self) -> Option<&Token> {
+        self.token
+            .get_or_init(|| find_token(&self.api_url))
+            .as_ref()
+    }
+
+    /// Current core and search rate limits, `None` if the server doesn't rate limit
+    pub fn rate_limits(&self) -> Result<Option<RateLimits>> {
+        #[derive(Deserialize)]
+        struct Resources {
+            core: RateLimit,
+            search: RateLimit,
+        }
+        #[derive(Deserialize)]
+        struct Response {
+            resources: Resources,
+        }
+
+        // GitHub Enterprise answers 404 when rate limiting is disabled
+        let response: Option<Response> = self.get_json("rate_limit")?;
+        Ok(response.map(|r| RateLimits {
+            core: r.resources.core,
+            search: r.resources.search,
+        }))
+    }
+
+    /// Search repositories, best match first
+    pub fn search_repos(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        #[derive(Deserialize)]
+        struct Response {
+            items: Vec<SearchResult>,
+        }
+
+        let path = format!(
+            "search/repositories?q={}&per_page={}",
+            percent_encode(query),
+            limit.clamp(1, 100)
+        );
+        let response: Option<Response> = self.get_json(&path)?;
+        Ok(response.map(|r| r.items).unwrap_or_default())
+    }
+
+    /// Search for a tool's repository, using its source for language filtering
+    pub fn search_repo(&self, name: &str, source: Option<&str>) -> Result<Option<SearchResult>> {
+        let query = match source_to_language_filter(source) {
+            Some(lang_filter) => format!("{} {}", name, lang_filter),
+            None => name.to_string(),
+        };
+        Ok(self.search_repos(&query, 1)?.into_iter().next())
+    }
+
+    /// Repositories starred by `user`, or by the token's owner, most recently starred first
+    pub fn starred(&self, user: Option<&str>) -> Result<Vec<RepoInfo>> {
+        let path = match user {
+            Some(user) => format!("users/{}/starred", user),
+            None => "user/starred".to_string(),
+        };
+
+        let mut repos = Vec::new();
+        for page in 1..=MAX_STARRED_PAGES {
+            let batch: Vec<RepoInfo> = self
+                .get_json(&format!("{}?per_page=100&page={}", path, page))?
+                .with_context(|| format!("GitHub user {} not found", user.unwrap_or("")))?;
+            let last = batch.len() < 100;
+            repos.extend(batch);
+            if last {
+                break;
+            }
+        }
+        Ok(repos)
+    }
+
+    /// Get detailed repo info including topics, `None` if it doesn't exist
+    pub fn get_repo(&self, owner: &str, repo: &str) -> Result<Option<RepoInfo>> {
+        self.get_json(&format!("repos/{}/{}", owner, repo))
+    }
+
+    /// Search and get full repo info (with its latest release) for a tool, using source for language filtering
+    pub fn find_repo(&self, tool_name: &str, source: Option<&str>) -> Result<Option<RepoInfo>> {
+        match self.search_repo(tool_name, source)? {
+            Some(result) => self.get_repo_details(&result.owner.login, &result.name),
+            None => Ok(None),
+        }
+    }
+
+    /// Get repo info along with its latest release
+    pub fn get_repo_details(&self, owner: &str, repo: &str) -> Result<Option<RepoInfo>> {
+        let Some(mut info) = self.get_repo(owner, repo)? else {
+            return Ok(None);
+        };
+        info.latest_release = self.latest_release(owner, repo)?;
+        Ok(Some(info))
+    }
+
+    /// Tag of a repository's latest release, `None` if it never published one
+    pub fn latest_release(&self, owner: &str, repo: &str) -> Result<Option<String>> {
+        #[derive(Deserialize)]
+        struct Release {
+            tag_name: String,
+        }
+
+        let release: Option<Release> =
+            self.get_json(&format!("repos/{}/{}/releases/latest", owner, repo))?;
+        Ok(release.map(|r| r.tag_name))
+    }
+
+    /// A repository's most recent releases, newest first
+    pub fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        Ok(self
+            .get_json(&format!(
+                "repos/{}/{}/releases?per_page={}",
+                owner, repo, MAX_RELEASES
+            ))?
+            .unwrap_or_default())
+    }
+
+    /// Fetch a repository's README as text
+    pub fn readme(&self, owner: &str, repo: &str) -> Result<String> {
+        use base64::{Engine as _, engine::general_purpose};
+
+        #[derive(Deserialize)]
+        struct ReadmeResponse {
+            content: String,
+            encoding: String,
+        }
+
+        let readme: ReadmeResponse = self
+            .get_json(&format!("repos/{}/{}/readme", owner, repo))?
+            .with_context(|| format!("{}/{} has no README", owner, repo))?;
+
+        if readme.encoding != "base64" {
+            bail!("Unexpected README encoding: {}", readme.encoding);
+        }
+
+        let decoded = general_purpose::STANDARD
+            .decode(readme.content.replace('\n', ""))
+            .context("Failed to decode README content")?;
+
+        String::from_utf8(decoded).context("README is not valid UTF-8")
+    }
+
+    /// The latest commit SHA of a repository (used for cache versioning)
+    pub fn head_commit(&self, owner: &str, repo: &str) -> Result<String> {
+        #[derive(Deserialize)]
+        struct Commit {
+            sha: String,
+        }
+
+        let commit: Commit = self
+            .get_json(&format!("repos/{}/{}/commits/HEAD", owner, repo))?
+            .with_context(|| format!("Repository {}/{} not found", owner, repo))?;
+        Ok(commit.sha)
+    }
+
+    fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
+        match self.get(path)? {
+            Some(body) => Ok(Some(
+                serde_json::from_str(&body).context("Failed to parse GitHub API response")?,
+            )),
+            None => Ok(None),
+        }
+    }
+
+    /// GET an API path, `None` on 404
+    fn get(&self, path: &str) -> Result<Option<String>> {
+        let url = format!("{}/{}", self.api_url, path);
+        let resource = if path.starts_with("search/") {
+            "search"
+        } else {
+            "core"
+        };
+        self.wait_for_quota(resource)?;
+
+        let cached = match self.cache {
+            Some(db) => db.get_cached_response(&url)?,
+            None => None,
+        };
+
+        // A second attempt only follows a short wait for the rate limit to reset
+        for _ in 0..2 {
+            let mut request = agent()
+                .get(&url)
+                .header("Accept", "application/vnd.github+json")
+                .header("X-GitHub-Api-Version", API_VERSION)
+                .header("User-Agent", concat!("hoards/", env!("CARGO_PKG_VERSION")));
+            if let Some(token) = self.token() {
+                request = request.header("Authorization", format!("Bearer {}", token.value));
+            }
+            if let Some(cached) = &cached {
+                request = request.header("If-None-Match", &cached.etag);
+            }
+
+            let mut response = match request.config().http_status_as_error(false).build().call() {
+                Ok(response) => response,
+                Err(e) => match &self.gh {
+                    Some(gh) => {
+                        return self
+                            .gh_api(gh, path)
+                            .with_context(|| format!("Failed to reach {} ({})", self.api_url, e));
+                    }
+                    None => {
+                        return Err(e).with_context(|| format!("Failed to reach {}", self.api_url));
+                    }
+                },
+            };
+
+            let header = |name: &str| {
+                response
+                    .headers()
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            let limit = rate_limit_from_headers(&header);
+            let etag = header("etag");
+            let retry_after = header("retry-after").and_then(|v| v.parse::<u64>().ok());
+            if let Some(limit) = &limit {
+                let resource = header("x-ratelimit-resource").unwrap_or(resource.to_string());
+                self.limits.borrow_mut().insert(resource, limit.clone());
+            }
+
+            let status = response.status().as_u16();
+            if status == 304
+                && let Some(cached) = cached
+            {
+                return Ok(Some(cached.body));
+            }
+            if status == 404 {
+                return Ok(None);
+            }
+
+            let body = response
+                .body_mut()
+                .read_to_string()
+                .context("Failed to read GitHub API response")?;
+
+            if (200..300).contains(&status) {
+                if let (Some(db), Some(etag)) = (self.cache, etag) {
+                    db.cache_response(&url, &etag, &body)?;
+                }
+                return Ok(Some(body));
+            }
+
+            let message = serde_json::from_str::<serde_json::Value>(&body)
+                .ok()
+                .and_then(|v| v.get("message")?.as_str().map(str::to_string))
+                .unwrap_or(body);
+
+            if status == 401 {
+                match self.token() {
+                    Some(token) => bail!("GitHub rejected the token from {}", token.source),
+                    None => {
+                        bail!("GitHub requires a token: set GITHUB_TOKEN or run `gh auth login`")
+                    }
+                }
+            }
+
+            // Primary limits report an exhausted quota, secondary ones ask to retry later
+            let exhausted = limit.as_ref().is_some_and(|l| l.remaining == 0);
+            if matches!(status, 403 | 429) && (exhausted || retry_after.is_some()) {
+                let wait = retry_after
+                    .unwrap_or_else(|| limit.as_ref().map_or(0, |l| l.reset_seconds() as u64 + 1));
+                if Duration::from_secs(wait) > MAX_RATE_LIMIT_WAIT {
+                    bail!(
+                        "GitHub API rate limit exceeded; resets in {} min",
+                        wait.div_ceil(60)
+                    );
+                }
+                std::thread::sleep(Duration::from_secs(wait));
+                continue;
+            }
+
+            bail!("GitHub API error {}: {}", status, message);
+        }
+
+        bail!("GitHub API rate limit exceeded")
+    }
+
+    /// GET an API path through `gh api`, which brings its own login and proxy settings
+    ///
+    /// Only used when the API can't be reached directly, so nothing is cached.
+    fn gh_api(&self, gh: &Path, path: &str) -> Result<Option<String>> {
+        let output = Command::new(gh)
+            .args(["api", "--hostname", &api_host(&self.api_url)])
+            .args(["-H", "Accept: application/vnd.github+json", path])
+            .output()
+            .context("Failed to run gh")?;
+        if output.status.success() {
+            return Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()));
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("(HTTP 404)") {
+            return Ok(None);
+        }
+        bail!("gh api {} failed: {}", path, stderr.trim())
+    }
+
+    /// Wait out an exhausted quota if it resets soon, or fail without spending a request
+    fn wait_for_quota(&self, resource: &str) -> Result<()> {
+        let Some(limit) = self.limits.borrow().get(resource).cloned() else {
+            return Ok(());
+        };
+        if limit.remaining > 0 {
+            return Ok(());
+        }
+
+        let wait = Duration::from_secs(limit.reset_seconds() as u64);
+        if wait > MAX_RATE_LIMIT_WAIT {
+            bail!(
+                "GitHub API rate limit exceeded; resets in {} min",
+                limit.reset_minutes().max(1)
+            );
+        }
+        std::thread::sleep(wait + Duration::from_secs(1));
+        Ok(())
+    }
 }
 
-/// Check if `gh` CLI is available
-pub fn is_gh_available() -> bool {
-    Command::new("gh")
-        .arg("--version")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+impl Forge for GitHubClient<'_> {
+    fn kind(&self) -> ForgeKind {
+        ForgeKind::GitHub
+    }
+
+    fn host(&self) -> String {
+        api_host(&self.api_url)
+    }
+
+    fn get_repo_details(&self, owner: &str, repo: &str) -> Result<Option<RepoInfo>> {
+        GitHubClient::get_repo_details(self, owner, repo)
+    }
+
+    fn readme(&self, owner: &str, repo: &str) -> Result<String> {
+        GitHubClient::readme(self, owner, repo)
+    }
+
+    fn head_commit(&self, owner: &str, repo: &str) -> Result<String> {
+        GitHubClient::head_commit(self, owner, repo)
+    }
+
+    fn releases(&self, owner: &str, repo: &str) -> Result<Vec<Release>> {
+        GitHubClient::releases(self, owner, repo)
+    }
+}
+
+/// Rate limit reported by a response's `x-ratelimit-*` headers
+fn rate_limit_from_headers(header: &dyn Fn(&str) -> Option<String>) -> Option<RateLimit> {
+    let value = |name: &str| header(name)?.parse::<i64>().ok();
+    Some(RateLimit {
+        limit: value("x-ratelimit-limit")?,
+        remaining: value("x-ratelimit-remaining")?,
+        reset: value("x-ratelimit-reset")?,
+        used: value("x-ratelimit-used").unwrap_or(0),
+    })
+}
+
+/// Percent-encode a query string value or path segment
+pub(crate) fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// The web host an API URL belongs to, as `gh` names it (api.github.com -> github.com)
+pub fn api_host(api_url: &str) -> String {
+    let host = api_url
+        .split_once("://")
+        .map_or(api_url, |(_, rest)| rest)
+        .split('/')
+        .next()
+        .unwrap_or_default();
+    host.strip_prefix("api.").unwrap_or(host).to_string()
+}
+
+/// Find a token for an API: environment variables, `gh`'s hosts file, then `gh auth token`
+pub fn find_token(api_url: &str) -> Option<Token> {
+    if let Some(token) = stored_token(api_url) {
+        return Some(token);
+    }
+
+    // Newer gh versions keep the token in the system keyring
+    let host = api_host(api_url);
+    if which::which("gh").is_ok() {
+        let output = Command::new("gh")
+            .args(["auth", "token", "--hostname", &host])
+            .output()
+            .ok()?;
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if output.status.success() && !value.is_empty() {
+            return Some(Token {
+                value,
+                source: "gh auth token".to_string(),
+            });
+        }
+    }
+
+    None
+}
+
+/// A token from the environment or `gh`'s hosts file, without running anything
+///
+/// As with `gh`, `GITHUB_TOKEN`/`GH_TOKEN` are for github.com and
+/// `GH_ENTERPRISE_TOKEN`/`GITHUB_ENTERPRISE_TOKEN` for other hosts.
+pub fn stored_token(api_url: &str) -> Option<Token> {
+    let host = api_host(api_url);
+    let env_vars: &[&str] = if host == "github.com" {
+        &["GITHUB_TOKEN", "GH_TOKEN"]
+    } else {
+        &["GH_ENTERPRISE_TOKEN", "GITHUB_ENTERPRISE_TOKEN"]
+    };
+    for var in env_vars {
+        if let Ok(value) = std::env::var(var)
+            && !value.trim().is_empty()
+        {
+            return Some(Token {
+                value: value.trim().to_string(),
+                source: var.to_string(),
+            });
+        }
+    }
+
+    if let Some(path) = gh_config_dir().map(|d| d.join("hosts.yml"))
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Some(value) = token_from_hosts_yml(&content, &host)
+    {
+        return Some(Token {
+            value,
+            source: path.display().to_string(),
+        });
+    }
+
+    None
+}
+
+/// Where `gh` keeps its configuration
+fn gh_config_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("GH_CONFIG_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(dir).join("gh"));
+    }
+    dirs::home_dir().map(|h| h.join(".config").join("gh"))
+}
+
+/// The `oauth_token` stored for a host in `gh`'s `hosts.yml`
+///
+/// The file maps hosts to their settings; the host's own token is preferred
+/// over the per-user ones newer versions nest under `users:`.
+fn token_from_hosts_yml(content: &str, host: &str) -> Option<String> {
+    let mut in_host = false;
+    let mut best: Option<(usize, String)> = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let indent = line.len() - trimmed.len();
+        if indent == 0 {
+            in_host = trimmed.trim_end().trim_end_matches(':').trim_matches('"') == host;
+            continue;
+        }
+        if in_host
+            && let Some(value) = trimmed.strip_prefix("oauth_token:")
+            && best.as_ref().is_none_or(|(i, _)| indent < *i)
+        {
+            let value = value.trim().trim_matches(['"', '\'']);
+            if !value.is_empty() {
+                best = Some((indent, value.to_string()));
+            }
+        }
+    }
+
+    best.map(|(_, token)| token)
 }
 
 /// Map installation source to GitHub language filter for better search accuracy
@@ -149,75 +689,168 @@ pub fn source_to_language_filter(source: Option<&str>) -> Option<&'static str> {
     }
 }
 
-/// Search GitHub for a repository by name, optionally using source for language filtering
-pub fn search_repo(name: &str, source: Option<&str>) -> Result<Option<SearchResult>> {
-    // Build search query with language filter based on installation source
-    let query = match source_to_language_filter(source) {
-        Some(lang_filter) => format!("{} {}", name, lang_filter),
-        None => name.to_string(),
-    };
-
-    let output = Command::new("gh")
-        .args([
-            "search",
-            "repos",
-            &query,
-            "--json",
-            "name,fullName,description,stargazersCount,owner",
-            "--limit",
-            "1",
-        ])
-        .output()
-        .context("Failed to run gh search")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("gh search failed: {}", stderr);
-    }
+/// Topics that mark a repository as a command-line tool
+const CLI_TOPICS: &[&str] = &[
+    "cli",
+    "command-line",
+    "command-line-tool",
+    "commandline",
+    "cli-app",
+    "terminal",
+    "tui",
+];
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let results: Vec<SearchResult> =
-        serde_json::from_str(&stdout).context("Failed to parse gh search output")?;
+/// Languages command-line tools are usually written in
+const CLI_LANGUAGES: &[&str] = &[
+    "Rust", "Go", "C", "C++", "Zig", "Nim", "Haskell", "OCaml", "Crystal",
+];
 
-    Ok(results.into_iter().next())
+/// How much a repository looks like a command-line tool
+///
+/// CLI topics count 2, a description mentioning the command line or terminal
+/// and a typical CLI language count 1 each.
+pub fn cli_score(info: &RepoInfo) -> u32 {
+    let mut score = 0;
+    if info
+        .topics
+        .iter()
+        .any(|t| CLI_TOPICS.contains(&t.to_lowercase().as_str()))
+    {
+        score += 2;
+    }
+    if let Some(desc) = &info.description {
+        // Whole words, so "client" or "clipboard" isn't a mention of a CLI
+        let desc = desc.to_lowercase();
+        let words: Vec<&str> = desc
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .collect();
+        if words.iter().any(|w| matches!(*w, "cli" | "terminal"))
+            || words.windows(2).any(|pair| pair == ["command", "line"])
+        {
+            score += 1;
+        }
+    }
+    if info
+        .language
+        .as_deref()
+        .is_some_and(|l| CLI_LANGUAGES.contains(&l))
+    {
+        score += 1;
+    }
+    score
 }
 
-/// Get detailed repo info including topics
-pub fn get_repo_info(owner: &str, repo: &str) -> Result<RepoInfo> {
-    let output = Command::new("gh")
-        .args([
-            "api",
-            &format!("repos/{}/{}", owner, repo),
-            "--jq",
-            r#"{name, full_name: .full_name, description, stargazersCount: .stargazers_count, language, homepage, topics, owner: {login: .owner.login}}"#,
-        ])
-        .output()
-        .context("Failed to run gh api")?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        bail!("gh api failed: {}", stderr);
-    }
+/// Install commands recognized in READMEs, in order of preference
+const README_INSTALLERS: &[(&str, InstallSource)] = &[
+    ("cargo install ", InstallSource::Cargo),
+    ("cargo binstall ", InstallSource::Cargo),
+    ("brew install ", InstallSource::Brew),
+    ("pipx install ", InstallSource::Pip),
+    ("pip install ", InstallSource::Pip),
+    ("pip3 install ", InstallSource::Pip),
+    ("npm install -g ", InstallSource::Npm),
+    ("npm i -g ", InstallSource::Npm),
+    ("apt install ", InstallSource::Apt),
+    ("apt-get install ", InstallSource::Apt),
+    ("flatpak install ", InstallSource::Flatpak),
+    ("snap install ", InstallSource::Snap),
+    ("go install ", InstallSource::Manual),
+];
 
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let info: RepoInfo = serde_json::from_str(&stdout).context("Failed to parse gh api output")?;
+/// Find an install command in a README's code (fenced blocks or inline backticks)
+pub fn install_from_readme(readme: &str) -> Option<(InstallSource, String)> {
+    let mut in_fence = false;
+    let mut code: Vec<&str> = Vec::new();
+    for line in readme.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        } else if in_fence {
+            code.push(line);
+        } else {
+            // Inline code spans sit between odd and even backticks
+            code.extend(line.split('`').skip(1).step_by(2));
+        }
+    }
 
-    Ok(info)
+    README_INSTALLERS.iter().find_map(|(prefix, source)| {
+        code.iter().find_map(|line| {
+            let start = line.find(prefix)?;
+            let command = line[start..]
+                .split(['`', ';'])
+                .next()?
+                .split(" #")
+                .next()?
+                .split("&&")
+                .next()?
+                .trim();
+            (command.len() > prefix.len() && command.len() <= 100)
+                .then(|| (source.clone(), command.to_string()))
+        })
+    })
 }
 
-/// Search and get full repo info for a tool, using source for language filtering
-pub fn find_repo(tool_name: &str, source: Option<&str>) -> Result<Option<RepoInfo>> {
-    // First search for the repo, using language filter based on source
-    let search_result = search_repo(tool_name, source)?;
+/// Install command flags that take a value
+const VALUE_FLAGS: &[&str] = &[
+    "--version",
+    "--vers",
+    "--root",
+    "--features",
+    "-F",
+    "--bin",
+    "--target",
+    "--profile",
+    "--python",
+    "--suffix",
+];
 
-    match search_result {
-        Some(result) => {
-            // Get full info including topics
-            let info = get_repo_info(&result.owner.login, &result.name)?;
-            Ok(Some(info))
+/// Install command flags that install from somewhere other than the registry
+const NON_REGISTRY_FLAGS: &[&str] = &[
+    "--git",
+    "--path",
+    "--registry",
+    "--index",
+    "--index-url",
+    "-i",
+    "--extra-index-url",
+    "-e",
+    "--editable",
+];
+
+/// The registry package an install command (from [`install_from_readme`]) installs
+///
+/// `None` when it installs from a URL, a path, a tap or another registry, which
+/// `hoards install` can't reproduce from a package name.
+pub fn package_from_command(command: &str) -> Option<String> {
+    let (prefix, _) = README_INSTALLERS
+        .iter()
+        .find(|(prefix, _)| command.starts_with(prefix))?;
+    let mut args = command[prefix.len()..].split_whitespace();
+    while let Some(arg) = args.next() {
+        let arg = arg.trim_matches(['\'', '"']);
+        if arg.starts_with('-') {
+            let flag = arg.split('=').next().unwrap_or(arg);
+            if NON_REGISTRY_FLAGS.contains(&flag) {
+                return None;
+            }
+            if VALUE_FLAGS.contains(&flag) && !arg.contains('=') {
+                args.next();
+            }
+            continue;
         }
-        None => Ok(None),
+
+        // Drop version requirements (`tool==1.2`, `tool@1.2`, but not `@scope/tool`)
+        let package = arg.split(['=', '<', '>', '~', '!', '[']).next()?;
+        let package = match package.get(1..).and_then(|rest| rest.find('@')) {
+            Some(at) => &package[..=at],
+            None => package,
+        };
+        let scoped_npm = package.starts_with('@');
+        return (crate::commands::validate_package_name(package).is_ok()
+            && (scoped_npm || !package.contains('/')))
+        .then(|| package.to_string());
     }
+    None
 }
 
 /// Map GitHub topics to a category using the mapping config
@@ -250,7 +883,12 @@ pub struct TopicMapping {
 impl TopicMapping {
     /// Load mapping from TOML file or use defaults
     pub fn load() -> Self {
-        let config_path = dirs::config_dir().map(|d| d.join("hoard").join("topic-mapping.toml"));
+        // Older versions looked in `~/.config/hoard`
+        let config_path = crate::config::HoardConfig::config_dir()
+            .ok()
+            .map(|d| d.join("topic-mapping.toml"))
+            .filter(|p| p.exists())
+            .or_else(|| dirs::config_dir().map(|d| d.join("hoard").join("topic-mapping.toml")));
 
         if let Some(path) = config_path
             && path.exists()
@@ -420,9 +1058,104 @@ impl TopicMapping {
     }
 }
 
+/// Tools pinned to a repository, or to none, by the user
+///
+/// Kept in `repo-mapping.toml` next to the topic mapping. Sync, fetch and
+/// backfill follow these instead of searching. Repositories are `owner/repo`
+/// on GitHub, or a URL (or `host/owner/repo`) on another forge:
+///
+/// ```toml
+/// unlinked = ["mytool"]  # not on any forge, never search for it
+///
+/// [repos]
+/// ripgrep = "BurntSushi/ripgrep"
+/// fdroidcl = "https://gitlab.com/fdroid/fdroidcl"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct RepoMapping {
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub unlinked: BTreeSet<String>,
+    #[serde(default)]
+    pub repos: BTreeMap<String, String>,
+    /// Forge kinds of self-hosted servers, for parsing pins to them
+    #[serde(skip)]
+    hosts: BTreeMap<String, ForgeKind>,
+}
+
+/// How a tool's repository is chosen
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoPin {
+    /// Pinned to a repository
+    Linked(RepoRef),
+    /// Pinned to no repository
+    Unlinked,
+    /// Pinned to something that isn't a known repository, with the reason
+    ///
+    /// The tool is left alone rather than searched for, which could match the wrong repository.
+    Invalid(String),
+}
+
+impl RepoMapping {
+    /// Path of the mapping file
+    pub fn path() -> Result<PathBuf> {
+        crate::config::HoardConfig::config_dir().map(|d| d.join("repo-mapping.toml"))
+    }
+
+    /// Load the mapping, empty if there is no file
+    pub fn load() -> Result<Self> {
+        let path = Self::path()?;
+        let mut mapping = if path.exists() {
+            let content = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            toml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))?
+        } else {
+            Self::default()
+        };
+        mapping.hosts = crate::forge::configured_hosts();
+        Ok(mapping)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path()?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, toml::to_string_pretty(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// The pin for a tool, if the user set one
+    pub fn get(&self, tool_name: &str) -> Option<RepoPin> {
+        if self.unlinked.contains(tool_name) {
+            return Some(RepoPin::Unlinked);
+        }
+        let pin = self.repos.get(tool_name)?;
+        Some(match RepoRef::parse(pin, &self.hosts) {
+            Ok(repo) => RepoPin::Linked(repo),
+            Err(e) => RepoPin::Invalid(format!(
+                "invalid pin '{}' in repo-mapping.toml: {:#}",
+                pin, e
+            )),
+        })
+    }
+
+    /// Pin a tool to a repository
+    pub fn link(&mut self, tool_name: &str, repo: &RepoRef) {
+        self.unlinked.remove(tool_name);
+        self.repos.insert(tool_name.to_string(), repo.to_string());
+    }
+
+    /// Pin a tool to no repository
+    pub fn unlink(&mut self, tool_name: &str) {
+        self.repos.remove(tool_name);
+        self.unlinked.insert(tool_name.to_string());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::http::mock_server;
 
     #[test]
     fn test_topics_to_category() {
@@ -469,4 +1202,293 @@ mod tests {
         assert_eq!(source_to_language_filter(Some("apt")), None);
         assert_eq!(source_to_language_filter(None), None);
     }
+
+    fn token(value: &str) -> Option<Token> {
+        Some(Token {
+            value: value.to_string(),
+            source: "test".to_string(),
+        })
+    }
+
+    const REPO: &str = r#"{"name": "ripgrep", "full_name": "BurntSushi/ripgrep",
+        "description": "fast grep", "stargazers_count": 50000, "language": "Rust",
+        "homepage": null, "topics": ["cli", "search"], "owner": {"login": "BurntSushi"},
+        "license": {"spdx_id": "Unlicense"}, "archived": false, "open_issues_count": 80}"#;
+
+    #[test]
+    fn test_client_revalidates_cached_responses() {
+        let (url, server) = mock_server(vec![
+            format!(
+                "HTTP/1.1 200 OK\netag: \"abc\"\nx-ratelimit-limit: 5000\nx-ratelimit-remaining: 4999\nx-ratelimit-reset: 0\n\n{}",
+                REPO
+            ),
+            "HTTP/1.1 304 Not Modified".to_string(),
+        ]);
+        let db = Database::open_in_memory().unwrap();
+        let client = GitHubClient::new(&url, token("secret")).with_cache(&db);
+
+        let repo = client.get_repo("BurntSushi", "ripgrep").unwrap().unwrap();
+        assert_eq!(repo.full_name, "BurntSushi/ripgrep");
+        assert_eq!(repo.stars, 50000);
+        assert_eq!(repo.topics, ["cli", "search"]);
+        assert_eq!(repo.license_id(), Some("Unlicense"));
+        assert_eq!(repo.open_issues, Some(80));
+
+        // The second request is conditional, and the cached body is reused
+        let repo = client.get_repo("BurntSushi", "ripgrep").unwrap().unwrap();
+        assert_eq!(repo.owner.login, "BurntSushi");
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /repos/burntsushi/ripgrep "));
+        assert!(requests[0].contains("authorization: bearer secret"));
+        assert!(!requests[0].contains("if-none-match"));
+        assert!(requests[1].contains("if-none-match: \"abc\""));
+        assert_eq!(client.limits.borrow()["core"].remaining, 4999);
+    }
+
+    #[test]
+    fn test_client_search_and_missing_repo() {
+        let (url, server) = mock_server(vec![
+            r#"HTTP/1.1 200 OK
+
+{"total_count": 1, "items": [{"name": "fd", "full_name": "sharkdp/fd", "description": null, "stargazers_count": 30000, "owner": {"login": "sharkdp"}}]}"#,
+            "HTTP/1.1 404 Not Found\n\n{\"message\": \"Not Found\"}",
+        ]);
+        let client = GitHubClient::new(&url, None);
+
+        let results = client.search_repos("fd language:rust", 5).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].full_name, "sharkdp/fd");
+        assert!(client.get_repo("nobody", "nothing").unwrap().is_none());
+
+        let requests = server.join().unwrap();
+        assert!(
+            requests[0].starts_with("get /search/repositories?q=fd%20language%3arust&per_page=5 ")
+        );
+        assert!(!requests[0].contains("authorization"));
+    }
+
+    #[test]
+    fn test_client_rate_limit_exceeded() {
+        let (url, server) = mock_server(vec![
+            "HTTP/1.1 403 Forbidden\nx-ratelimit-limit: 60\nx-ratelimit-remaining: 0\nx-ratelimit-reset: 9999999999\n\n{\"message\": \"API rate limit exceeded\"}",
+        ]);
+        let client = GitHubClient::new(&url, None);
+
+        let err = client.get_repo("a", "b").unwrap_err().to_string();
+        assert!(err.contains("rate limit exceeded"), "{}", err);
+        server.join().unwrap();
+
+        // Known to be exhausted, so no further request is made
+        let err = client.get_repo("a", "b").unwrap_err().to_string();
+        assert!(err.contains("rate limit exceeded"), "{}", err);
+    }
+
+    #[test]
+    fn test_client_unauthorized() {
+        let (url, server) = mock_server(vec![
+            "HTTP/1.1 401 Unauthorized\n\n{\"message\": \"Bad credentials\"}",
+        ]);
+        let client = GitHubClient::new(&url, token("expired"));
+
+        let err = client.get_repo("a", "b").unwrap_err().to_string();
+        assert!(err.contains("token from test"), "{}", err);
+        server.join().unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_client_falls_back_to_gh() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let gh = dir.path().join("gh");
+        std::fs::write(
+            &gh,
+            format!(
+                "#!/bin/sh\necho \"$@\" >> {log}\ncase \"$*\" in\n  *missing*) echo 'gh: Not Found (HTTP 404)' >&2; exit 1 ;;\n  *) cat <<'EOF'\n{repo}\nEOF\nesac\n",
+                log = dir.path().join("log").display(),
+                repo = REPO
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&gh, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        // Nothing listens on port 1, so every request goes through gh
+        let mut client = GitHubClient::new("http://127.0.0.1:1", None);
+        client.gh = Some(gh);
+
+        let repo = client.get_repo("BurntSushi", "ripgrep").unwrap().unwrap();
+        assert_eq!(repo.full_name, "BurntSushi/ripgrep");
+        assert!(client.get_repo("nobody", "missing").unwrap().is_none());
+
+        let log = std::fs::read_to_string(dir.path().join("log")).unwrap();
+        assert!(log.starts_with("api --hostname 127.0.0.1:1 -H"), "{}", log);
+        assert!(log.contains("repos/BurntSushi/ripgrep"));
+
+        // Without gh the connection error stands
+        client.gh = None;
+        let err = client.get_repo("a", "b").unwrap_err().to_string();
+        assert!(err.starts_with("Failed to reach"), "{}", err);
+    }
+
+    #[test]
+    fn test_token_from_hosts_yml() {
+        let hosts = "github.com:
+    users:
+        octocat:
+            oauth_token: gho_user
+    oauth_token: gho_host
+    user: octocat
+ghe.example.com:
+    oauth_token: \"gho_enterprise\"
+";
+        assert_eq!(
+            token_from_hosts_yml(hosts, "github.com").as_deref(),
+            Some("gho_host")
+        );
+        assert_eq!(
+            token_from_hosts_yml(hosts, "ghe.example.com").as_deref(),
+            Some("gho_enterprise")
+        );
+        assert_eq!(token_from_hosts_yml(hosts, "gitlab.com"), None);
+    }
+
+    #[test]
+    fn test_repo_mapping() {
+        let mut mapping: RepoMapping = toml::from_str(
+            r#"
+            unlinked = ["internal-tool"]
+
+            [repos]
+            rg = "BurntSushi/ripgrep"
+            broken = "no-slash"
+            tool = "https://codeberg.org/someone/tool"
+            internal = "https://git.example.com/team/internal"
+            "#,
+        )
+        .unwrap();
+
+        let linked = |input: &str| {
+            Some(RepoPin::Linked(
+                RepoRef::parse(input, &BTreeMap::new()).unwrap(),
+            ))
+        };
+        assert_eq!(mapping.get("rg"), linked("BurntSushi/ripgrep"));
+        assert_eq!(mapping.get("tool"), linked("codeberg.org/someone/tool"));
+        assert_eq!(mapping.get("internal-tool"), Some(RepoPin::Unlinked));
+        assert!(
+            matches!(mapping.get("broken"), Some(RepoPin::Invalid(e)) if e.contains("no-slash"))
+        );
+        // Self-hosted servers need an entry in the config
+        assert!(
+            matches!(mapping.get("internal"), Some(RepoPin::Invalid(e)) if e.contains("git.example.com"))
+        );
+        assert_eq!(mapping.get("fd"), None);
+
+        let acme = RepoRef::parse("https://gitlab.com/acme/tools/tool", &BTreeMap::new()).unwrap();
+        mapping.link("internal-tool", &acme);
+        mapping.unlink("rg");
+        assert_eq!(mapping.get("internal-tool"), Some(RepoPin::Linked(acme)));
+        assert_eq!(mapping.repos["internal-tool"], "gitlab.com/acme/tools/tool");
+        assert_eq!(mapping.get("rg"), Some(RepoPin::Unlinked));
+
+        let saved = toml::to_string_pretty(&mapping).unwrap();
+        assert_eq!(toml::from_str::<RepoMapping>(&saved).unwrap(), mapping);
+    }
+
+    #[test]
+    fn test_starred_pages() {
+        // A full page means there may be more
+        let page: Vec<String> = (0..100)
+            .map(|_| REPO.replace(char::is_whitespace, ""))
+            .collect();
+        let (url, server) = mock_server(vec![
+            format!("HTTP/1.1 200 OK\n\n[{}]", page.join(",")),
+            format!("HTTP/1.1 200 OK\n\n[{}]", REPO),
+        ]);
+        let client = GitHubClient::new(&url, None);
+
+        assert_eq!(client.starred(Some("someone")).unwrap().len(), 101);
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("get /users/someone/starred?per_page=100&page=1 "));
+        assert!(requests[1].starts_with("get /users/someone/starred?per_page=100&page=2 "));
+    }
+
+    #[test]
+    fn test_cli_score() {
+        let mut repo: RepoInfo = serde_json::from_str(REPO).unwrap();
+        assert_eq!(cli_score(&repo), 3);
+
+        repo.topics = vec!["web".to_string()];
+        repo.description = Some("A fast command-line grep".to_string());
+        assert_eq!(cli_score(&repo), 2);
+
+        repo.language = Some("TypeScript".to_string());
+        repo.description = Some("A React component library".to_string());
+        assert_eq!(cli_score(&repo), 0);
+
+        // Words that merely start with "cli" don't count
+        repo.language = Some("Go".to_string());
+        repo.description = Some("Redis client library with clipboard support".to_string());
+        assert_eq!(cli_score(&repo), 1);
+        repo.description = Some("Redis CLI, for the terminal".to_string());
+        assert_eq!(cli_score(&repo), 2);
+    }
+
+    #[test]
+    fn test_install_from_readme() {
+        let readme = "# tool\n\nYou can cargo install it too.\n\n```sh\n$ brew install tool\n$ cargo install --locked tool # from source\n```\n";
+        assert_eq!(
+            install_from_readme(readme),
+            Some((
+                InstallSource::Cargo,
+                "cargo install --locked tool".to_string()
+            ))
+        );
+
+        let readme = "Install with `pipx install tool` or `npm i -g tool`.";
+        assert_eq!(
+            install_from_readme(readme),
+            Some((InstallSource::Pip, "pipx install tool".to_string()))
+        );
+
+        assert_eq!(install_from_readme("Just run make install."), None);
+    }
+
+    #[test]
+    fn test_package_from_command() {
+        let package = |command| package_from_command(command);
+        assert_eq!(package("pipx install foo-cli").as_deref(), Some("foo-cli"));
+        assert_eq!(
+            package("cargo install --locked --version 1.2 bar").as_deref(),
+            Some("bar")
+        );
+        assert_eq!(
+            package("pip install 'tool[all]>=2'").as_deref(),
+            Some("tool")
+        );
+        assert_eq!(
+            package("npm i -g @scope/tool@3").as_deref(),
+            Some("@scope/tool")
+        );
+        // Not installable by package name
+        assert_eq!(
+            package("cargo install --git https://example.com/x tool"),
+            None
+        );
+        assert_eq!(package("brew install owner/tap/tool"), None);
+        assert_eq!(package("go install example.com/tool@latest"), None);
+        assert_eq!(package("make install"), None);
+    }
+
+    #[test]
+    fn test_api_host() {
+        assert_eq!(api_host(DEFAULT_API_URL), "github.com");
+        assert_eq!(
+            api_host("https://ghe.example.com/api/v3"),
+            "ghe.example.com"
+        );
+        assert_eq!(api_host("http://127.0.0.1:8080"), "127.0.0.1:8080");
+    }
 }
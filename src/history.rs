@@ -1,17 +1,25 @@
 //! Shell history parsing for usage tracking
 //!
-//! Parses history files from Fish, Bash, and Zsh to count tool usage.
+//! Parses history from Fish, Bash, Zsh, Nushell, Elvish, xonsh and PowerShell
+//! (plus the Atuin and McFly databases) to count tool usage.
 
+use crate::config::HistoryProvider;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Parsed command from history
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct HistoryEntry {
     pub command: String,
     pub timestamp: Option<i64>,
+    /// Directory the command ran in, for histories that record it
+    pub cwd: Option<String>,
+    /// Exit status, for histories that record it
+    pub status: Option<i32>,
+    /// Wall-clock duration in milliseconds
+    pub duration_ms: Option<u64>,
 }
 
 /// Get the path to Fish history file
@@ -29,12 +37,121 @@ pub fn zsh_history_path() -> Option<PathBuf> {
     dirs::home_dir().map(|d| d.join(".zsh_history"))
 }
 
+/// Get the path to Nushell's plaintext history file
+pub fn nushell_history_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("nushell").join("history.txt"))
+}
+
+/// Get the path to Nushell's SQLite history (when `history.file_format = "sqlite"`)
+pub fn nushell_db_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("nushell").join("history.sqlite3"))
+}
+
+/// Get the path to PowerShell's PSReadLine history file
+pub fn powershell_history_path() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return dirs::config_dir().map(|d| {
+            d.join("Microsoft")
+                .join("Windows")
+                .join("PowerShell")
+                .join("PSReadLine")
+                .join("ConsoleHost_history.txt")
+        });
+    }
+    xdg_data_home().map(|d| {
+        d.join("powershell")
+            .join("PSReadLine")
+            .join("ConsoleHost_history.txt")
+    })
+}
+
+/// Get the directory holding xonsh's per-session JSON history files
+pub fn xonsh_history_dir() -> Option<PathBuf> {
+    std::env::var_os("XONSH_DATA_DIR")
+        .map(PathBuf::from)
+        .or_else(|| xdg_data_home().map(|d| d.join("xonsh")))
+        .map(|d| d.join("history_json"))
+}
+
+/// Get the path to Elvish's command store
+pub fn elvish_db_path() -> Option<PathBuf> {
+    // Elvish 0.18+ keeps state under XDG_STATE_HOME; older versions used ~/.elvish/db
+    let state = std::env::var_os("XDG_STATE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|d| d.join(".local").join("state")))
+        .map(|d| d.join("elvish").join("db.bolt"));
+    state
+        .filter(|p| p.exists())
+        .or_else(|| dirs::home_dir().map(|d| d.join(".elvish").join("db")))
+}
+
+/// XDG data directory (`~/.local/share`) on every platform, as used by XDG-minded tools
+fn xdg_data_home() -> Option<PathBuf> {
+    std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| dirs::home_dir().map(|d| d.join(".local").join("share")))
+}
+
+/// Shells whose history files can be scanned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryShell {
+    Fish,
+    Bash,
+    Zsh,
+    Nushell,
+    PowerShell,
+}
+
+impl HistoryShell {
+    pub fn name(&self) -> &'static str {
+        match self {
+            HistoryShell::Fish => "fish",
+            HistoryShell::Bash => "bash",
+            HistoryShell::Zsh => "zsh",
+            HistoryShell::Nushell => "nushell",
+            HistoryShell::PowerShell => "powershell",
+        }
+    }
+
+    /// Parse history file contents in this shell's format
+    pub fn parse(&self, content: &str) -> Vec<HistoryEntry> {
+        match self {
+            HistoryShell::Fish => parse_fish_entries(content),
+            HistoryShell::Bash => parse_bash_entries(content),
+            HistoryShell::Zsh => parse_zsh_entries(content),
+            HistoryShell::Nushell => parse_nushell_entries(content),
+            HistoryShell::PowerShell => parse_powershell_entries(content),
+        }
+    }
+}
+
+/// History files that exist on this system
+pub fn history_files() -> Vec<(HistoryShell, PathBuf)> {
+    [
+        (HistoryShell::Fish, fish_history_path()),
+        (HistoryShell::Bash, bash_history_path()),
+        (HistoryShell::Zsh, zsh_history_path()),
+        // Nushell writes either a plaintext or a SQLite history, never both
+        (
+            HistoryShell::Nushell,
+            nushell_history_path().filter(|_| !nushell_db_path().is_some_and(|p| p.exists())),
+        ),
+        (HistoryShell::PowerShell, powershell_history_path()),
+    ]
+    .into_iter()
+    .filter_map(|(shell, path)| path.filter(|p| p.exists()).map(|p| (shell, p)))
+    .collect()
+}
+
 /// Parse Fish history file
 /// Format: `- cmd: <command>\n  when: <timestamp>\n`
 pub fn parse_fish_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read fish history: {}", path.display()))?;
+    Ok(parse_fish_entries(&content))
+}
 
+fn parse_fish_entries(content: &str) -> Vec<HistoryEntry> {
     let mut entries = Vec::new();
     let mut current_cmd: Option<String> = None;
     let mut current_time: Option<i64> = None;
@@ -46,6 +163,7 @@ pub fn parse_fish_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
                 entries.push(HistoryEntry {
                     command: cmd,
                     timestamp: current_time.take(),
+                    ..Default::default()
                 });
             }
             current_cmd = Some(cmd.to_string());
@@ -59,27 +177,44 @@ pub fn parse_fish_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
         entries.push(HistoryEntry {
             command: cmd,
             timestamp: current_time,
+            ..Default::default()
         });
     }
 
-    Ok(entries)
+    entries
 }
 
-/// Parse Bash history file (simple format, one command per line)
+/// Parse Bash history file
+/// One command per line; with `HISTTIMEFORMAT` set, each command is preceded by `#<epoch>`
 pub fn parse_bash_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read bash history: {}", path.display()))?;
+    Ok(parse_bash_entries(&content))
+}
 
-    let entries = content
-        .lines()
-        .filter(|line| !line.is_empty() && !line.starts_with('#'))
-        .map(|line| HistoryEntry {
+fn parse_bash_entries(content: &str) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut pending_time: Option<i64> = None;
+
+    for line in content.lines() {
+        if line.is_empty() {
+            continue;
+        }
+        if let Some(comment) = line.strip_prefix('#') {
+            // Timestamp lines are `#` followed only by digits; anything else is a comment
+            if !comment.is_empty() && comment.bytes().all(|b| b.is_ascii_digit()) {
+                pending_time = comment.parse().ok();
+            }
+            continue;
+        }
+        entries.push(HistoryEntry {
             command: line.to_string(),
-            timestamp: None,
-        })
-        .collect();
+            timestamp: pending_time.take(),
+            ..Default::default()
+        });
+    }
 
-    Ok(entries)
+    entries
 }
 
 /// Parse Zsh history file
@@ -87,8 +222,11 @@ pub fn parse_bash_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
 pub fn parse_zsh_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read zsh history: {}", path.display()))?;
+    Ok(parse_zsh_entries(&content))
+}
 
-    let entries = content
+fn parse_zsh_entries(content: &str) -> Vec<HistoryEntry> {
+    content
         .lines()
         .filter(|line| !line.is_empty())
         .map(|line| {
@@ -102,54 +240,683 @@ pub fn parse_zsh_history(path: &PathBuf) -> Result<Vec<HistoryEntry>> {
                 return HistoryEntry {
                     command: command.to_string(),
                     timestamp,
+                    ..Default::default()
                 };
             }
             // Simple format
             HistoryEntry {
                 command: line.to_string(),
                 timestamp: None,
+                ..Default::default()
             }
         })
-        .collect();
+        .collect()
+}
 
-    Ok(entries)
+/// Parse Nushell plaintext history
+/// One command per line, with embedded newlines stored as `<\n>`
+fn parse_nushell_entries(content: &str) -> Vec<HistoryEntry> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| HistoryEntry {
+            command: line.replace("<\\n>", "\n"),
+            timestamp: None,
+            ..Default::default()
+        })
+        .collect()
 }
 
-/// Extract the base command from a command line (first word, without path)
-pub fn extract_command(line: &str) -> Option<&str> {
-    let line = line.trim();
+/// Parse PowerShell PSReadLine history
+/// One command per line; a trailing backtick continues the command on the next line
+fn parse_powershell_entries(content: &str) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut current = String::new();
+
+    for line in content.lines() {
+        if let Some(continued) = line.strip_suffix('`') {
+            current.push_str(continued);
+            current.push('\n');
+            continue;
+        }
+        current.push_str(line);
+        if !current.trim().is_empty() {
+            entries.push(HistoryEntry {
+                command: std::mem::take(&mut current),
+                timestamp: None,
+                ..Default::default()
+            });
+        }
+        current.clear();
+    }
+
+    entries
+}
+
+/// Parse a xonsh JSON history file (`data.cmds[].inp`, start time in `ts[0]`)
+fn parse_xonsh_entries(content: &str) -> Vec<HistoryEntry> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(content) else {
+        return Vec::new();
+    };
+
+    json.pointer("/data/cmds")
+        .and_then(|c| c.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|cmd| {
+            let input = cmd.get("inp")?.as_str()?.trim();
+            (!input.is_empty()).then(|| HistoryEntry {
+                command: input.to_string(),
+                timestamp: cmd
+                    .pointer("/ts/0")
+                    .and_then(|t| t.as_f64())
+                    .map(|t| t as i64),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Parse `store:cmds ... | to-json` output from Elvish: one `{"seq":n,"text":"..."}` per line
+fn parse_elvish_cmds(output: &str) -> Vec<(u64, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let json: serde_json::Value = serde_json::from_str(line).ok()?;
+            let seq = json.get("seq")?.as_f64()? as u64;
+            let text = json.get("text")?.as_str()?.to_string();
+            Some((seq, text))
+        })
+        .collect()
+}
 
-    // Skip empty lines or common prefixes
-    if line.is_empty() {
-        return None;
+// ==================== Incremental Scanning ====================
+
+/// Bytes fingerprinted at the end of the ingested region to recognise it later
+const CHECKPOINT_TAIL_LEN: usize = 256;
+
+/// Multiplier of the polynomial rolling hash used for tail fingerprints
+const TAIL_HASH_BASE: u64 = 0x100_0000_01b3;
+
+/// Where the previous scan of a history file stopped
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryCheckpoint {
+    /// Inode of the file when it was read (changes when the file is replaced)
+    pub inode: u64,
+    /// Byte offset just past the last ingested line
+    pub offset: u64,
+    /// Newest entry timestamp seen so far
+    pub last_timestamp: Option<i64>,
+    /// The last bytes before `offset`, used to detect rewrites and find our place again
+    pub tail: TailFingerprint,
+}
+
+/// Length and rolling hash of the bytes just before a checkpoint
+///
+/// Enough to find where the previous scan stopped without keeping any of the
+/// history itself (which may hold secrets passed as arguments).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TailFingerprint {
+    pub len: usize,
+    pub hash: u64,
+}
+
+impl TailFingerprint {
+    /// Stored size of a fingerprint
+    pub const ENCODED_LEN: usize = 16;
+
+    pub fn of(bytes: &[u8]) -> Self {
+        Self {
+            len: bytes.len(),
+            hash: bytes.iter().fold(0, |hash, &b| roll_in(hash, b)),
+        }
     }
 
-    // Handle sudo, env, time, etc.
-    let line = line
-        .strip_prefix("sudo ")
-        .or_else(|| line.strip_prefix("env "))
-        .or_else(|| line.strip_prefix("time "))
-        .or_else(|| line.strip_prefix("command "))
-        .unwrap_or(line);
+    /// Whether `content` ends with the fingerprinted bytes
+    fn is_suffix_of(&self, content: &[u8]) -> bool {
+        content.len() >= self.len && Self::of(&content[content.len() - self.len..]) == *self
+    }
+
+    /// End offset of the last run of bytes in `content` with this fingerprint
+    fn rfind_end(&self, content: &[u8]) -> Option<usize> {
+        if self.len == 0 || content.len() < self.len {
+            return None;
+        }
+
+        // Weight of the byte leaving the window: BASE^(len - 1)
+        let top = (1..self.len).fold(1u64, |p, _| p.wrapping_mul(TAIL_HASH_BASE));
+        let mut hash = Self::of(&content[..self.len]).hash;
+        let mut found = (hash == self.hash).then_some(self.len);
+        for end in self.len..content.len() {
+            let leaving = (content[end - self.len] as u64 + 1).wrapping_mul(top);
+            hash = roll_in(hash.wrapping_sub(leaving), content[end]);
+            if hash == self.hash {
+                found = Some(end + 1);
+            }
+        }
+        found
+    }
+
+    /// Stored form: length then hash, little-endian
+    pub fn to_bytes(self) -> Vec<u8> {
+        let mut bytes = (self.len as u64).to_le_bytes().to_vec();
+        bytes.extend_from_slice(&self.hash.to_le_bytes());
+        bytes
+    }
+
+    /// Read a stored fingerprint; older versions stored the raw bytes instead
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        match bytes.split_at_checked(8) {
+            Some((len, hash)) if bytes.len() == Self::ENCODED_LEN => Self {
+                len: u64::from_le_bytes(len.try_into().unwrap_or_default()) as usize,
+                hash: u64::from_le_bytes(hash.try_into().unwrap_or_default()),
+            },
+            _ => Self::of(bytes),
+        }
+    }
+}
+
+fn roll_in(hash: u64, byte: u8) -> u64 {
+    // Offset by one so leading zero bytes still change the hash
+    hash.wrapping_mul(TAIL_HASH_BASE)
+        .wrapping_add(byte as u64 + 1)
+}
+
+/// Read the entries added to a history file since `checkpoint`
+///
+/// Returns the new entries and the checkpoint to store for the next scan.
+pub fn read_new_entries(
+    shell: HistoryShell,
+    path: &Path,
+    checkpoint: Option<&HistoryCheckpoint>,
+) -> Result<(Vec<HistoryEntry>, HistoryCheckpoint)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let content = fs::read(path).with_context(|| {
+        format!(
+            "Failed to read {} history: {}",
+            shell.name(),
+            path.display()
+        )
+    })?;
+    let inode = fs::metadata(path)?.ino();
+
+    Ok(new_entries_since(shell, &content, inode, checkpoint))
+}
+
+fn new_entries_since(
+    shell: HistoryShell,
+    content: &[u8],
+    inode: u64,
+    checkpoint: Option<&HistoryCheckpoint>,
+) -> (Vec<HistoryEntry>, HistoryCheckpoint) {
+    // Only consume complete lines so a half-written entry is picked up next time
+    let end = content
+        .iter()
+        .rposition(|&b| b == b'\n')
+        .map_or(0, |pos| pos + 1);
+    let content = &content[..end];
+
+    let (start, newer_than) = match checkpoint {
+        Some(cp) => resume_point(content, inode, cp),
+        None => (0, None),
+    };
+
+    let mut entries = shell.parse(&String::from_utf8_lossy(&content[start..]));
+    if let Some(since) = newer_than {
+        entries.retain(|e| e.timestamp.is_some_and(|ts| ts > since));
+    }
+
+    let last_timestamp = entries
+        .iter()
+        .filter_map(|e| e.timestamp)
+        .chain(checkpoint.and_then(|cp| cp.last_timestamp))
+        .max();
+
+    let next = HistoryCheckpoint {
+        inode,
+        offset: end as u64,
+        last_timestamp,
+        tail: TailFingerprint::of(&content[end.saturating_sub(CHECKPOINT_TAIL_LEN)..]),
+    };
+
+    (entries, next)
+}
+
+/// Decide where to resume reading: (byte offset, only keep entries newer than)
+fn resume_point(content: &[u8], inode: u64, cp: &HistoryCheckpoint) -> (usize, Option<i64>) {
+    let offset = cp.offset as usize;
+    if offset == 0 {
+        return (0, None);
+    }
+
+    // Appended to in place: same file, and the bytes before our offset are unchanged
+    if cp.inode == inode && offset <= content.len() && cp.tail.is_suffix_of(&content[..offset]) {
+        return (offset, None);
+    }
+
+    // Rewritten (fish saves, HISTFILESIZE trimming) or rotated: find the last chunk we ingested
+    if let Some(end) = cp.tail.rfind_end(content) {
+        return (end, None);
+    }
+
+    match cp.last_timestamp {
+        // Timestamped history: anything newer than what we've seen is new
+        Some(ts) => (0, Some(ts)),
+        // Truncated and started over
+        None if content.len() < offset => (0, None),
+        // No way to tell old from new without double counting - start from the end
+        None => (content.len(), None),
+    }
+}
+
+// ==================== History Inputs ====================
+
+/// Where a history input stores its commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryKind {
+    /// A plain shell history file
+    Shell(HistoryShell),
+    /// Atuin's SQLite database
+    Atuin,
+    /// McFly's SQLite database
+    Mcfly,
+    /// Nushell's SQLite history
+    NushellDb,
+    /// Elvish's command store (read through the `elvish` binary)
+    Elvish,
+    /// One xonsh JSON session file
+    Xonsh,
+}
+
+/// A history file or database that `usage scan` reads from
+#[derive(Debug, Clone)]
+pub struct HistoryInput {
+    pub kind: HistoryKind,
+    pub path: PathBuf,
+}
+
+impl HistoryInput {
+    /// Short label for messages ("fish", "atuin", ...)
+    pub fn name(&self) -> &'static str {
+        match self.kind {
+            HistoryKind::Shell(shell) => shell.name(),
+            HistoryKind::Atuin => "atuin",
+            HistoryKind::Mcfly => "mcfly",
+            HistoryKind::NushellDb => "nushell",
+            HistoryKind::Elvish => "elvish",
+            HistoryKind::Xonsh => "xonsh",
+        }
+    }
+
+    /// Read entries added since `checkpoint`, returning the next checkpoint
+    pub fn read_new_entries(
+        &self,
+        checkpoint: Option<&HistoryCheckpoint>,
+    ) -> Result<(Vec<HistoryEntry>, HistoryCheckpoint)> {
+        match self.kind {
+            HistoryKind::Shell(shell) => read_new_entries(shell, &self.path, checkpoint),
+            HistoryKind::Atuin => {
+                read_atuin_entries(&self.path, checkpoint, local_hostname().as_deref())
+            }
+            HistoryKind::Mcfly => read_mcfly_entries(&self.path, checkpoint),
+            HistoryKind::NushellDb => read_nushell_db_entries(&self.path, checkpoint),
+            HistoryKind::Elvish => read_elvish_entries(&self.path, checkpoint),
+            HistoryKind::Xonsh => read_xonsh_entries(&self.path, checkpoint),
+        }
+    }
+}
+
+/// History inputs available for the configured provider
+pub fn history_inputs(provider: HistoryProvider) -> Vec<HistoryInput> {
+    match provider {
+        HistoryProvider::Shell => {
+            let mut inputs: Vec<HistoryInput> = history_files()
+                .into_iter()
+                .map(|(shell, path)| HistoryInput {
+                    kind: HistoryKind::Shell(shell),
+                    path,
+                })
+                .collect();
+
+            if let Some(path) = nushell_db_path().filter(|p| p.exists()) {
+                inputs.push(HistoryInput {
+                    kind: HistoryKind::NushellDb,
+                    path,
+                });
+            }
+            if let Some(path) = elvish_db_path().filter(|p| p.exists())
+                && which::which("elvish").is_ok()
+            {
+                inputs.push(HistoryInput {
+                    kind: HistoryKind::Elvish,
+                    path,
+                });
+            }
+            // xonsh writes one JSON file per session
+            if let Some(dir) = xonsh_history_dir() {
+                let mut sessions: Vec<PathBuf> = fs::read_dir(dir)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                    .collect();
+                sessions.sort();
+                inputs.extend(sessions.into_iter().map(|path| HistoryInput {
+                    kind: HistoryKind::Xonsh,
+                    path,
+                }));
+            }
+
+            inputs
+        }
+        HistoryProvider::Atuin => atuin_db_path()
+            .filter(|p| p.exists())
+            .map(|path| HistoryInput {
+                kind: HistoryKind::Atuin,
+                path,
+            })
+            .into_iter()
+            .collect(),
+        HistoryProvider::Mcfly => mcfly_db_path()
+            .filter(|p| p.exists())
+            .map(|path| HistoryInput {
+                kind: HistoryKind::Mcfly,
+                path,
+            })
+            .into_iter()
+            .collect(),
+    }
+}
+
+// ==================== History Databases ====================
+
+/// Get the path to Atuin's history database (respects ATUIN_DB_PATH)
+pub fn atuin_db_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("ATUIN_DB_PATH") {
+        return Some(PathBuf::from(path));
+    }
+    // Atuin uses the XDG layout on every platform
+    xdg_data_home().map(|d| d.join("atuin").join("history.db"))
+}
+
+/// Get the path to McFly's history database
+pub fn mcfly_db_path() -> Option<PathBuf> {
+    // Older McFly versions kept everything in ~/.mcfly
+    let legacy = dirs::home_dir().map(|d| d.join(".mcfly").join("history.db"));
+    if let Some(path) = legacy.as_ref().filter(|p| p.exists()) {
+        return Some(path.clone());
+    }
+    dirs::data_dir()
+        .map(|d| d.join("mcfly").join("history.db"))
+        .filter(|p| p.exists())
+        .or_else(|| dirs::data_dir().map(|d| d.join("McFly").join("history.db")))
+}
+
+/// Hostname of this machine, used to skip synced history from other hosts
+pub fn local_hostname() -> Option<String> {
+    std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| {
+            std::process::Command::new("hostname")
+                .output()
+                .ok()
+                .map(|o| String::from_utf8_lossy(&o.stdout).into_owned())
+        })
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+}
+
+/// Read new commands from Atuin's database
+///
+/// Atuin stores nanosecond timestamps and durations (-1 when unknown, like the
+/// exit status) and syncs history between machines as `hostname:user`; only
+/// commands from `host` count towards local usage.
+fn read_atuin_entries(
+    path: &Path,
+    checkpoint: Option<&HistoryCheckpoint>,
+    host: Option<&str>,
+) -> Result<(Vec<HistoryEntry>, HistoryCheckpoint)> {
+    let conn = open_history_db(path)?;
+    let has_deleted = conn
+        .prepare("SELECT deleted_at FROM history LIMIT 0")
+        .is_ok();
+    let query = format!(
+        "SELECT rowid, timestamp / 1000000000, command, hostname, cwd,
+                CASE WHEN exit >= 0 THEN exit END,
+                CASE WHEN duration >= 0 THEN duration / 1000000 END
+         FROM history
+         WHERE rowid > ?1 AND timestamp / 1000000000 > ?2{}
+         ORDER BY rowid",
+        if has_deleted {
+            " AND deleted_at IS NULL"
+        } else {
+            ""
+        }
+    );
+
+    let (mut entries, next) = read_db_entries(&conn, path, "history", &query, checkpoint)?;
+    if let Some(host) = host {
+        entries.retain(|(entry_host, _)| {
+            entry_host.as_deref().is_none_or(|h| {
+                h == host
+                    || h.strip_prefix(host)
+                        .is_some_and(|rest| rest.starts_with(':'))
+            })
+        });
+    }
+
+    Ok((entries.into_iter().map(|(_, e)| e).collect(), next))
+}
+
+/// Read new commands from McFly's database (timestamps are in seconds)
+fn read_mcfly_entries(
+    path: &Path,
+    checkpoint: Option<&HistoryCheckpoint>,
+) -> Result<(Vec<HistoryEntry>, HistoryCheckpoint)> {
+    let conn = open_history_db(path)?;
+    let query = "SELECT rowid, when_run, cmd, NULL, NULL, NULL, NULL FROM commands
+                 WHERE rowid > ?1 AND when_run > ?2
+                 ORDER BY rowid";
+    let (entries, next) = read_db_entries(&conn, path, "commands", query, checkpoint)?;
+    Ok((entries.into_iter().map(|(_, e)| e).collect(), next))
+}
 
-    // Get first word
-    let cmd = line.split_whitespace().next()?;
+/// Read new commands from Nushell's SQLite history (millisecond timestamps)
+fn read_nushell_db_entries(
+    path: &Path,
+    checkpoint: Option<&HistoryCheckpoint>,
+) -> Result<(Vec<HistoryEntry>, HistoryCheckpoint)> {
+    let conn = open_history_db(path)?;
+    let query =
+        "SELECT rowid, start_timestamp / 1000, command_line, NULL, NULL, NULL, NULL FROM history
+                 WHERE rowid > ?1 AND COALESCE(start_timestamp / 1000, 0) > ?2
+                 ORDER BY rowid";
+    let (entries, next) = read_db_entries(&conn, path, "history", query, checkpoint)?;
+    Ok((entries.into_iter().map(|(_, e)| e).collect(), next))
+}
+
+/// Read new commands from Elvish's store
+///
+/// The store is a bbolt database, so it's read through `elvish` itself. Commands
+/// carry a sequence number (used as the checkpoint offset) but no timestamp.
+fn read_elvish_entries(
+    path: &Path,
+    checkpoint: Option<&HistoryCheckpoint>,
+) -> Result<(Vec<HistoryEntry>, HistoryCheckpoint)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let inode = fs::metadata(path)?.ino();
+    let from = checkpoint
+        .filter(|cp| cp.inode == inode)
+        .map_or(0, |cp| cp.offset);
+
+    let script = format!(
+        "use store; store:cmds {} (store:next-cmd-seq) | to-json",
+        from
+    );
+    let output = std::process::Command::new("elvish")
+        .args(["-norc", "-c", &script])
+        .output()
+        .context("Failed to run elvish")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "elvish store query failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let cmds = parse_elvish_cmds(&String::from_utf8_lossy(&output.stdout));
+    let next = HistoryCheckpoint {
+        inode,
+        offset: cmds.last().map_or(from, |(seq, _)| seq + 1),
+        last_timestamp: None,
+        tail: TailFingerprint::default(),
+    };
+    let entries = cmds
+        .into_iter()
+        .map(|(_, command)| HistoryEntry {
+            command,
+            timestamp: None,
+            ..Default::default()
+        })
+        .collect();
 
-    // Remove path prefix (e.g., /usr/bin/git -> git)
-    let cmd = cmd.rsplit('/').next().unwrap_or(cmd);
+    Ok((entries, next))
+}
 
-    // Skip shell builtins and common non-tools
-    let skip = [
-        "cd", "ls", "echo", "export", "set", "unset", "alias", "source", "if", "then", "else",
-        "fi", "for", "do", "done", "while", "case", "esac", "function", "return", "exit", "true",
-        "false", "test", "[", "[[", "pwd", "pushd", "popd", "dirs", "history", "clear",
-    ];
+/// Read new commands from a xonsh JSON session file
+///
+/// xonsh rewrites the whole file as the session goes on, so the checkpoint
+/// offset counts commands rather than bytes.
+fn read_xonsh_entries(
+    path: &Path,
+    checkpoint: Option<&HistoryCheckpoint>,
+) -> Result<(Vec<HistoryEntry>, HistoryCheckpoint)> {
+    use std::os::unix::fs::MetadataExt;
 
-    if skip.contains(&cmd) {
-        return None;
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read xonsh history: {}", path.display()))?;
+    let inode = fs::metadata(path)?.ino();
+    let mut entries = parse_xonsh_entries(&content);
+    let total = entries.len() as u64;
+
+    match checkpoint {
+        Some(cp) if cp.offset <= total => {
+            entries.drain(..cp.offset as usize);
+        }
+        Some(cp) => {
+            let since = cp.last_timestamp.unwrap_or(i64::MAX);
+            entries.retain(|e| e.timestamp.is_some_and(|ts| ts > since));
+        }
+        None => {}
     }
 
-    Some(cmd)
+    let last_timestamp = entries
+        .iter()
+        .filter_map(|e| e.timestamp)
+        .chain(checkpoint.and_then(|cp| cp.last_timestamp))
+        .max();
+    let next = HistoryCheckpoint {
+        inode,
+        offset: total,
+        last_timestamp,
+        tail: TailFingerprint::default(),
+    };
+
+    Ok((entries, next))
+}
+
+/// History entry tagged with the host it ran on (when the database records it)
+type HostEntry = (Option<String>, HistoryEntry);
+
+fn open_history_db(path: &Path) -> Result<rusqlite::Connection> {
+    rusqlite::Connection::open_with_flags(
+        path,
+        rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY | rusqlite::OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("Failed to open history database: {}", path.display()))
+}
+
+/// Run a `(rowid, timestamp, command, hostname, cwd, exit status, duration in ms)`
+/// query from where the checkpoint left off
+///
+/// Checkpoints for databases store the last rowid as `offset`. If the database
+/// was replaced (new inode, or fewer rows than before), fall back to timestamps.
+fn read_db_entries(
+    conn: &rusqlite::Connection,
+    path: &Path,
+    table: &str,
+    query: &str,
+    checkpoint: Option<&HistoryCheckpoint>,
+) -> Result<(Vec<HostEntry>, HistoryCheckpoint)> {
+    use std::os::unix::fs::MetadataExt;
+
+    let inode = fs::metadata(path)?.ino();
+    let max_rowid: i64 = conn.query_row(
+        &format!("SELECT COALESCE(MAX(rowid), 0) FROM {}", table),
+        [],
+        |row| row.get(0),
+    )?;
+
+    let (after_rowid, after_time) = match checkpoint {
+        Some(cp) if cp.inode == inode && cp.offset as i64 <= max_rowid => {
+            (cp.offset as i64, i64::MIN)
+        }
+        Some(cp) => (0, cp.last_timestamp.unwrap_or(i64::MIN)),
+        None => (0, i64::MIN),
+    };
+
+    let mut stmt = conn.prepare(query)?;
+    let rows = stmt
+        .query_map(rusqlite::params![after_rowid, after_time], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(3)?,
+                HistoryEntry {
+                    command: row.get(2)?,
+                    timestamp: row.get(1)?,
+                    cwd: row.get(4)?,
+                    status: row.get(5)?,
+                    duration_ms: row.get(6)?,
+                },
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let last_rowid = rows.last().map_or(after_rowid, |row| row.0);
+    let last_timestamp = rows
+        .iter()
+        .filter_map(|row| row.2.timestamp)
+        .chain(checkpoint.and_then(|cp| cp.last_timestamp))
+        .max();
+
+    let entries = rows
+        .into_iter()
+        .map(|(_, host, entry)| (host, entry))
+        .collect();
+
+    let next = HistoryCheckpoint {
+        inode,
+        offset: last_rowid as u64,
+        last_timestamp,
+        tail: TailFingerprint::default(),
+    };
+
+    Ok((entries, next))
+}
+
+/// Extract the first tool a command line runs (without path)
+pub fn extract_command(line: &str) -> Option<&str> {
+    extract_commands(line).into_iter().next()
+}
+
+/// Extract every tool a command line runs, across pipelines, `&&`/`||`/`;`,
+/// subshells and substitutions, with wrappers like `sudo` or `xargs` unwrapped
+pub fn extract_commands(line: &str) -> Vec<&str> {
+    crate::cmdline::invoked_commands(line)
 }
 
 /// Count command usage from history entries
@@ -157,7 +924,7 @@ pub fn count_commands(entries: &[HistoryEntry]) -> HashMap<String, i64> {
     let mut counts: HashMap<String, i64> = HashMap::new();
 
     for entry in entries {
-        if let Some(cmd) = extract_command(&entry.command) {
+        for cmd in extract_commands(&entry.command) {
             *counts.entry(cmd.to_string()).or_insert(0) += 1;
         }
     }
@@ -169,48 +936,14 @@ pub fn count_commands(entries: &[HistoryEntry]) -> HashMap<String, i64> {
 pub fn parse_all_histories() -> Result<HashMap<String, i64>> {
     let mut total_counts: HashMap<String, i64> = HashMap::new();
 
-    // Try Fish history
-    if let Some(path) = fish_history_path()
-        && path.exists()
-    {
-        match parse_fish_history(&path) {
-            Ok(entries) => {
-                let counts = count_commands(&entries);
-                for (cmd, count) in counts {
-                    *total_counts.entry(cmd).or_insert(0) += count;
-                }
-            }
-            Err(e) => eprintln!("Warning: Failed to parse fish history: {}", e),
-        }
-    }
-
-    // Try Bash history
-    if let Some(path) = bash_history_path()
-        && path.exists()
-    {
-        match parse_bash_history(&path) {
-            Ok(entries) => {
-                let counts = count_commands(&entries);
-                for (cmd, count) in counts {
-                    *total_counts.entry(cmd).or_insert(0) += count;
-                }
-            }
-            Err(e) => eprintln!("Warning: Failed to parse bash history: {}", e),
-        }
-    }
-
-    // Try Zsh history
-    if let Some(path) = zsh_history_path()
-        && path.exists()
-    {
-        match parse_zsh_history(&path) {
-            Ok(entries) => {
-                let counts = count_commands(&entries);
-                for (cmd, count) in counts {
+    for input in history_inputs(HistoryProvider::Shell) {
+        match input.read_new_entries(None) {
+            Ok((entries, _)) => {
+                for (cmd, count) in count_commands(&entries) {
                     *total_counts.entry(cmd).or_insert(0) += count;
                 }
             }
-            Err(e) => eprintln!("Warning: Failed to parse zsh history: {}", e),
+            Err(e) => eprintln!("Warning: Failed to parse {} history: {}", input.name(), e),
         }
     }
 
@@ -292,18 +1025,22 @@ mod tests {
             HistoryEntry {
                 command: "git status".to_string(),
                 timestamp: None,
+                ..Default::default()
             },
             HistoryEntry {
                 command: "git commit".to_string(),
                 timestamp: None,
+                ..Default::default()
             },
             HistoryEntry {
                 command: "rg pattern".to_string(),
                 timestamp: None,
+                ..Default::default()
             },
             HistoryEntry {
                 command: "git push".to_string(),
                 timestamp: None,
+                ..Default::default()
             },
         ];
 
@@ -325,10 +1062,12 @@ mod tests {
             HistoryEntry {
                 command: "cd /tmp".to_string(),
                 timestamp: None,
+                ..Default::default()
             },
             HistoryEntry {
                 command: "echo hello".to_string(),
                 timestamp: None,
+                ..Default::default()
             },
         ];
         let counts = count_commands(&entries);
@@ -388,12 +1127,36 @@ mod tests {
         assert_eq!(entries[0].command, "git status");
         assert_eq!(entries[1].command, "cargo build");
         assert_eq!(entries[2].command, "rg pattern");
-        // Bash history doesn't have timestamps
+        // Without HISTTIMEFORMAT there are no timestamps
         assert!(entries[0].timestamp.is_none());
 
         Ok(())
     }
 
+    #[test]
+    fn test_parse_bash_history_with_timestamps() -> Result<()> {
+        let mut file = NamedTempFile::new()?;
+        writeln!(file, "#1704067200")?;
+        writeln!(file, "git status")?;
+        writeln!(file, "# a real comment")?;
+        writeln!(file, "#1704067300")?;
+        writeln!(file, "cargo build")?;
+        writeln!(file, "rg pattern")?;
+        file.flush()?;
+
+        let path = file.path().to_path_buf();
+        let entries = parse_bash_history(&path)?;
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].command, "git status");
+        assert_eq!(entries[0].timestamp, Some(1704067200));
+        assert_eq!(entries[1].timestamp, Some(1704067300));
+        // Timestamp applies to the next command only
+        assert!(entries[2].timestamp.is_none());
+
+        Ok(())
+    }
+
     // ==================== Zsh History Parsing Tests ====================
 
     #[test]
@@ -466,6 +1229,291 @@ mod tests {
         assert!(fish.is_some() || bash.is_some() || zsh.is_some());
     }
 
+    // ==================== Other Shell Parsing Tests ====================
+
+    #[test]
+    fn test_parse_nushell_history() {
+        let entries =
+            parse_nushell_entries("ls | where size > 1kb\nfor x in [1 2] {<\\n>  rg $x<\\n>}\n\n");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].command, "ls | where size > 1kb");
+        assert_eq!(entries[1].command, "for x in [1 2] {\n  rg $x\n}");
+    }
+
+    #[test]
+    fn test_parse_powershell_history() {
+        let entries =
+            parse_powershell_entries("git status\nGet-ChildItem `\n  -Recurse\n\nrg foo\n");
+        assert_eq!(
+            commands(&entries),
+            ["git status", "Get-ChildItem \n  -Recurse", "rg foo"]
+        );
+    }
+
+    #[test]
+    fn test_parse_xonsh_history() {
+        let json = r#"{"data": {"cmds": [
+            {"inp": "git status\n", "rtn": 0, "ts": [1704067200.5, 1704067201.0]},
+            {"inp": "   ", "ts": [1704067250.0, 1704067251.0]},
+            {"inp": "rg foo\n", "rtn": 1, "ts": [1704067300.1, 1704067300.2]}
+        ]}, "locked": true}"#;
+        let entries = parse_xonsh_entries(json);
+        assert_eq!(commands(&entries), ["git status", "rg foo"]);
+        assert_eq!(entries[0].timestamp, Some(1704067200));
+        assert!(parse_xonsh_entries("not json").is_empty());
+    }
+
+    #[test]
+    fn test_read_xonsh_entries_incremental() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("xonsh-session.json");
+        let session = |cmds: &[(&str, f64)]| {
+            let cmds: Vec<_> = cmds
+                .iter()
+                .map(|(inp, ts)| serde_json::json!({"inp": inp, "ts": [ts, ts]}))
+                .collect();
+            serde_json::json!({"data": {"cmds": cmds}}).to_string()
+        };
+
+        fs::write(&path, session(&[("git status", 100.0)]))?;
+        let (entries, cp) = read_xonsh_entries(&path, None)?;
+        assert_eq!(commands(&entries), ["git status"]);
+
+        // xonsh rewrites the file with the full session each flush
+        fs::write(&path, session(&[("git status", 100.0), ("fd x", 200.0)]))?;
+        let (entries, cp) = read_xonsh_entries(&path, Some(&cp))?;
+        assert_eq!(commands(&entries), ["fd x"]);
+        assert_eq!(cp.offset, 2);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_elvish_cmds() {
+        let output =
+            "{\"seq\":1,\"text\":\"git status\"}\n{\"seq\":2,\"text\":\"rg foo\"}\ngarbage\n";
+        assert_eq!(
+            parse_elvish_cmds(output),
+            vec![(1, "git status".to_string()), (2, "rg foo".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_read_nushell_db_entries() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("history.sqlite3");
+        let conn = rusqlite::Connection::open(&path)?;
+        conn.execute_batch(
+            "CREATE TABLE history (id INTEGER PRIMARY KEY, command_line TEXT NOT NULL,
+                start_timestamp INTEGER, session_id INTEGER, hostname TEXT, cwd TEXT,
+                duration_ms INTEGER, exit_status INTEGER, more_info TEXT);
+             INSERT INTO history (command_line, start_timestamp) VALUES ('git status', 1704067200123);",
+        )?;
+
+        let (entries, cp) = read_nushell_db_entries(&path, None)?;
+        assert_eq!(commands(&entries), ["git status"]);
+        assert_eq!(entries[0].timestamp, Some(1704067200));
+
+        conn.execute(
+            "INSERT INTO history (command_line, start_timestamp) VALUES ('rg foo', 1704067300000)",
+            [],
+        )?;
+        let (entries, _) = read_nushell_db_entries(&path, Some(&cp))?;
+        assert_eq!(commands(&entries), ["rg foo"]);
+
+        Ok(())
+    }
+
+    // ==================== Incremental Scanning Tests ====================
+
+    fn commands(entries: &[HistoryEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.command.as_str()).collect()
+    }
+
+    #[test]
+    fn test_new_entries_appended() {
+        let shell = HistoryShell::Bash;
+        let (entries, cp) = new_entries_since(shell, b"git status\nrg foo\n", 1, None);
+        assert_eq!(commands(&entries), ["git status", "rg foo"]);
+        assert_eq!(cp.offset, 18);
+
+        // Nothing new: nothing counted
+        let (entries, cp) = new_entries_since(shell, b"git status\nrg foo\n", 1, Some(&cp));
+        assert!(entries.is_empty());
+
+        // Appended lines only; the unterminated line waits for the next scan
+        let (entries, cp) =
+            new_entries_since(shell, b"git status\nrg foo\nfd bar\ncargo b", 1, Some(&cp));
+        assert_eq!(commands(&entries), ["fd bar"]);
+        assert_eq!(cp.offset, 25);
+    }
+
+    #[test]
+    fn test_new_entries_rewritten_file() {
+        let shell = HistoryShell::Bash;
+        let history: String = (0..100).map(|i| format!("git commit -m {}\n", i)).collect();
+        let (_, cp) = new_entries_since(shell, history.as_bytes(), 1, None);
+
+        // HISTFILESIZE trimming drops the oldest lines and rewrites the file
+        let trimmed: String = history
+            .lines()
+            .skip(10)
+            .map(|l| format!("{}\n", l))
+            .collect();
+        let rewritten = format!("{}fd\n", trimmed);
+        let (entries, _) = new_entries_since(shell, rewritten.as_bytes(), 2, Some(&cp));
+        assert_eq!(commands(&entries), ["fd"]);
+    }
+
+    #[test]
+    fn test_new_entries_truncated_file() {
+        let shell = HistoryShell::Bash;
+        let (_, cp) = new_entries_since(shell, b"git status\nrg foo\n", 1, None);
+
+        let (entries, _) = new_entries_since(shell, b"fd\n", 1, Some(&cp));
+        assert_eq!(commands(&entries), ["fd"]);
+    }
+
+    #[test]
+    fn test_new_entries_timestamp_fallback() {
+        let shell = HistoryShell::Zsh;
+        let (_, cp) = new_entries_since(shell, b": 100:0;git status\n: 200:0;rg foo\n", 1, None);
+        assert_eq!(cp.last_timestamp, Some(200));
+
+        // Reordered file where our tail no longer appears: fall back to timestamps
+        let (entries, cp) = new_entries_since(
+            shell,
+            b": 200:0;rg  foo\n: 150:0;fd\n: 300:0;cargo build\n",
+            2,
+            Some(&cp),
+        );
+        assert_eq!(commands(&entries), ["cargo build"]);
+        assert_eq!(cp.last_timestamp, Some(300));
+    }
+
+    #[test]
+    fn test_new_entries_rewritten_without_timestamps_skips() {
+        let shell = HistoryShell::Bash;
+        let (_, cp) = new_entries_since(shell, b"git status\nrg foo\n", 1, None);
+
+        // Unrecognisable rewrite with no timestamps: better to miss than to double count
+        let (entries, _) = new_entries_since(shell, b"one\ntwo\nthree\nfour\n", 2, Some(&cp));
+        assert!(entries.is_empty());
+    }
+
+    #[test]
+    fn test_read_new_entries_from_file() -> Result<()> {
+        let mut file = NamedTempFile::new()?;
+        writeln!(file, "- cmd: git status")?;
+        writeln!(file, "  when: 1704067200")?;
+        file.flush()?;
+
+        let (entries, cp) = read_new_entries(HistoryShell::Fish, file.path(), None)?;
+        assert_eq!(entries.len(), 1);
+
+        writeln!(file, "- cmd: cargo build")?;
+        writeln!(file, "  when: 1704067300")?;
+        file.flush()?;
+
+        let (entries, cp) = read_new_entries(HistoryShell::Fish, file.path(), Some(&cp))?;
+        assert_eq!(commands(&entries), ["cargo build"]);
+        assert_eq!(cp.last_timestamp, Some(1704067300));
+
+        Ok(())
+    }
+
+    // ==================== History Database Tests ====================
+
+    #[test]
+    fn test_read_atuin_entries() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("history.db");
+        let conn = rusqlite::Connection::open(&path)?;
+        conn.execute_batch(
+            "CREATE TABLE history (id TEXT PRIMARY KEY, timestamp INTEGER NOT NULL,
+                duration INTEGER, exit INTEGER, command TEXT, cwd TEXT, session TEXT,
+                hostname TEXT, deleted_at INTEGER);",
+        )?;
+        let host = "laptop:me";
+        let insert = |id: &str, ts: i64, cmd: &str, host: &str, deleted: Option<i64>| {
+            conn.execute(
+                "INSERT INTO history (id, timestamp, command, hostname, deleted_at, exit, duration)
+                 VALUES (?1, ?2, ?3, ?4, ?5, -1, -1)",
+                rusqlite::params![id, ts * 1_000_000_000, cmd, host, deleted],
+            )
+        };
+        insert("a", 1704067200, "git status", host, None)?;
+        insert("b", 1704067300, "rg foo", host, Some(1))?;
+        insert(
+            "c",
+            1704067400,
+            "cargo build",
+            "some-other-machine:me",
+            None,
+        )?;
+
+        conn.execute(
+            "UPDATE history SET cwd = '/home/me/src', exit = 1, duration = 2500000000 WHERE id = 'a'",
+            [],
+        )?;
+
+        let (entries, cp) = read_atuin_entries(&path, None, Some("laptop"))?;
+        // Deleted entries and other hosts' entries never count
+        assert_eq!(commands(&entries), ["git status"]);
+        assert_eq!(entries[0].timestamp, Some(1704067200));
+        assert_eq!(entries[0].cwd.as_deref(), Some("/home/me/src"));
+        assert_eq!(entries[0].status, Some(1));
+        assert_eq!(entries[0].duration_ms, Some(2500));
+        assert_eq!(cp.offset, 3);
+
+        // Without a known hostname, every host's entries count
+        let (entries, _) = read_atuin_entries(&path, None, None)?;
+        assert_eq!(commands(&entries), ["git status", "cargo build"]);
+        assert_eq!((entries[1].status, entries[1].duration_ms), (None, None));
+
+        insert("d", 1704067500, "fd bar", host, None)?;
+        let (entries, _) = read_atuin_entries(&path, Some(&cp), Some("laptop"))?;
+        assert_eq!(commands(&entries), ["fd bar"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_mcfly_entries() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("history.db");
+        let conn = rusqlite::Connection::open(&path)?;
+        conn.execute_batch(
+            "CREATE TABLE commands (id INTEGER PRIMARY KEY AUTOINCREMENT, cmd TEXT NOT NULL,
+                cmd_tpl TEXT, session_id TEXT, when_run INTEGER NOT NULL, exit_code INTEGER,
+                selected INTEGER, dir TEXT, old_dir TEXT);
+             INSERT INTO commands (cmd, when_run) VALUES ('git status', 1704067200);
+             INSERT INTO commands (cmd, when_run) VALUES ('rg foo', 1704067300);",
+        )?;
+
+        let (entries, cp) = read_mcfly_entries(&path, None)?;
+        assert_eq!(commands(&entries), ["git status", "rg foo"]);
+        assert_eq!(cp.last_timestamp, Some(1704067300));
+
+        conn.execute(
+            "INSERT INTO commands (cmd, when_run) VALUES ('fd', 1704067400)",
+            [],
+        )?;
+        let (entries, cp) = read_mcfly_entries(&path, Some(&cp))?;
+        assert_eq!(commands(&entries), ["fd"]);
+
+        // Database recreated: only entries newer than what we've seen count
+        conn.execute_batch(
+            "DELETE FROM commands; DELETE FROM sqlite_sequence;
+             INSERT INTO commands (cmd, when_run) VALUES ('fd', 1704067400);
+             INSERT INTO commands (cmd, when_run) VALUES ('bat x', 1704067500);",
+        )?;
+        let (entries, _) = read_mcfly_entries(&path, Some(&cp))?;
+        assert_eq!(commands(&entries), ["bat x"]);
+
+        Ok(())
+    }
+
     // ==================== Integration Tests ====================
 
     #[test]
@@ -21,3 +21,56 @@ pub static HTTP_AGENT: LazyLock<ureq::Agent> = LazyLock::new(|| {
 pub fn agent() -> &'static ureq::Agent {
     &HTTP_AGENT
 }
+
+/// Serve one canned response per connection, returning the server URL and the requests it saw
+///
+/// Requests are recorded with their head lowercased, followed by the body as sent.
+#[cfg(test)]
+pub(crate) fn mock_server<S: Into<String> + Send + 'static>(
+    responses: Vec<S>,
+) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for response in responses {
+            let response: String = response.into();
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = String::new();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                request.push_str(&line.to_lowercase());
+            }
+            let length = request
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length:"))
+                .and_then(|l| l.trim().parse().ok())
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            request.push('\n');
+            request.push_str(&String::from_utf8_lossy(&body));
+            requests.push(request);
+
+            let (head, body) = response.split_once("\n\n").unwrap_or((&response, ""));
+            let head = head.replace('\n', "\r\n");
+            write!(
+                stream,
+                "{}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                head,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+        requests
+    });
+    (url, handle)
+}
@@ -1,15 +1,21 @@
 pub mod ai;
+pub mod aliases;
 pub mod cli;
+pub mod cmdline;
 pub mod commands;
 pub mod config;
 pub mod db;
+pub mod disk;
+pub mod forge;
 pub mod github;
 pub mod history;
 pub mod http;
 pub mod icons;
 pub mod models;
+pub mod release_notes;
 pub mod scanner;
 pub mod sources;
+pub mod spool;
 pub mod tui;
 pub mod updates;
 
@@ -28,7 +34,7 @@ pub use commands::{cmd_fetch_descriptions, cmd_scan, cmd_sync_status};
 pub use commands::{cmd_similar, cmd_suggest, cmd_trending};
 
 // Insights commands
-pub use commands::{cmd_categories, cmd_info, cmd_overview, cmd_stats};
+pub use commands::{cmd_categories, cmd_info, cmd_insights_disk, cmd_overview, cmd_stats};
 
 // Workflow commands
 pub use commands::{cmd_cleanup, cmd_init, cmd_maintain};
@@ -63,13 +69,16 @@ pub use commands::{
 
 // GitHub commands
 pub use commands::{
-    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_info, cmd_gh_rate_limit, cmd_gh_search, cmd_gh_sync,
+    cmd_gh_backfill, cmd_gh_fetch, cmd_gh_import_stars, cmd_gh_info, cmd_gh_link,
+    cmd_gh_rate_limit, cmd_gh_search, cmd_gh_sync, cmd_gh_unlink,
 };
 
 // Usage commands
 pub use commands::{
-    cmd_labels, cmd_recommend, cmd_unused, cmd_usage_config, cmd_usage_init, cmd_usage_log,
-    cmd_usage_reset, cmd_usage_scan, cmd_usage_show, cmd_usage_tool, ensure_usage_configured,
+    cmd_insights_project, cmd_labels, cmd_recommend, cmd_unused, cmd_usage_by_project,
+    cmd_usage_config, cmd_usage_export, cmd_usage_hosts, cmd_usage_import, cmd_usage_init,
+    cmd_usage_log, cmd_usage_purge, cmd_usage_reset, cmd_usage_scan, cmd_usage_show,
+    cmd_usage_subcommands, cmd_usage_tool, ensure_usage_configured,
 };
 
 // Misc commands
@@ -88,7 +97,10 @@ pub use commands::{cmd_completions_install, cmd_completions_status, cmd_completi
 pub use config::{AiProvider, HoardConfig};
 
 // Database
-pub use db::{CachedExtraction, Database, GitHubInfo, GitHubInfoInput, ToolUsage};
+pub use db::{
+    CachedExtraction, Database, GitHubInfo, GitHubInfoInput, ProjectToolUsage, SubcommandUsage,
+    ToolUsage,
+};
 
 // Models
 pub use models::{Bundle, Config, InstallSource, Interest, Tool};
@@ -99,4 +111,4 @@ pub use scanner::{
 };
 
 // Sources
-pub use sources::{PackageSource, all_sources, get_source, source_for};
+pub use sources::{CacheUsage, PackageSource, all_sources, get_source, source_for};
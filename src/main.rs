@@ -70,14 +70,19 @@ use hoards::{
     // GitHub commands
     cmd_gh_backfill,
     cmd_gh_fetch,
+    cmd_gh_import_stars,
     cmd_gh_info,
+    cmd_gh_link,
     cmd_gh_rate_limit,
     cmd_gh_search,
     cmd_gh_sync,
+    cmd_gh_unlink,
     cmd_import,
     // Insights commands
     cmd_info,
     cmd_init,
+    cmd_insights_disk,
+    cmd_insights_project,
     // Install commands
     cmd_install,
     // Usage commands
@@ -100,20 +105,43 @@ use hoards::{
     // Updates commands
     cmd_updates,
     cmd_upgrade,
+    cmd_usage_by_project,
     cmd_usage_config,
+    cmd_usage_export,
+    cmd_usage_hosts,
+    cmd_usage_import,
     cmd_usage_init,
     cmd_usage_log,
+    cmd_usage_purge,
     cmd_usage_reset,
     cmd_usage_scan,
     cmd_usage_show,
+    cmd_usage_subcommands,
     cmd_usage_tool,
     ensure_usage_configured,
 };
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+
+    // The shell hook runs before every command: spool it without opening the database
+    if let Commands::Usage(UsageCommands::Log {
+        command,
+        cwd,
+        status,
+        duration_ms,
+    }) = &cli.command
+    {
+        return cmd_usage_log(command, cwd.as_deref(), *status, *duration_ms);
+    }
+
     let db = Database::open()?;
 
+    // Fold in usage the shell hook spooled since the last run; never block the command on it
+    if let Err(e) = hoards::spool::ingest(&db) {
+        eprintln!("Warning: failed to ingest logged usage: {e}");
+    }
+
     match cli.command {
         // ============================================
         // CORE COMMANDS
@@ -205,7 +233,7 @@ fn main() -> Result<()> {
                 cmd_search(&db, &query)?;
                 if github {
                     println!();
-                    cmd_gh_search(&query, limit)?;
+                    cmd_gh_search(&db, &query, limit)?;
                 }
                 Ok(())
             }
@@ -214,7 +242,12 @@ fn main() -> Result<()> {
             DiscoverCommands::Missing { category } => cmd_suggest(category),
             DiscoverCommands::Recommended { count } => cmd_recommend(&db, count),
             DiscoverCommands::Similar { tool } => cmd_similar(&db, &tool),
-            DiscoverCommands::Trending { category, limit } => cmd_trending(&db, category, limit),
+            DiscoverCommands::Trending {
+                category,
+                limit,
+                by,
+                days,
+            } => cmd_trending(&db, category, limit, &by, days),
             _ => unreachable!("all DiscoverCommands variants covered"),
         },
 
@@ -222,17 +255,31 @@ fn main() -> Result<()> {
         // INSIGHTS COMMANDS
         // ============================================
         Commands::Insights(command) => match command {
-            InsightsCommands::Usage { tool, limit } => {
-                if let Some(name) = tool {
-                    cmd_usage_tool(&db, &name)
+            InsightsCommands::Usage {
+                tool,
+                limit,
+                subcommands,
+                by_project,
+                all_hosts,
+            } => {
+                if by_project {
+                    cmd_usage_by_project(&db, limit)
+                } else if let Some(name) = tool {
+                    if subcommands {
+                        cmd_usage_subcommands(&db, &name, limit)
+                    } else {
+                        cmd_usage_tool(&db, &name, all_hosts)
+                    }
                 } else {
-                    cmd_usage_show(&db, limit)
+                    cmd_usage_show(&db, limit, all_hosts)
                 }
             }
-            InsightsCommands::Unused => cmd_unused(&db),
+            InsightsCommands::Project { path } => cmd_insights_project(&db, &path),
+            InsightsCommands::Unused { all_hosts } => cmd_unused(&db, all_hosts),
             InsightsCommands::Health { fix } => cmd_doctor(&db, fix),
             InsightsCommands::Stats => cmd_stats(&db),
             InsightsCommands::Overview => cmd_overview(&db),
+            InsightsCommands::Disk { limit, unused } => cmd_insights_disk(&db, limit, unused),
             _ => unreachable!("all InsightsCommands variants covered"),
         },
 
@@ -244,14 +291,19 @@ fn main() -> Result<()> {
             cross,
             tracked,
             all_versions,
-        } => cmd_updates(&db, source, cross, tracked, all_versions),
+            notes,
+        } => cmd_updates(&db, source, cross, tracked, all_versions, notes),
 
         // ============================================
         // WORKFLOW COMMANDS
         // ============================================
         Commands::Init { auto } => cmd_init(&db, auto),
         Commands::Maintain { auto, dry_run } => cmd_maintain(&db, auto, dry_run),
-        Commands::Cleanup { force, dry_run } => cmd_cleanup(&db, force, dry_run),
+        Commands::Cleanup {
+            force,
+            purge_caches,
+            dry_run,
+        } => cmd_cleanup(&db, force, purge_caches, dry_run),
 
         // ============================================
         // TUI
@@ -290,10 +342,15 @@ fn main() -> Result<()> {
                 limit,
                 delay,
             } => cmd_gh_sync(&db, dry_run, limit, delay),
-            GhCommands::RateLimit => cmd_gh_rate_limit(),
+            GhCommands::RateLimit => cmd_gh_rate_limit(&db),
             GhCommands::Backfill { dry_run } => cmd_gh_backfill(&db, dry_run),
             GhCommands::Fetch { name } => cmd_gh_fetch(&db, &name),
-            GhCommands::Search { query, limit } => cmd_gh_search(&query, limit),
+            GhCommands::Link { name, repo } => cmd_gh_link(&db, &name, &repo),
+            GhCommands::Unlink { name } => cmd_gh_unlink(&db, &name),
+            GhCommands::ImportStars { user, dry_run } => {
+                cmd_gh_import_stars(&db, user.as_deref(), dry_run)
+            }
+            GhCommands::Search { query, limit } => cmd_gh_search(&db, &query, limit),
             GhCommands::Info { name } => cmd_gh_info(&db, &name),
             _ => unreachable!("all GhCommands variants covered"),
         },
@@ -303,7 +360,12 @@ fn main() -> Result<()> {
         // ============================================
         Commands::Ai(command) => match command {
             AiCommands::Config(config_cmd) => match config_cmd {
-                AiConfigCommands::Set { provider } => cmd_ai_set(&provider),
+                AiConfigCommands::Set {
+                    provider,
+                    model,
+                    base_url,
+                    api_key_env,
+                } => cmd_ai_set(&provider, model, base_url, api_key_env),
                 AiConfigCommands::Show => cmd_ai_show(),
                 AiConfigCommands::Test => cmd_ai_test(),
                 _ => unreachable!("all AiConfigCommands variants covered"),
@@ -365,7 +427,7 @@ fn main() -> Result<()> {
                 no_ai,
             } => cmd_ai_migrate(&db, from, to, dry_run, json, no_ai),
             // Hidden backward compatibility aliases
-            AiCommands::Set { provider } => cmd_ai_set(&provider),
+            AiCommands::Set { provider } => cmd_ai_set(&provider, None, None, None),
             AiCommands::ShowConfig => cmd_ai_show(),
             AiCommands::Test => cmd_ai_test(),
             AiCommands::Categorize { dry_run } => cmd_ai_categorize(dry_run),
@@ -397,18 +459,39 @@ fn main() -> Result<()> {
         // ============================================
         Commands::Usage(command) => match command {
             UsageCommands::Scan { dry_run, reset } => cmd_usage_scan(&db, dry_run, reset),
-            UsageCommands::Show { limit } => cmd_usage_show(&db, limit),
-            UsageCommands::Tool { name } => cmd_usage_tool(&db, &name),
-            UsageCommands::Log { command } => cmd_usage_log(&db, &command),
+            UsageCommands::Show { limit } => cmd_usage_show(&db, limit, false),
+            UsageCommands::Tool { name } => cmd_usage_tool(&db, &name, false),
+            UsageCommands::Log { .. } => unreachable!("handled before opening the database"),
             UsageCommands::Init { shell } => {
                 let config = HoardConfig::load()?;
                 cmd_usage_init(&config, shell)
             }
-            UsageCommands::Config { mode } => {
+            UsageCommands::Config {
+                mode,
+                history,
+                subcommands,
+                ignore_commands,
+                ignore_dirs,
+            } => {
                 let mut config = HoardConfig::load()?;
-                cmd_usage_config(&mut config, mode)
+                cmd_usage_config(
+                    &mut config,
+                    mode,
+                    history,
+                    subcommands,
+                    ignore_commands,
+                    ignore_dirs,
+                )
             }
             UsageCommands::Reset { force } => cmd_usage_reset(&db, force),
+            UsageCommands::Purge { tool, since, force } => {
+                cmd_usage_purge(&db, tool.as_deref(), since.as_deref(), force)
+            }
+            UsageCommands::Export { output, host } => {
+                cmd_usage_export(&db, output.as_deref(), host.as_deref())
+            }
+            UsageCommands::Import { file, force } => cmd_usage_import(&db, &file, force),
+            UsageCommands::Hosts { remove } => cmd_usage_hosts(&db, remove.as_deref()),
             _ => unreachable!("all UsageCommands variants covered"),
         },
 
@@ -488,7 +571,7 @@ fn main() -> Result<()> {
         Commands::Info => cmd_info(),
         Commands::Categories => cmd_categories(&db),
         Commands::Labels => cmd_labels(&db),
-        Commands::Unused => cmd_unused(&db),
+        Commands::Unused => cmd_unused(&db, false),
         Commands::Recommend { count } => cmd_recommend(&db, count),
         Commands::Doctor { fix } => cmd_doctor(&db, fix),
 
@@ -0,0 +1,314 @@
+//! Release notes for pending updates
+//!
+//! Notes come from the releases of the tool's repository (the one recorded by
+//! sync, or else the one its package registry lists) between the installed and
+//! the latest version. A changelog link from the registry, or the repository's
+//! releases page, is kept alongside so there's somewhere to read more.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::db::Database;
+use crate::forge::{self, Release, RepoRef};
+use crate::http::HTTP_AGENT;
+use crate::updates::{Update, is_stable_version, version_is_newer};
+
+/// `project_urls` labels of a PyPI project that point to its changelog
+const CHANGELOG_LABELS: &[&str] = &["changelog", "changes", "release", "history", "news"];
+
+/// Releases between the installed and latest version of a tool
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseNotes {
+    /// Releases newer than the installed version up to the latest, newest first
+    pub releases: Vec<Release>,
+    /// Changelog or releases page
+    pub link: Option<String>,
+}
+
+impl ReleaseNotes {
+    pub fn is_empty(&self) -> bool {
+        self.releases.is_empty() && self.link.is_none()
+    }
+}
+
+/// Where a package registry says a package lives
+#[derive(Debug, Default, PartialEq)]
+struct PackageLinks {
+    /// Repository, homepage and other project URLs, most specific first
+    urls: Vec<String>,
+    changelog: Option<String>,
+}
+
+/// Release notes for an update, cached per version range
+pub fn release_notes(db: &Database, update: &Update) -> Result<ReleaseNotes> {
+    if let Some(cached) = db.get_release_notes(&update.name, &update.current, &update.latest)?
+        && let Ok(notes) = serde_json::from_str(&cached)
+    {
+        return Ok(notes);
+    }
+
+    let (notes, complete) = fetch_release_notes(db, update)?;
+    // Nothing found isn't cached, the repository may be linked later, and
+    // neither is a link standing in for releases that failed to load
+    if complete && !notes.is_empty() {
+        db.cache_release_notes(
+            &update.name,
+            &update.current,
+            &update.latest,
+            &serde_json::to_string(&notes)?,
+        )?;
+    }
+    Ok(notes)
+}
+
+/// Release notes, and whether the releases could be read
+fn fetch_release_notes(db: &Database, update: &Update) -> Result<(ReleaseNotes, bool)> {
+    // The registry is only asked when sync hasn't recorded the repository. Any
+    // package author can put any URL there, so that repository is read anonymously
+    let (repo, changelog, trusted) = match db.get_github_info(&update.name)? {
+        Some(info) => (Some(info.repo_ref()), None, true),
+        None => {
+            let links = package_links(&update.name, &update.source);
+            let hosts = forge::configured_hosts();
+            let repo = links
+                .urls
+                .iter()
+                .find_map(|url| RepoRef::parse(&normalize_repo_url(url), &hosts).ok());
+            (repo, links.changelog, false)
+        }
+    };
+
+    let mut notes = ReleaseNotes {
+        releases: Vec::new(),
+        link: changelog,
+    };
+    let mut complete = true;
+    if let Some(repo) = repo {
+        // Without the releases, the changelog or releases page is still worth showing
+        let client = if trusted {
+            forge::client(repo.forge, &repo.host, db)
+        } else {
+            forge::anonymous_client(repo.forge, &repo.host, db)
+        };
+        match client.releases(&repo.owner, &repo.repo) {
+            Ok(releases) => {
+                notes.releases =
+                    releases_between(releases, &update.name, &update.current, &update.latest)
+            }
+            Err(_) => complete = false,
+        }
+        notes.link = notes.link.or_else(|| Some(repo.releases_url()));
+    }
+    Ok((notes, complete))
+}
+
+/// Releases after `current` up to and including `latest`, newest first
+///
+/// Versions are read from tags (`1.2.0`, `v1.2.0`, `tool-v1.2.0`, `tool@1.2.0`).
+/// Repositories releasing several packages prefix their tags with the package
+/// name, so when any tag carries `name` only those count. Prereleases are
+/// skipped unless the update is to one.
+pub fn releases_between(
+    releases: Vec<Release>,
+    name: &str,
+    current: &str,
+    latest: &str,
+) -> Vec<Release> {
+    let scoped = releases
+        .iter()
+        .any(|r| strip_package_prefix(&r.tag_name, name).is_some());
+
+    let mut between: Vec<(String, Release)> = releases
+        .into_iter()
+        .filter_map(|release| {
+            let tag = match strip_package_prefix(&release.tag_name, name) {
+                Some(rest) => rest,
+                None if scoped => return None,
+                None => &release.tag_name,
+            };
+            let version = tag_version(tag)?.to_string();
+            let is_latest = version == latest;
+            if !is_latest && (release.prerelease || !is_stable_version(&version)) {
+                return None;
+            }
+            (version_is_newer(&version, current) && !version_is_newer(&version, latest))
+                .then_some((version, release))
+        })
+        .collect();
+
+    between.sort_by(|(a, _), (b, _)| {
+        if version_is_newer(a, b) {
+            Ordering::Less
+        } else if version_is_newer(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    });
+    between.into_iter().map(|(_, release)| release).collect()
+}
+
+/// Rest of a tag after the package name and its separator (`tool-`, `tool@`, `tool/`)
+fn strip_package_prefix<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let rest = tag.get(name.len()..)?;
+    if !tag[..name.len()].eq_ignore_ascii_case(name) {
+        return None;
+    }
+    rest.strip_prefix(['-', '_', '@', '/'])
+}
+
+/// Version in a tag, from its first digit (`v1.2.0` is `1.2.0`)
+fn tag_version(tag: &str) -> Option<&str> {
+    tag.find(|c: char| c.is_ascii_digit()).map(|i| &tag[i..])
+}
+
+/// Turn npm-style repository URLs (`git+https://`, `git://`) into web URLs
+fn normalize_repo_url(url: &str) -> String {
+    let url = url.trim().trim_start_matches("git+");
+    match url.strip_prefix("git://") {
+        Some(rest) => format!("https://{}", rest),
+        None => url.to_string(),
+    }
+}
+
+/// Project URLs and changelog a package's registry lists
+fn package_links(package: &str, source: &str) -> PackageLinks {
+    let get = |url: String| -> Option<serde_json::Value> {
+        let mut response = HTTP_AGENT.get(&url).call().ok()?;
+        response.body_mut().read_json().ok()
+    };
+    let strings = |values: &[&serde_json::Value]| -> Vec<String> {
+        values
+            .iter()
+            .filter_map(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+            .collect()
+    };
+
+    let links = match source {
+        "cargo" => {
+            get(format!("https://crates.io/api/v1/crates/{}", package)).map(|json| PackageLinks {
+                urls: strings(&[&json["crate"]["repository"], &json["crate"]["homepage"]]),
+                changelog: None,
+            })
+        }
+        "npm" => {
+            get(format!("https://registry.npmjs.org/{}/latest", package)).map(|json| PackageLinks {
+                urls: strings(&[
+                    &json["repository"]["url"],
+                    &json["repository"],
+                    &json["homepage"],
+                ]),
+                changelog: None,
+            })
+        }
+        "pip" => get(format!("https://pypi.org/pypi/{}/json", package))
+            .map(|json| pypi_links(&json["info"])),
+        _ => None,
+    };
+    links.unwrap_or_default()
+}
+
+/// Links among a PyPI project's `project_urls` and homepage
+fn pypi_links(info: &serde_json::Value) -> PackageLinks {
+    let mut links = PackageLinks::default();
+    if let Some(project_urls) = info["project_urls"].as_object() {
+        for (label, url) in project_urls {
+            let Some(url) = url.as_str() else { continue };
+            let label = label.to_lowercase();
+            if links.changelog.is_none() && CHANGELOG_LABELS.iter().any(|l| label.contains(l)) {
+                links.changelog = Some(url.to_string());
+            }
+            links.urls.push(url.to_string());
+        }
+    }
+    if let Some(home_page) = info["home_page"].as_str().filter(|s| !s.is_empty()) {
+        links.urls.push(home_page.to_string());
+    }
+    links
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            name: None,
+            body: None,
+            html_url: None,
+            published_at: None,
+            prerelease,
+        }
+    }
+
+    fn tags(releases: &[Release]) -> Vec<&str> {
+        releases.iter().map(|r| r.tag_name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_releases_between() {
+        let releases = vec![
+            release("v2.0.0-rc.1", true),
+            release("v1.3.0", false),
+            release("v1.2.1", false),
+            release("v1.2.0-beta.1", false),
+            release("v1.2.0", false),
+            release("v1.1.0", false),
+        ];
+        let between = releases_between(releases.clone(), "tool", "1.1.0", "1.3.0");
+        assert_eq!(tags(&between), ["v1.3.0", "v1.2.1", "v1.2.0"]);
+
+        // A prerelease counts when it's the update itself
+        let between = releases_between(releases, "tool", "1.3.0", "2.0.0-rc.1");
+        assert_eq!(tags(&between), ["v2.0.0-rc.1"]);
+
+        // Tags of other packages in the same repository are ignored
+        let releases = vec![
+            release("other-v5.0.0", false),
+            release("tool-v0.3.0", false),
+            release("tool-v0.2.0", false),
+            release("0.3.0", false),
+        ];
+        let between = releases_between(releases, "tool", "0.2.0", "0.3.0");
+        assert_eq!(tags(&between), ["tool-v0.3.0"]);
+
+        assert!(releases_between(vec![release("nightly", true)], "tool", "1.0", "2.0").is_empty());
+    }
+
+    #[test]
+    fn test_package_links() {
+        assert_eq!(
+            normalize_repo_url("git+https://github.com/owner/tool.git"),
+            "https://github.com/owner/tool.git"
+        );
+        assert_eq!(
+            normalize_repo_url("git://github.com/owner/tool.git"),
+            "https://github.com/owner/tool.git"
+        );
+
+        let info = serde_json::json!({
+            "home_page": "",
+            "project_urls": {
+                "Documentation": "https://tool.readthedocs.io",
+                "Changelog": "https://tool.readthedocs.io/en/latest/changes.html",
+                "Source": "https://github.com/owner/tool"
+            }
+        });
+        let links = pypi_links(&info);
+        assert_eq!(
+            links.changelog.as_deref(),
+            Some("https://tool.readthedocs.io/en/latest/changes.html")
+        );
+        let hosts = Default::default();
+        let repo = links
+            .urls
+            .iter()
+            .find_map(|url| RepoRef::parse(url, &hosts).ok())
+            .unwrap();
+        assert_eq!(repo.to_string(), "owner/tool");
+    }
+}
@@ -1,6 +1,6 @@
 //! Apt (Debian/Ubuntu) package source
 
-use super::PackageSource;
+use super::{CacheUsage, PackageSource, run_purge};
 use crate::models::{InstallSource, Tool};
 use crate::scanner::{KNOWN_TOOLS, is_installed};
 use anyhow::Result;
@@ -101,6 +101,16 @@ impl AptSource {
             _ => false,
         }
     }
+
+    /// Package names from `apt-get -s autoremove` ("Remv foo [1.0-1]")
+    fn parse_autoremove(output: &str) -> Vec<String> {
+        output
+            .lines()
+            .filter_map(|l| l.strip_prefix("Remv "))
+            .filter_map(|l| l.split_whitespace().next())
+            .map(|s| s.to_string())
+            .collect()
+    }
 }
 
 impl PackageSource for AptSource {
@@ -214,4 +224,59 @@ impl PackageSource for AptSource {
     fn uninstall_command(&self, package: &str) -> String {
         format!("sudo apt remove {}", package)
     }
+
+    fn cache_usage(&self) -> Option<CacheUsage> {
+        let output = Command::new("apt-get")
+            .args(["-s", "autoremove"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let packages = Self::parse_autoremove(&String::from_utf8_lossy(&output.stdout));
+        if packages.is_empty() {
+            return None;
+        }
+
+        // Installed-Size is reported in KiB
+        let sizes = Command::new("dpkg-query")
+            .args(["-W", "-f", "${Installed-Size}\n"])
+            .args(&packages)
+            .output()
+            .ok()?;
+        let kib: u64 = String::from_utf8_lossy(&sizes.stdout)
+            .lines()
+            .filter_map(|l| l.trim().parse::<u64>().ok())
+            .sum();
+
+        Some(CacheUsage {
+            bytes: kib * 1024,
+            detail: format!("{} autoremove candidate(s)", packages.len()),
+            purge_display: "sudo apt-get autoremove".to_string(),
+        })
+    }
+
+    fn purge_cache(&self) -> Result<()> {
+        // No -y: apt lists the packages it is about to remove and asks first
+        run_purge("sudo", &["apt-get", "autoremove"])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_autoremove() {
+        let output = "Reading package lists...\n\
+                      The following packages will be REMOVED:\n  linux-headers-6.1 python3-old\n\
+                      Remv linux-headers-6.1 [6.1.0-1]\n\
+                      Remv python3-old [3.9.2-1]\n";
+        assert_eq!(
+            AptSource::parse_autoremove(output),
+            vec!["linux-headers-6.1", "python3-old"]
+        );
+        assert!(AptSource::parse_autoremove("0 upgraded, 0 newly installed").is_empty());
+    }
 }
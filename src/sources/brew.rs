@@ -1,6 +1,6 @@
 //! Homebrew package source
 
-use super::PackageSource;
+use super::{CacheUsage, PackageSource, run_purge};
 use crate::http::HTTP_AGENT;
 use crate::models::{InstallSource, Tool};
 use crate::scanner::{KNOWN_TOOLS, is_installed};
@@ -93,4 +93,58 @@ impl PackageSource for BrewSource {
             .as_str()
             .map(|s| s.to_string())
     }
+
+    fn cache_usage(&self) -> Option<CacheUsage> {
+        let output = Command::new("brew")
+            .args(["cleanup", "--dry-run", "--prune=all"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let bytes = Self::parse_cleanup_estimate(&stdout)?;
+        let files = stdout
+            .lines()
+            .filter(|l| l.starts_with("Would remove"))
+            .count();
+
+        Some(CacheUsage {
+            bytes,
+            detail: format!("{} stale download(s) and old version(s)", files),
+            purge_display: "brew cleanup --prune=all".to_string(),
+        })
+    }
+
+    fn purge_cache(&self) -> Result<()> {
+        run_purge("brew", &["cleanup", "--prune=all"])
+    }
+}
+
+impl BrewSource {
+    /// Parse "This operation would free approximately 1.2GB of disk space."
+    fn parse_cleanup_estimate(output: &str) -> Option<u64> {
+        output.lines().find_map(|line| {
+            let rest = line.split("would free approximately ").nth(1)?;
+            let size = rest.split(" of disk space").next()?;
+            crate::disk::parse_size(size)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cleanup_estimate() {
+        let output = "Would remove: /Users/me/Library/Caches/Homebrew/jq--1.6.bottle.tar.gz (520.1KB)\n\
+                      ==> This operation would free approximately 1.5MB of disk space.\n";
+        assert_eq!(
+            BrewSource::parse_cleanup_estimate(output),
+            Some((1.5 * 1024.0 * 1024.0) as u64)
+        );
+        assert_eq!(BrewSource::parse_cleanup_estimate("nothing to do"), None);
+    }
 }
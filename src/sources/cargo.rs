@@ -1,10 +1,11 @@
 //! Cargo (Rust) package source
 
-use super::PackageSource;
+use super::{CacheUsage, PackageSource};
 use crate::http::HTTP_AGENT;
 use crate::models::{InstallSource, Tool};
 use crate::scanner::{KNOWN_TOOLS, is_installed};
 use anyhow::Result;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 pub struct CargoSource;
@@ -93,4 +94,93 @@ impl PackageSource for CargoSource {
             .as_str()
             .map(|s| s.to_string())
     }
+
+    fn disk_usage(&self, tool: &Tool) -> Option<u64> {
+        let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+        let binary_bytes = crate::disk::binary_size(binary);
+
+        // Registry sources and .crate archives kept around from the install
+        let registry_bytes: u64 = Self::cargo_home()
+            .map(|home| {
+                Self::registry_entries(&home.join("registry").join("src"), &tool.name)
+                    .chain(Self::registry_entries(
+                        &home.join("registry").join("cache"),
+                        &tool.name,
+                    ))
+                    .map(|p| crate::disk::dir_size(&p))
+                    .sum()
+            })
+            .unwrap_or(0);
+
+        match (binary_bytes, registry_bytes) {
+            (None, 0) => None,
+            (b, r) => Some(b.unwrap_or(0) + r),
+        }
+    }
+
+    fn cache_usage(&self) -> Option<CacheUsage> {
+        let home = Self::cargo_home()?;
+        let dirs: Vec<PathBuf> = Self::cache_dirs(&home)
+            .into_iter()
+            .filter(|d| d.is_dir())
+            .collect();
+        if dirs.is_empty() {
+            return None;
+        }
+
+        Some(CacheUsage {
+            bytes: dirs.iter().map(|d| crate::disk::dir_size(d)).sum(),
+            detail: format!("{}/{{registry,git}}", home.display()),
+            purge_display: "remove registry cache/src and git checkouts".to_string(),
+        })
+    }
+
+    fn purge_cache(&self) -> Result<()> {
+        let Some(home) = Self::cargo_home() else {
+            return Ok(());
+        };
+        // The registry index and git db stay so the next build doesn't refetch everything
+        for dir in Self::cache_dirs(&home) {
+            if dir.is_dir() {
+                std::fs::remove_dir_all(&dir)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CargoSource {
+    /// Cargo home directory (respects CARGO_HOME)
+    fn cargo_home() -> Option<PathBuf> {
+        std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|d| d.join(".cargo")))
+    }
+
+    /// Cache directories that cargo recreates on demand
+    fn cache_dirs(home: &Path) -> [PathBuf; 3] {
+        [
+            home.join("registry").join("cache"),
+            home.join("registry").join("src"),
+            home.join("git").join("checkouts"),
+        ]
+    }
+
+    /// Entries named `<crate>-<version>` (or `.crate` archives) under each registry index dir
+    fn registry_entries(root: &Path, crate_name: &str) -> impl Iterator<Item = PathBuf> {
+        let prefix = format!("{}-", crate_name);
+        std::fs::read_dir(root)
+            .into_iter()
+            .flatten()
+            .filter_map(|e| e.ok())
+            .flat_map(|index| std::fs::read_dir(index.path()).into_iter().flatten())
+            .filter_map(|e| e.ok())
+            .filter(move |e| {
+                e.file_name().to_str().is_some_and(|n| {
+                    n.strip_prefix(&prefix)
+                        .is_some_and(|v| v.starts_with(|c: char| c.is_ascii_digit()))
+                })
+            })
+            .map(|e| e.path())
+    }
 }
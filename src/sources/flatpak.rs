@@ -1,6 +1,6 @@
 //! Flatpak package source
 
-use super::PackageSource;
+use super::{CacheUsage, PackageSource, run_purge};
 use crate::models::{InstallSource, Tool};
 use crate::scanner::is_installed;
 use anyhow::Result;
@@ -25,6 +25,46 @@ impl FlatpakSource {
             _ => "app",
         }
     }
+
+    /// Parse the "Installed:" size line from `flatpak info` output
+    fn parse_installed_size(info: &str) -> Option<u64> {
+        info.lines()
+            .map(|l| l.trim())
+            .find_map(|l| l.strip_prefix("Installed:"))
+            .and_then(crate::disk::parse_size)
+    }
+
+    /// Runtimes no installed app depends on, with their sizes
+    ///
+    /// `app_runtimes` are `id/arch/branch` refs from `flatpak list --app --columns=runtime`,
+    /// `runtimes` are `ref<TAB>size` lines from `flatpak list --runtime --columns=ref,size`.
+    /// Extensions of another installed runtime (locales, GL drivers, codecs) are left out
+    /// since they may be shared; `flatpak uninstall --unused` decides about those.
+    fn unused_runtimes(app_runtimes: &str, runtimes: &str) -> Vec<(String, u64)> {
+        let used: Vec<&str> = app_runtimes.lines().map(|l| l.trim()).collect();
+        let installed: Vec<(&str, u64)> = runtimes
+            .lines()
+            .filter_map(|l| {
+                let (runtime_ref, size) = l.split_once('\t')?;
+                let runtime_ref = runtime_ref.trim();
+                let runtime_ref = runtime_ref.strip_prefix("runtime/").unwrap_or(runtime_ref);
+                Some((runtime_ref, crate::disk::parse_size(size).unwrap_or(0)))
+            })
+            .collect();
+        let id_of = |r: &str| r.split('/').next().unwrap_or(r).to_string();
+
+        installed
+            .iter()
+            .filter(|(r, _)| !used.contains(r))
+            .filter(|(r, _)| {
+                let id = id_of(r);
+                !installed
+                    .iter()
+                    .any(|(other, _)| id.starts_with(&format!("{}.", id_of(other))))
+            })
+            .map(|(r, size)| (r.to_string(), *size))
+            .collect()
+    }
 }
 
 impl PackageSource for FlatpakSource {
@@ -161,6 +201,49 @@ impl PackageSource for FlatpakSource {
 
         None
     }
+
+    fn disk_usage(&self, tool: &Tool) -> Option<u64> {
+        // Binary name holds the full app ID (see scan)
+        let app_id = tool.binary_name.as_deref().unwrap_or(&tool.name);
+        let output = Command::new("flatpak")
+            .args(["info", app_id])
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        Self::parse_installed_size(&stdout)
+    }
+
+    fn cache_usage(&self) -> Option<CacheUsage> {
+        let list = |args: &[&str]| -> Option<String> {
+            let output = Command::new("flatpak").args(args).output().ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+        let apps = list(&["list", "--app", "--columns=runtime"])?;
+        let runtimes = list(&["list", "--runtime", "--columns=ref,size"])?;
+
+        let unused = Self::unused_runtimes(&apps, &runtimes);
+        if unused.is_empty() {
+            return None;
+        }
+
+        Some(CacheUsage {
+            bytes: unused.iter().map(|(_, size)| size).sum(),
+            detail: format!("{} unused runtime(s)", unused.len()),
+            purge_display: "flatpak uninstall --unused -y".to_string(),
+        })
+    }
+
+    fn purge_cache(&self) -> Result<()> {
+        run_purge("flatpak", &["uninstall", "--unused", "-y"])
+    }
 }
 
 #[cfg(test)]
@@ -218,4 +301,37 @@ mod tests {
         assert_eq!(FlatpakSource::remote_to_category("gnome-nightly"), "dev");
         assert_eq!(FlatpakSource::remote_to_category("unknown"), "app");
     }
+
+    #[test]
+    fn test_parse_installed_size() {
+        let info = "Firefox - Fast, Private & Safe Web Browser\n\n          ID: org.mozilla.firefox\n      Origin: flathub\n   Installed: 266.2 MB\n";
+        assert_eq!(
+            FlatpakSource::parse_installed_size(info),
+            Some((266.2 * 1024.0 * 1024.0) as u64)
+        );
+        assert_eq!(FlatpakSource::parse_installed_size("ID: foo"), None);
+    }
+
+    #[test]
+    fn test_unused_runtimes() {
+        let apps = "org.gnome.Platform/x86_64/46\n";
+        let runtimes = "org.gnome.Platform/x86_64/46\t1.0 GB\n\
+                        org.gnome.Platform/x86_64/44\t900.0 MB\n\
+                        org.gnome.Platform.Locale/x86_64/44\t20.0 MB\n\
+                        org.freedesktop.Sdk/x86_64/23.08\t512.0 MB\n";
+        let unused = FlatpakSource::unused_runtimes(apps, runtimes);
+        assert_eq!(
+            unused,
+            vec![
+                (
+                    "org.gnome.Platform/x86_64/44".to_string(),
+                    900 * 1024 * 1024
+                ),
+                (
+                    "org.freedesktop.Sdk/x86_64/23.08".to_string(),
+                    512 * 1024 * 1024
+                ),
+            ]
+        );
+    }
 }
@@ -58,6 +58,47 @@ pub trait PackageSource: Send + Sync {
     fn check_update(&self, _package: &str, _current_version: &str) -> Option<String> {
         None
     }
+
+    /// Bytes on disk attributable to an installed tool
+    ///
+    /// Defaults to the size of the binary on PATH. Sources that install
+    /// into their own directories (venvs, node_modules, app bundles) override this.
+    fn disk_usage(&self, tool: &Tool) -> Option<u64> {
+        let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+        crate::disk::binary_size(binary)
+    }
+
+    /// Reclaimable bytes held in this source's download/build caches
+    ///
+    /// Returns None when the manager isn't present or keeps no cache worth reporting.
+    fn cache_usage(&self) -> Option<CacheUsage> {
+        None
+    }
+
+    /// Purge the caches reported by `cache_usage`
+    fn purge_cache(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Reclaimable cache space reported by a package source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheUsage {
+    /// Bytes that a purge would free
+    pub bytes: u64,
+    /// What the bytes are (a cache path, "3 unused runtimes", ...)
+    pub detail: String,
+    /// Command (or action) run by `purge_cache`, shown before confirming
+    pub purge_display: String,
+}
+
+/// Run a cache purge command with inherited stdio, failing on non-zero exit
+pub(crate) fn run_purge(program: &str, args: &[&str]) -> Result<()> {
+    let status = std::process::Command::new(program).args(args).status()?;
+    if !status.success() {
+        anyhow::bail!("{} {} failed", program, args.join(" "));
+    }
+    Ok(())
 }
 
 /// Get all available package sources
@@ -1,11 +1,24 @@
 //! Npm (Node.js) package source
 
-use super::PackageSource;
+use super::{CacheUsage, PackageSource, run_purge};
 use crate::http::HTTP_AGENT;
 use crate::models::{InstallSource, Tool};
 use crate::scanner::{KNOWN_TOOLS, is_installed};
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Command;
+use std::sync::LazyLock;
+
+/// Global `node_modules` directory, looked up once: `npm root -g` is slow to start
+static GLOBAL_ROOT: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
+    let output = Command::new("npm").args(["root", "-g"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim(),
+    ))
+});
 
 pub struct NpmSource;
 
@@ -102,4 +115,38 @@ impl PackageSource for NpmSource {
             .as_str()
             .map(|s| s.to_string())
     }
+
+    fn disk_usage(&self, tool: &Tool) -> Option<u64> {
+        let package_dir = GLOBAL_ROOT.as_ref()?.join(&tool.name);
+        if !package_dir.is_dir() {
+            return None;
+        }
+
+        Some(crate::disk::dir_size(&package_dir))
+    }
+
+    fn cache_usage(&self) -> Option<CacheUsage> {
+        let output = Command::new("npm")
+            .args(["config", "get", "cache"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        if !dir.is_dir() {
+            return None;
+        }
+
+        Some(CacheUsage {
+            bytes: crate::disk::dir_size(&dir),
+            detail: dir.display().to_string(),
+            purge_display: "npm cache clean --force".to_string(),
+        })
+    }
+
+    fn purge_cache(&self) -> Result<()> {
+        run_purge("npm", &["cache", "clean", "--force"])
+    }
 }
@@ -1,10 +1,11 @@
 //! Pip (Python) package source
 
-use super::PackageSource;
+use super::{CacheUsage, PackageSource, run_purge};
 use crate::http::HTTP_AGENT;
 use crate::models::{InstallSource, Tool};
 use crate::scanner::{KNOWN_TOOLS, is_installed};
 use anyhow::Result;
+use std::path::PathBuf;
 use std::process::Command;
 
 pub struct PipSource;
@@ -103,4 +104,54 @@ impl PackageSource for PipSource {
             .as_str()
             .map(|s| s.to_string())
     }
+
+    fn disk_usage(&self, tool: &Tool) -> Option<u64> {
+        // pipx installs each tool into its own venv - count the whole venv
+        if let Some(venv) = Self::pipx_venv_dir(&tool.name)
+            && venv.is_dir()
+        {
+            return Some(crate::disk::dir_size(&venv));
+        }
+
+        let binary = tool.binary_name.as_deref().unwrap_or(&tool.name);
+        crate::disk::binary_size(binary)
+    }
+
+    fn cache_usage(&self) -> Option<CacheUsage> {
+        let (pip, dir) = Self::cache_dir()?;
+        Some(CacheUsage {
+            bytes: crate::disk::dir_size(&dir),
+            detail: dir.display().to_string(),
+            purge_display: format!("{} cache purge", pip),
+        })
+    }
+
+    fn purge_cache(&self) -> Result<()> {
+        match Self::cache_dir() {
+            Some((pip, _)) => run_purge(pip, &["cache", "purge"]),
+            None => Ok(()),
+        }
+    }
+}
+
+impl PipSource {
+    /// pip's wheel/http cache directory, with the pip binary that reported it
+    fn cache_dir() -> Option<(&'static str, PathBuf)> {
+        ["pip3", "pip"].into_iter().find_map(|pip| {
+            let output = Command::new(pip).args(["cache", "dir"]).output().ok()?;
+            if !output.status.success() {
+                return None;
+            }
+            let dir = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+            dir.is_dir().then_some((pip, dir))
+        })
+    }
+
+    /// Location of a pipx-managed venv (respects PIPX_HOME)
+    fn pipx_venv_dir(package: &str) -> Option<PathBuf> {
+        let home = std::env::var_os("PIPX_HOME")
+            .map(PathBuf::from)
+            .or_else(|| dirs::data_local_dir().map(|d| d.join("pipx")))?;
+        Some(home.join("venvs").join(package))
+    }
 }
@@ -0,0 +1,638 @@
+//! Usage spool for the shell hook
+//!
+//! `hoards usage log` runs after every shell command, so it only appends one
+//! line to a spool file next to the database: no schema setup, and shell
+//! aliases and tool names come from caches checked against the mtimes of the rc
+//! files and the database. The spool is folded into the usage tables lazily, by
+//! the next `hoards` invocation or a TUI refresh.
+//!
+//! Command lines can hold secrets, so they're never spooled: an entry only names
+//! the tools a line ran, and the subcommands of tools opted into tracking them.
+//! Programs that match no tool (a password pasted at the prompt) are dropped.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::aliases::Aliases;
+use crate::cmdline::{invocations_with, looks_like_subcommand};
+use crate::config::{HoardConfig, UsageConfig};
+use crate::db::Database;
+
+const SPOOL_FILE: &str = "usage.spool";
+
+/// Parsed shell aliases, so the hook doesn't reread every rc file
+const ALIAS_CACHE: &str = "aliases.cache";
+
+/// Names and binaries of tracked tools, so the hook rarely opens the database
+const TOOL_CACHE: &str = "tools.cache";
+
+/// Prefix of spool files taken over by an ingesting process
+const CLAIM_PREFIX: &str = "usage.spool.claimed-";
+
+/// Claims older than this were left behind by an ingestion that didn't finish
+const STALE_CLAIM: Duration = Duration::from_secs(60);
+
+/// One command line logged by the shell hook
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpoolEntry {
+    /// Unix timestamp of when the command ran
+    pub ts: i64,
+    /// Directory the command ran in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    /// Programs the command line ran
+    #[serde(default)]
+    pub commands: Vec<SpoolCommand>,
+    /// The full command line, only found in entries spooled by older versions
+    #[serde(default, skip_serializing)]
+    pub cmd: Option<String>,
+    /// Exit status, when the hook logs after the command finished
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<i32>,
+    /// Wall-clock duration in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
+}
+
+/// A program run by a logged command line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpoolCommand {
+    pub program: String,
+    /// Only kept for tools whose subcommands are tracked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subcommand: Option<String>,
+}
+
+impl SpoolEntry {
+    /// What may be kept of a command line, or `None` if nothing is
+    ///
+    /// The programs it runs after alias expansion, minus ignored commands, with
+    /// subcommands only for opted-in tools. The exit status and duration belong to
+    /// the line as a whole, so they're only kept when it ran a single program.
+    pub fn from_line(
+        line: &str,
+        status: Option<i32>,
+        duration_ms: Option<u64>,
+        config: &UsageConfig,
+        aliases: &Aliases,
+    ) -> Option<Self> {
+        let invocations = invocations_with(line, aliases);
+        let single = invocations.len() == 1;
+
+        let commands: Vec<SpoolCommand> = invocations
+            .into_iter()
+            .filter(|i| !config.ignores_command(i.program))
+            .map(|i| SpoolCommand {
+                program: i.program.to_string(),
+                // The hook runs without the database, so this matches on the
+                // command name; ingestion checks again against the tool name.
+                // Paths, hosts and tokens never reach the spool file.
+                subcommand: i
+                    .subcommand
+                    .filter(|sub| looks_like_subcommand(sub))
+                    .filter(|_| config.tracks_subcommands(i.program, i.program))
+                    .map(str::to_string),
+            })
+            .collect();
+        if commands.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            ts: Utc::now().timestamp(),
+            cwd: None,
+            commands,
+            cmd: None,
+            status: status.filter(|_| single),
+            duration_ms: duration_ms.filter(|_| single),
+        })
+    }
+}
+
+/// Directory holding the spool (next to the database)
+pub fn spool_dir() -> Result<PathBuf> {
+    let db_path = Database::db_path()?;
+    db_path
+        .parent()
+        .map(Path::to_path_buf)
+        .context("Database path has no parent directory")
+}
+
+/// Aliases for the shell hook, through a cache next to the spool
+pub fn cached_aliases() -> Aliases {
+    spool_dir()
+        .map(|dir| Aliases::load_cached(&dir.join(ALIAS_CACHE)))
+        .unwrap_or_default()
+}
+
+/// Commands that name a tracked tool (tool names and binaries), for the shell hook
+///
+/// `None` when neither the database nor a cached copy can be read.
+pub fn cached_tool_commands() -> Option<HashSet<String>> {
+    let dir = spool_dir().ok()?;
+    tool_commands_cached(&Database::db_path().ok()?, &dir.join(TOOL_CACHE))
+}
+
+#[derive(Serialize, Deserialize)]
+struct ToolCache {
+    stamps: Vec<(PathBuf, Option<SystemTime>)>,
+    commands: HashSet<String>,
+}
+
+/// Tool commands from `cache` while the database files are unchanged, else from the database
+fn tool_commands_cached(db_path: &Path, cache: &Path) -> Option<HashSet<String>> {
+    // Writes land in the WAL first, so its mtime counts too
+    let mut wal = db_path.as_os_str().to_owned();
+    wal.push("-wal");
+    let stamps: Vec<(PathBuf, Option<SystemTime>)> = [db_path.to_path_buf(), PathBuf::from(wal)]
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect();
+
+    let cached = fs::read(cache)
+        .ok()
+        .and_then(|content| serde_json::from_slice::<ToolCache>(&content).ok());
+    if let Some(cached) = &cached
+        && cached.stamps == stamps
+    {
+        return Some(cached.commands.clone());
+    }
+
+    let tools = Database::open_read_only(db_path).and_then(|db| db.get_tool_binaries());
+    let Ok(tools) = tools else {
+        return cached.map(|c| c.commands);
+    };
+    let commands: HashSet<String> = tools
+        .into_iter()
+        .flat_map(|(name, binary)| [name, binary])
+        .collect();
+
+    // Written aside and renamed, so a concurrent hook never reads half a file
+    let fresh = ToolCache { stamps, commands };
+    if let Ok(json) = serde_json::to_vec(&fresh) {
+        let tmp = cache.with_extension(format!("tmp-{}", std::process::id()));
+        if fs::write(&tmp, json).is_ok() && fs::rename(&tmp, cache).is_err() {
+            fs::remove_file(&tmp).ok();
+        }
+    }
+    Some(fresh.commands)
+}
+
+/// Append an entry to the spool
+pub fn append(entry: &SpoolEntry) -> Result<()> {
+    append_to(&spool_dir()?, entry)
+}
+
+fn append_to(dir: &Path, entry: &SpoolEntry) -> Result<()> {
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+
+    let path = dir.join(SPOOL_FILE);
+    let open = || OpenOptions::new().create(true).append(true).open(&path);
+    let mut file = match open() {
+        Ok(file) => file,
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            fs::create_dir_all(dir)?;
+            open()?
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    // A single write to an O_APPEND file, so lines from concurrent shells never interleave
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Fold spooled commands into the usage tables, returning how many were ingested
+pub fn ingest(db: &Database) -> Result<usize> {
+    ingest_from(db, &spool_dir()?, || {
+        let config = HoardConfig::load().map(|c| c.usage).unwrap_or_default();
+        (config, Aliases::load())
+    })
+}
+
+/// `load` reads the usage config and aliases, only when there's something to ingest
+fn ingest_from(
+    db: &Database,
+    dir: &Path,
+    load: impl FnOnce() -> (UsageConfig, Aliases),
+) -> Result<usize> {
+    let claimed = claim(dir)?;
+    if claimed.is_empty() {
+        return Ok(0);
+    }
+
+    let mut entries = Vec::new();
+    for path in &claimed {
+        let content = fs::read(path)?;
+        // Skip torn or malformed lines rather than losing the whole batch
+        entries.extend(
+            String::from_utf8_lossy(&content)
+                .lines()
+                .filter_map(|line| serde_json::from_str::<SpoolEntry>(line).ok()),
+        );
+    }
+
+    let (config, aliases) = load();
+    db.transaction(|db| {
+        let mut recorder = Recorder::new(&config, &aliases);
+        for entry in &entries {
+            recorder.record(db, entry)?;
+        }
+        Ok(())
+    })?;
+
+    // Only drop the claimed files once their entries are committed
+    for path in claimed {
+        fs::remove_file(path).ok();
+    }
+
+    Ok(entries.len())
+}
+
+/// Take over the spool, and any stale claims, by renaming them
+///
+/// Renames are atomic, so two processes ingesting at once never count an
+/// entry twice, and the hook keeps appending to a fresh spool meanwhile.
+fn claim(dir: &Path) -> Result<Vec<PathBuf>> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
+    let claim_name = format!("{}{}-{}", CLAIM_PREFIX, now.as_nanos(), std::process::id());
+
+    // Claims record when they were made in their name (a rename keeps the old
+    // mtime), so an ingestion still in progress is never mistaken for stale
+    let mut candidates = vec![dir.join(SPOOL_FILE)];
+    if let Ok(read_dir) = fs::read_dir(dir) {
+        for entry in read_dir.flatten() {
+            let name = entry.file_name();
+            let claimed_at = name
+                .to_string_lossy()
+                .strip_prefix(CLAIM_PREFIX)
+                .and_then(|rest| rest.split('-').next())
+                .and_then(|nanos| nanos.parse::<u128>().ok());
+            if let Some(claimed_at) = claimed_at
+                && now.as_nanos().saturating_sub(claimed_at) > STALE_CLAIM.as_nanos()
+            {
+                candidates.push(entry.path());
+            }
+        }
+    }
+
+    let mut claimed = Vec::new();
+    for (i, from) in candidates.iter().enumerate() {
+        let to = dir.join(format!("{}-{}", claim_name, i));
+        match fs::rename(from, &to) {
+            Ok(()) => claimed.push(to),
+            // Nothing spooled yet, or another process got there first
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(claimed)
+}
+
+/// Records spooled commands, caching lookups shared across a batch
+struct Recorder<'a> {
+    config: &'a UsageConfig,
+    aliases: &'a Aliases,
+    /// cwd -> (canonical directory, git root)
+    contexts: HashMap<String, (String, Option<String>)>,
+}
+
+impl<'a> Recorder<'a> {
+    fn new(config: &'a UsageConfig, aliases: &'a Aliases) -> Self {
+        Self {
+            config,
+            aliases,
+            contexts: HashMap::new(),
+        }
+    }
+
+    fn record(&mut self, db: &Database, entry: &SpoolEntry) -> Result<()> {
+        let now = Utc::now();
+        let when = DateTime::from_timestamp(entry.ts, 0)
+            .filter(|dt| *dt <= now)
+            .unwrap_or(now);
+        let last_used = when.to_rfc3339();
+        let date = when.format("%Y-%m-%d").to_string();
+
+        // The ignore lists may have changed since the entry was spooled
+        if let Some(cwd) = &entry.cwd
+            && self.config.ignores_directory(Path::new(cwd))
+        {
+            return Ok(());
+        }
+
+        // Entries from older versions carry the whole line: reduce it the same way
+        let legacy;
+        let entry = match &entry.cmd {
+            Some(line) if entry.commands.is_empty() => {
+                let Some(reduced) = SpoolEntry::from_line(
+                    line,
+                    entry.status,
+                    entry.duration_ms,
+                    self.config,
+                    self.aliases,
+                ) else {
+                    return Ok(());
+                };
+                legacy = reduced;
+                &legacy
+            }
+            _ => entry,
+        };
+
+        // Every tool in the line counts (pipelines, `&&` chains, sudo/xargs wrappers,
+        // aliases, ...); an exit status or duration is only spooled for a single one
+        for command in &entry.commands {
+            if self.config.ignores_command(&command.program) {
+                continue;
+            }
+            let Some(tool_name) = db.match_command_to_tool(&command.program)? else {
+                continue;
+            };
+            db.record_usage_on(&tool_name, 1, Some(&last_used), &date)?;
+            db.record_outcome(&tool_name, entry.status, entry.duration_ms, &last_used)?;
+
+            if let Some(cwd) = &entry.cwd {
+                let (directory, git_root) = self.contexts.entry(cwd.clone()).or_insert_with(|| {
+                    let (directory, git_root) = project_context(Path::new(cwd));
+                    (
+                        directory.to_string_lossy().to_string(),
+                        git_root.map(|r| r.to_string_lossy().to_string()),
+                    )
+                });
+                db.record_usage_context(&tool_name, directory, git_root.as_deref(), &last_used)?;
+            }
+
+            if let Some(sub) = &command.subcommand
+                && self.config.tracks_subcommands(&tool_name, &command.program)
+            {
+                db.record_subcommand_usage(&tool_name, sub, 1, Some(&last_used))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Canonical form of a directory, plus the git repository containing it (if any)
+pub fn project_context(dir: &Path) -> (PathBuf, Option<PathBuf>) {
+    let dir = fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf());
+    // `.git` is a directory in regular clones and a file in worktrees/submodules
+    let git_root = dir
+        .ancestors()
+        .find(|ancestor| ancestor.join(".git").exists())
+        .map(Path::to_path_buf);
+    (dir, git_root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Tool;
+
+    fn entry(cmd: &str) -> SpoolEntry {
+        finished(cmd, None, None)
+    }
+
+    fn finished(cmd: &str, status: Option<i32>, duration_ms: Option<u64>) -> SpoolEntry {
+        SpoolEntry {
+            ts: 1704067200,
+            ..SpoolEntry::from_line(
+                cmd,
+                status,
+                duration_ms,
+                &UsageConfig::default(),
+                &Aliases::default(),
+            )
+            .unwrap()
+        }
+    }
+
+    #[test]
+    fn test_append_and_ingest() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let spool_dir = dir.path().join("data");
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+        db.insert_tool(&Tool::new("fzf").installed())?;
+
+        // The directory is created on first append
+        append_to(&spool_dir, &entry("rg foo | fzf"))?;
+        append_to(&spool_dir, &entry("rg bar"))?;
+        append_to(&spool_dir, &entry("unknown-tool"))?;
+
+        assert_eq!(ingest_from(&db, &spool_dir, Default::default)?, 3);
+        assert_eq!(db.get_usage("rg")?.unwrap().use_count, 2);
+        assert_eq!(db.get_usage("fzf")?.unwrap().use_count, 1);
+        assert_eq!(
+            db.get_usage("rg")?.unwrap().last_used.as_deref(),
+            Some("2024-01-01T00:00:00+00:00")
+        );
+
+        // Everything was consumed
+        assert_eq!(ingest_from(&db, &spool_dir, Default::default)?, 0);
+        assert_eq!(fs::read_dir(&spool_dir)?.count(), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_outcomes() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+        db.insert_tool(&Tool::new("fzf").installed())?;
+
+        append_to(dir.path(), &finished("rg foo", Some(0), Some(250)))?;
+        append_to(dir.path(), &finished("sudo rg bar", Some(2), Some(250)))?;
+        // The status of a pipeline is its last command's, so it isn't attributed
+        append_to(dir.path(), &finished("rg foo | fzf", Some(1), Some(250)))?;
+        // Lines logged by an older hook carry neither field
+        append_to(dir.path(), &entry("rg baz"))?;
+
+        assert_eq!(ingest_from(&db, dir.path(), Default::default)?, 4);
+        let rg = db.get_outcome_stats("rg")?.unwrap();
+        assert_eq!((rg.runs, rg.failures), (2, 1));
+        assert!(rg.p50_ms.is_some());
+        assert_eq!(db.get_outcome_stats("fzf")?, None);
+        assert_eq!(db.get_usage("fzf")?.unwrap().use_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_resolves_aliases() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("eza").installed())?;
+
+        let mut aliases = Aliases::default();
+        aliases.insert("ll", "eza -l");
+        let spooled = SpoolEntry::from_line("ll src", None, None, &Default::default(), &aliases);
+        assert_eq!(spooled.unwrap().commands[0].program, "eza");
+
+        // Older versions spooled the whole line, resolved at ingestion
+        fs::write(
+            dir.path().join(SPOOL_FILE),
+            "{\"ts\":1704067200,\"cmd\":\"ll src\"}\n",
+        )?;
+        assert_eq!(
+            ingest_from(&db, dir.path(), || (UsageConfig::default(), aliases))?,
+            1
+        );
+        assert_eq!(db.get_usage("eza")?.unwrap().use_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_spool_entry_redaction() {
+        let config = UsageConfig {
+            subcommands: vec!["git".to_string()],
+            ignore_commands: vec!["pass".to_string(), "vault*".to_string()],
+            ..Default::default()
+        };
+        let spool =
+            |line| SpoolEntry::from_line(line, Some(0), Some(10), &config, &Aliases::default());
+
+        // Only the program names survive, plus subcommands of opted-in tools
+        let entry = spool("git commit -m 'token=hunter2' && curl -H secret https://x").unwrap();
+        let line = serde_json::to_string(&entry).unwrap();
+        assert!(!line.contains("hunter2") && !line.contains("secret"));
+        assert!(line.contains("\"subcommand\":\"commit\""));
+        assert_eq!(entry.status, None);
+        // Arguments of a tracked tool aren't kept as its subcommand
+        for line in [
+            "git ./notes/secret.txt",
+            "git user@host.example",
+            "git 'ghp_123/x'",
+        ] {
+            assert_eq!(
+                spool(line).unwrap().commands[0].subcommand,
+                None,
+                "{}",
+                line
+            );
+        }
+
+        // Ignored commands are dropped, and lines with nothing else aren't spooled
+        assert_eq!(spool("pass show email"), None);
+        let entry = spool("vault-cli login | tee log").unwrap();
+        assert_eq!(entry.commands, spool("tee log").unwrap().commands);
+        assert_eq!(entry.status, None);
+    }
+
+    #[test]
+    fn test_ingest_skips_malformed_lines() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+
+        fs::write(
+            dir.path().join(SPOOL_FILE),
+            "{\"ts\":1704067200,\"cmd\":\"rg a\"}\n{\"ts\":17040\n",
+        )?;
+
+        assert_eq!(ingest_from(&db, dir.path(), Default::default)?, 1);
+        assert_eq!(db.get_usage("rg")?.unwrap().use_count, 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_stale_claims_only() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db = Database::open_in_memory()?;
+        db.insert_tool(&Tool::new("rg").installed())?;
+
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_nanos();
+        let line = "{\"ts\":1704067200,\"cmd\":\"rg a\"}\n";
+        // Left behind by an ingestion that died, and one still in progress elsewhere
+        let stale = dir
+            .path()
+            .join(format!("{}{}-1-0", CLAIM_PREFIX, now - 3_600_000_000_000));
+        let active = dir.path().join(format!("{}{}-2-0", CLAIM_PREFIX, now));
+        fs::write(&stale, line)?;
+        fs::write(&active, line)?;
+
+        assert_eq!(ingest_from(&db, dir.path(), Default::default)?, 1);
+        assert!(!stale.exists());
+        assert!(active.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ingest_missing_dir() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db = Database::open_in_memory()?;
+        assert_eq!(
+            ingest_from(&db, &dir.path().join("missing"), Default::default)?,
+            0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tool_commands_cached() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let db_path = dir.path().join("hoards.db");
+        let cache = dir.path().join(TOOL_CACHE);
+        let db = Database::open_at(&db_path)?;
+        db.insert_tool(&Tool::new("ripgrep").with_binary("rg"))?;
+
+        let commands = tool_commands_cached(&db_path, &cache).unwrap();
+        assert!(commands.contains("ripgrep") && commands.contains("rg"));
+        assert!(!commands.contains("hunter2"));
+        assert!(cache.exists());
+
+        // A new tool changes the database files, so the cache is rebuilt
+        db.insert_tool(&Tool::new("fzf"))?;
+        assert!(
+            tool_commands_cached(&db_path, &cache)
+                .unwrap()
+                .contains("fzf")
+        );
+
+        // An unreadable database falls back to the cached copy
+        let missing = dir.path().join("missing.db");
+        assert!(
+            tool_commands_cached(&missing, &cache)
+                .unwrap()
+                .contains("fzf")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_project_context() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let repo = dir.path().join("repo");
+        fs::create_dir_all(repo.join(".git"))?;
+        fs::create_dir_all(repo.join("src"))?;
+
+        let (directory, git_root) = project_context(&repo.join("src"));
+        assert_eq!(directory, fs::canonicalize(repo.join("src"))?);
+        assert_eq!(git_root, Some(fs::canonicalize(&repo)?));
+
+        let (_, git_root) = project_context(dir.path());
+        assert_eq!(git_root, None);
+
+        Ok(())
+    }
+}
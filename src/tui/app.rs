@@ -1,13 +1,15 @@
 //! Application state for the TUI
 
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 
 use crate::Update;
-use crate::config::{AiProvider, HoardConfig, SourcesConfig, TuiTheme, UsageMode};
-use crate::db::{Database, GitHubInfo, ToolUsage};
+use crate::config::{AiConfig, AiProvider, HoardConfig, SourcesConfig, TuiTheme, UsageMode};
+use crate::db::{Database, GitHubInfo, OutcomeStats, SubcommandUsage, ToolUsage};
 use crate::models::{Bundle, InstallSource, Tool};
+use crate::release_notes::ReleaseNotes;
 
 /// A search result from the Discover tab
 #[derive(Debug, Clone)]
@@ -102,68 +104,55 @@ impl ConfigSection {
     /// Get the starting line number for this section in the config menu.
     /// Used for click detection and auto-scroll.
     ///
-    /// Layout (without custom theme description):
-    /// - Lines 0-5: AI Provider (header + 5 options)
-    /// - Line 6: empty
-    /// - Lines 7-14: Theme (header + 7 options)
-    /// - Line 15: empty
-    /// - Lines 16-23: Sources (header + 7 options)
-    /// - Line 24: empty
-    /// - Lines 25-27: Usage (header + 2 options)
-    /// - Line 28: empty
-    /// - Line 29: Buttons
+    /// Each section is a header, one line per item and an empty line, except
+    /// the buttons which share a single line. Selecting the custom theme adds
+    /// a file path hint after the themes.
     pub fn start_line(&self, custom_theme_selected: bool) -> usize {
-        let theme_extra = if custom_theme_selected { 1 } else { 0 };
+        let theme_extra = usize::from(custom_theme_selected);
+        let after = |section: ConfigSection| {
+            section.start_line(custom_theme_selected) + section.item_count() + 2
+        };
         match self {
             Self::AiProvider => 0,
-            Self::Theme => 7,
-            Self::Sources => 16 + theme_extra,
-            Self::UsageMode => 25 + theme_extra,
-            Self::Buttons => 29 + theme_extra,
+            Self::Theme => after(Self::AiProvider),
+            Self::Sources => after(Self::Theme) + theme_extra,
+            Self::UsageMode => after(Self::Sources),
+            Self::Buttons => after(Self::UsageMode),
         }
     }
 
     /// Get the line range for items in this section (excluding header).
     /// Returns (first_item_line, last_item_line) inclusive.
     pub fn item_lines(&self, custom_theme_selected: bool) -> (usize, usize) {
-        let theme_extra = if custom_theme_selected { 1 } else { 0 };
+        let start = self.start_line(custom_theme_selected);
         match self {
-            Self::AiProvider => (1, 5),                              // 5 AI providers
-            Self::Theme => (8, 14),                                  // 7 themes (indices 0-6)
-            Self::Sources => (17 + theme_extra, 23 + theme_extra),   // 7 sources
-            Self::UsageMode => (26 + theme_extra, 27 + theme_extra), // 2 modes
-            Self::Buttons => (29 + theme_extra, 29 + theme_extra),   // 1 line
+            Self::Buttons => (start, start), // 1 line
+            _ => (start + 1, start + self.item_count()),
         }
     }
 
     /// Number of selectable items in this section
     pub fn item_count(&self) -> usize {
         match self {
-            Self::AiProvider => 5, // None, Claude, Gemini, Codex, Opencode
-            Self::Theme => 7,      // 6 built-in + Custom
-            Self::Sources => 7,    // cargo, apt, pip, npm, brew, flatpak, manual
-            Self::UsageMode => 2,  // Scan, Hook
-            Self::Buttons => 2,    // Save, Cancel
+            Self::AiProvider => AiProvider::all().len(),
+            Self::Theme => 7,     // 6 built-in + Custom
+            Self::Sources => 7,   // cargo, apt, pip, npm, brew, flatpak, manual
+            Self::UsageMode => 2, // Scan, Hook
+            Self::Buttons => 2,   // Save, Cancel
         }
     }
 }
 
 /// Config menu layout constants
 pub mod config_menu_layout {
-    /// Base number of lines in config menu (without custom theme description)
-    pub const TOTAL_LINES_BASE: usize = 30;
-    /// Extra line when custom theme is selected (for file path hint)
-    pub const CUSTOM_THEME_EXTRA_LINES: usize = 1;
+    use super::ConfigSection;
+
     /// Index of custom theme
     pub const CUSTOM_THEME_INDEX: usize = 6;
 
     /// Calculate total lines based on whether custom theme is selected
     pub fn total_lines(custom_theme_selected: bool) -> usize {
-        if custom_theme_selected {
-            TOTAL_LINES_BASE + CUSTOM_THEME_EXTRA_LINES
-        } else {
-            TOTAL_LINES_BASE
-        }
+        ConfigSection::Buttons.start_line(custom_theme_selected) + 1
     }
 }
 
@@ -514,13 +503,20 @@ pub enum InputMode {
 /// Background operation that needs loading indicator
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BackgroundOp {
-    CheckUpdates { step: usize },
+    CheckUpdates {
+        step: usize,
+    },
+    /// Fetch release notes for each available update, one tool per step
+    FetchReleaseNotes {
+        step: usize,
+    },
 }
 
 impl BackgroundOp {
     pub fn title(&self) -> &'static str {
         match self {
             BackgroundOp::CheckUpdates { .. } => "Checking for Updates",
+            BackgroundOp::FetchReleaseNotes { .. } => "Fetching Release Notes",
         }
     }
 }
@@ -667,6 +663,7 @@ pub enum SortBy {
     Name,
     Usage,
     Recent,
+    Size,
 }
 
 impl SortBy {
@@ -674,7 +671,8 @@ impl SortBy {
         match self {
             SortBy::Name => SortBy::Usage,
             SortBy::Usage => SortBy::Recent,
-            SortBy::Recent => SortBy::Name,
+            SortBy::Recent => SortBy::Size,
+            SortBy::Size => SortBy::Name,
         }
     }
 
@@ -683,6 +681,7 @@ impl SortBy {
             SortBy::Name => "name",
             SortBy::Usage => "usage",
             SortBy::Recent => "recent",
+            SortBy::Size => "size",
         }
     }
 }
@@ -701,7 +700,7 @@ pub const COMMANDS: &[(&str, &str)] = &[
     ("s", "sort [field] - cycle or set sort"),
     (
         "sort",
-        "sort [field] - cycle or set sort (name/usage/recent)",
+        "sort [field] - cycle or set sort (name/usage/recent/size)",
     ),
     (
         "filter",
@@ -758,6 +757,14 @@ pub struct CacheManager {
     pub github_cache: HashMap<String, GitHubInfo>,
     /// Labels/tags per tool
     pub labels_cache: HashMap<String, Vec<String>>,
+    /// Last measured disk usage in bytes (from `insights disk`)
+    pub disk_usage: HashMap<String, u64>,
+    /// Subcommand usage per tool, loaded when a tool's details are shown
+    pub subcommand_usage: HashMap<String, Vec<SubcommandUsage>>,
+    /// Success rate and durations per tool (from the shell hook)
+    pub outcomes: HashMap<String, OutcomeStats>,
+    /// Recorded star counts per tool, loaded when a tool's details are shown
+    pub star_history: HashMap<String, Vec<i64>>,
 }
 
 impl CacheManager {
@@ -771,12 +778,22 @@ impl CacheManager {
             .into_iter()
             .collect();
         let labels_cache = db.get_all_tool_labels().unwrap_or_default();
+        let disk_usage = db.get_all_disk_usage().unwrap_or_default();
+        let outcomes = db
+            .get_all_outcome_stats()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
 
         Self {
             usage_data,
             daily_usage,
             github_cache,
             labels_cache,
+            disk_usage,
+            subcommand_usage: HashMap::new(),
+            outcomes,
+            star_history: HashMap::new(),
         }
     }
 
@@ -795,6 +812,18 @@ impl CacheManager {
         self.github_cache.get(tool_name)
     }
 
+    /// Reload usage caches from database
+    pub fn reload_usage(&mut self, db: &Database) {
+        self.usage_data = db.get_all_usage().unwrap_or_default().into_iter().collect();
+        self.daily_usage = db.get_all_daily_usage(7).unwrap_or_default();
+        self.subcommand_usage.clear();
+        self.outcomes = db
+            .get_all_outcome_stats()
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+    }
+
     /// Reload labels cache from database
     pub fn reload_labels(&mut self, db: &Database) {
         self.labels_cache = db.get_all_tool_labels().unwrap_or_default();
@@ -1004,6 +1033,9 @@ pub struct App {
     pub available_updates: HashMap<String, Update>,
     pub updates_checked: bool,
     pub updates_loading: bool,
+    pub release_notes: HashMap<String, ReleaseNotes>,
+    /// Scroll offset of the release notes pane, and the tool it applies to
+    pub release_notes_scroll: (String, u16),
 
     // UI state
     pub show_help: bool,
@@ -1032,7 +1064,7 @@ pub struct App {
 
     // Feature availability status (for footer display)
     pub ai_available: bool, // AI provider is configured
-    pub gh_available: bool, // GitHub CLI is installed
+    pub gh_available: bool, // GitHub token or gh CLI found
 
     // Last sync timestamp
     pub last_sync: Option<chrono::DateTime<chrono::Utc>>,
@@ -1046,8 +1078,15 @@ pub struct App {
     // Config menu state
     pub show_config_menu: bool,
     pub config_menu: ConfigMenuState,
+
+    // Changes written by other processes (shell hook, CLI commands)
+    pub data_version: i64,
+    pub last_external_check: Instant,
 }
 
+/// How often the TUI looks for usage and tool changes made outside it
+const EXTERNAL_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
 impl App {
     pub fn new(db: &Database) -> Result<Self> {
         let all_tools = db.list_tools(true, None)?; // installed only
@@ -1058,7 +1097,16 @@ impl App {
         let config_exists = HoardConfig::exists();
         let config = HoardConfig::load().unwrap_or_default();
         let ai_available = config.ai.provider != AiProvider::None;
-        let gh_available = which::which("gh").is_ok();
+        // Don't run `gh auth token` on startup: an installed gh is taken as a login
+        let gh_available = crate::github::stored_token(
+            config
+                .github
+                .api_url
+                .as_deref()
+                .unwrap_or(crate::github::DEFAULT_API_URL),
+        )
+        .is_some()
+            || which::which("gh").is_ok();
 
         // Get theme from config
         let theme_variant = super::theme::ThemeVariant::from_config_theme(config.tui.theme);
@@ -1088,6 +1136,8 @@ impl App {
             available_updates: HashMap::new(),
             updates_checked: false,
             updates_loading: false,
+            release_notes: HashMap::new(),
+            release_notes_scroll: (String::new(), 0),
             show_help: false,
             show_details_popup: false,
             sort_by: SortBy::default(),
@@ -1110,6 +1160,8 @@ impl App {
             discover_loading: false,
             show_config_menu,
             config_menu,
+            data_version: db.data_version().unwrap_or_default(),
+            last_external_check: Instant::now(),
         })
     }
 
@@ -1205,6 +1257,26 @@ impl App {
         self.available_updates.get(tool_name)
     }
 
+    /// Scroll offset of the release notes pane for a tool (reset when another is selected)
+    pub fn release_notes_offset(&self, tool_name: &str) -> u16 {
+        if self.release_notes_scroll.0 == tool_name {
+            self.release_notes_scroll.1
+        } else {
+            0
+        }
+    }
+
+    /// Scroll the selected tool's release notes by `delta` lines
+    pub fn scroll_release_notes(&mut self, delta: i16) {
+        let Some(name) = self.selected_tool().map(|t| t.name.clone()) else {
+            return;
+        };
+        let offset = self
+            .release_notes_offset(&name)
+            .saturating_add_signed(delta);
+        self.release_notes_scroll = (name, offset);
+    }
+
     /// Apply current search filter and sort to tools
     pub fn apply_filter_and_sort(&mut self) {
         // Start with all tools, optionally filtered by source and favorites
@@ -1266,7 +1338,7 @@ impl App {
         // Sort by fuzzy score when searching, otherwise by user preference
         if !self.search_query.is_empty() {
             // Sort by score descending (best matches first)
-            filtered.sort_by(|a, b| b.1.cmp(&a.1));
+            filtered.sort_by_key(|t| std::cmp::Reverse(t.1));
         } else {
             // Sort by user preference
             match self.sort_by {
@@ -1280,7 +1352,13 @@ impl App {
                     });
                 }
                 SortBy::Recent => {
-                    filtered.sort_by(|a, b| b.0.updated_at.cmp(&a.0.updated_at));
+                    filtered.sort_by_key(|t| std::cmp::Reverse(t.0.updated_at));
+                }
+                SortBy::Size => {
+                    let sizes = &self.cache.disk_usage;
+                    filtered.sort_by_key(|t| {
+                        std::cmp::Reverse(sizes.get(&t.0.name).copied().unwrap_or(0))
+                    });
                 }
             }
         }
@@ -1449,6 +1527,55 @@ impl App {
         self.cache.usage_data.get(tool_name)
     }
 
+    /// Pick up usage logged by the shell hook and writes from other hoards processes
+    ///
+    /// Called from the main loop; only does work every few seconds.
+    pub fn poll_external_changes(&mut self, db: &Database) {
+        if self.last_external_check.elapsed() < EXTERNAL_CHECK_INTERVAL {
+            return;
+        }
+        self.last_external_check = Instant::now();
+
+        let ingested = crate::spool::ingest(db).unwrap_or(0);
+        let version = db.data_version().unwrap_or(self.data_version);
+        if ingested > 0 || version != self.data_version {
+            self.data_version = version;
+            self.cache.reload_usage(db);
+            self.cache.reload_labels(db);
+            self.refresh_tools(db);
+        }
+    }
+
+    /// Ingest usage spooled by the shell hook and reload usage statistics
+    pub fn refresh_usage(&mut self, db: &Database) {
+        if let Err(e) = crate::spool::ingest(db) {
+            self.set_status(format!("Failed to ingest logged usage: {}", e), true);
+        }
+        self.cache.reload_usage(db);
+    }
+
+    /// Get subcommand usage for a tool, most used first (cached, or fetch from db)
+    pub fn get_subcommand_usage(&mut self, tool_name: &str, db: &Database) -> &[SubcommandUsage] {
+        self.cache
+            .subcommand_usage
+            .entry(tool_name.to_string())
+            .or_insert_with(|| db.get_subcommand_usage(tool_name).unwrap_or_default())
+    }
+
+    /// Get a tool's star counts over the last 30 days, oldest first (cached, or fetch from db)
+    pub fn get_star_history(&mut self, tool_name: &str, db: &Database) -> &[i64] {
+        self.cache
+            .star_history
+            .entry(tool_name.to_string())
+            .or_insert_with(|| {
+                db.get_star_history(tool_name, 30)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(_, stars)| stars)
+                    .collect()
+            })
+    }
+
     /// Get GitHub info for a tool (cached, or fetch from db)
     pub fn get_github_info(&mut self, tool_name: &str, db: &Database) -> Option<&GitHubInfo> {
         if !self.cache.github_cache.contains_key(tool_name)
@@ -1487,7 +1614,24 @@ impl App {
 
     /// Save config from menu and close
     pub fn save_config_menu(&mut self) {
-        let config = self.config_menu.to_config();
+        let mut config = self.config_menu.to_config();
+
+        // Keep settings the menu doesn't show
+        if let Ok(existing) = HoardConfig::load() {
+            config.github = existing.github;
+            config.forges = existing.forges;
+            // Like `ai config set`, settings of a previous provider don't carry over
+            config.ai = if config.ai.provider == existing.ai.provider {
+                existing.ai
+            } else {
+                AiConfig {
+                    provider: config.ai.provider,
+                    timeout_secs: existing.ai.timeout_secs,
+                    max_retries: existing.ai.max_retries,
+                    ..AiConfig::default()
+                }
+            };
+        }
 
         // Apply theme immediately
         self.theme_variant = super::theme::ThemeVariant::from_config_theme(config.tui.theme);
@@ -1908,8 +2052,9 @@ impl App {
             "name" | "n" | "alpha" => SortBy::Name,
             "usage" | "u" | "used" => SortBy::Usage,
             "recent" | "r" | "last" => SortBy::Recent,
+            "size" | "s" | "disk" => SortBy::Size,
             _ => {
-                self.set_status("Sort: name, usage, recent".to_string(), true);
+                self.set_status("Sort: name, usage, recent, size".to_string(), true);
                 return;
             }
         };
@@ -2429,9 +2574,43 @@ impl App {
                     let count = self.available_updates.len();
                     if count == 0 {
                         self.set_status("All tools are up to date!", false);
+                        false
                     } else {
                         self.set_status(format!("{} update(s) available", count), false);
+                        self.background_op = Some(BackgroundOp::FetchReleaseNotes { step: 0 });
+                        true
                     }
+                }
+            }
+            BackgroundOp::FetchReleaseNotes { step } => {
+                let mut names: Vec<String> = self.available_updates.keys().cloned().collect();
+                names.sort();
+
+                if step == 0 {
+                    self.release_notes.clear();
+                }
+                let Some(name) = names.get(step) else {
+                    return false;
+                };
+
+                self.loading_progress = LoadingProgress {
+                    current_step: step + 1,
+                    total_steps: names.len(),
+                    step_name: name.clone(),
+                    found_count: names.len(),
+                };
+
+                // Tools whose notes can't be fetched just show none
+                if let Some(update) = self.available_updates.get(name)
+                    && let Ok(notes) = crate::release_notes::release_notes(db, update)
+                {
+                    self.release_notes.insert(name.clone(), notes);
+                }
+
+                if step + 1 < names.len() {
+                    self.background_op = Some(BackgroundOp::FetchReleaseNotes { step: step + 1 });
+                    true
+                } else {
                     false
                 }
             }
@@ -2894,12 +3073,16 @@ mod tests {
 
         // Verify the indices in AiProvider::all() match expectations
         let all = AiProvider::all();
-        assert_eq!(all.len(), 5);
+        assert_eq!(all.len(), 9);
         assert_eq!(all[0], AiProvider::None);
         assert_eq!(all[1], AiProvider::Claude);
         assert_eq!(all[2], AiProvider::Gemini);
         assert_eq!(all[3], AiProvider::Codex);
         assert_eq!(all[4], AiProvider::Opencode);
+        assert_eq!(all[5], AiProvider::Anthropic);
+        assert_eq!(all[6], AiProvider::OpenAi);
+        assert_eq!(all[7], AiProvider::Ollama);
+        assert_eq!(all[8], AiProvider::Local);
     }
 
     #[test]
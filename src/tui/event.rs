@@ -127,15 +127,13 @@ fn handle_config_menu(app: &mut App, key: KeyEvent) {
         }
 
         // Left/right navigation for buttons
-        KeyCode::Char('h') | KeyCode::Left => {
-            if app.config_menu.section == ConfigSection::Buttons {
-                app.config_menu.button_focused = 0; // Save
-            }
+        KeyCode::Char('h') | KeyCode::Left if app.config_menu.section == ConfigSection::Buttons => {
+            app.config_menu.button_focused = 0; // Save
         }
-        KeyCode::Char('l') | KeyCode::Right => {
-            if app.config_menu.section == ConfigSection::Buttons {
-                app.config_menu.button_focused = 1; // Cancel
-            }
+        KeyCode::Char('l') | KeyCode::Right
+            if app.config_menu.section == ConfigSection::Buttons =>
+        {
+            app.config_menu.button_focused = 1; // Cancel
         }
 
         // Toggle checkbox / select radio / activate button
@@ -261,6 +259,10 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
         KeyCode::Char('D') => app.request_uninstall(), // Shift+d for uninstall (safer)
         KeyCode::Char('u') => app.request_update(),    // Update tools with available updates
 
+        // Scroll release notes (Updates tab)
+        KeyCode::Char('J') if app.tab == Tab::Updates => app.scroll_release_notes(1),
+        KeyCode::Char('K') if app.tab == Tab::Updates => app.scroll_release_notes(-1),
+
         // Details popup (for narrow terminals or quick view)
         KeyCode::Enter => app.toggle_details_popup(),
 
@@ -283,6 +285,7 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent, db: &Database) {
                 // Schedule background operation (main loop will show loading state)
                 app.schedule_op(super::app::BackgroundOp::CheckUpdates { step: 0 });
             } else {
+                app.refresh_usage(db);
                 app.refresh_tools(db);
             }
         }
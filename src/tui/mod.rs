@@ -62,6 +62,7 @@ fn run_app(terminal: &mut Tui, app: &mut App, db: &Database) -> Result<()> {
     while app.running {
         terminal.draw(|frame| ui::render(frame, app, db))?;
         event::handle_events(app, db)?;
+        app.poll_external_changes(db);
 
         // Execute background operations step by step with loading indicator
         while app.has_background_op() {
@@ -126,7 +126,7 @@ fn highlight_matches(
         vec![Span::styled(name.to_string(), Style::default().fg(normal))]
     }
 }
-use crate::db::Database;
+use crate::db::{Database, format_duration_ms};
 use crate::icons::source_icon;
 
 /// Generate a sparkline string from usage data
@@ -152,6 +152,25 @@ fn sparkline(data: &[i64]) -> String {
         .collect()
 }
 
+/// Generate a sparkline string from star counts, scaled between their low and high
+fn star_sparkline(data: &[i64]) -> String {
+    let (Some(&min), Some(&max)) = (data.iter().min(), data.iter().max()) else {
+        return String::new();
+    };
+    let blocks = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    data.iter()
+        .map(|&value| {
+            if max == min {
+                blocks[0]
+            } else {
+                let idx = (((value - min) as f64 / (max - min) as f64) * 7.0).round() as usize;
+                blocks[idx.min(7)]
+            }
+        })
+        .collect()
+}
+
 /// Determine health status based on usage recency
 /// Returns (indicator, color) tuple
 fn health_indicator(
@@ -297,7 +316,18 @@ fn render_body(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme, a
         // Store list area for mouse interaction
         app.set_list_area(chunks[0].x, chunks[0].y, chunks[0].width, chunks[0].height);
         render_tool_list(frame, app, theme, chunks[0]);
-        render_details(frame, app, db, theme, chunks[1]);
+
+        // Updates tab: release notes below the details
+        if app.tab == Tab::Updates && app.updates_checked && !app.available_updates.is_empty() {
+            let right = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+                .split(chunks[1]);
+            render_details(frame, app, db, theme, right[0]);
+            render_release_notes(frame, app, theme, right[1]);
+        } else {
+            render_details(frame, app, db, theme, chunks[1]);
+        }
     } else {
         // Narrow terminal: list only (details on Enter in future)
         app.set_list_area(area.x, area.y, area.width, area.height);
@@ -341,11 +371,17 @@ fn build_tool_extra_info(app: &App, tool: &crate::models::Tool) -> (String, Stri
         let usage = app.get_usage(&tool.name).map(|u| u.use_count).unwrap_or(0);
         let daily = app.cache.daily_usage.get(&tool.name);
         let spark_str = daily.map(|d| sparkline(d)).unwrap_or_default();
-        let info = if usage > 0 {
+        let mut info = if usage > 0 {
             format!(" ({usage})")
         } else {
             String::new()
         };
+        // Show the size column when sorting by it
+        if app.sort_by == super::app::SortBy::Size
+            && let Some(bytes) = app.cache.disk_usage.get(&tool.name)
+        {
+            info.push_str(&format!(" {}", crate::disk::format_bytes(*bytes)));
+        }
         (info, spark_str)
     }
 }
@@ -522,8 +558,15 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
     let tool = app.selected_tool().cloned();
 
     let content = if let Some(tool) = tool {
-        // Pre-fetch GitHub info while we have mutable access
+        // Pre-fetch GitHub info, star history and subcommand usage while we have mutable access
         let _ = app.get_github_info(&tool.name, db);
+        let star_history = app.get_star_history(&tool.name, db).to_vec();
+        let top_subcommands: Vec<(String, i64)> = app
+            .get_subcommand_usage(&tool.name, db)
+            .iter()
+            .take(5)
+            .map(|s| (s.subcommand.clone(), s.use_count))
+            .collect();
 
         let mut lines = vec![
             Line::from(vec![
@@ -579,6 +622,17 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
             ]));
         }
 
+        // Disk usage (measured by `insights disk`)
+        if let Some(bytes) = app.cache.disk_usage.get(&tool.name) {
+            lines.push(Line::from(vec![
+                Span::styled("Disk: ", Style::default().fg(theme.subtext0)),
+                Span::styled(
+                    crate::disk::format_bytes(*bytes),
+                    Style::default().fg(theme.text),
+                ),
+            ]));
+        }
+
         // Labels (as colored pills)
         if let Some(labels) = app.cache.labels_cache.get(&tool.name)
             && !labels.is_empty()
@@ -626,13 +680,64 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
                     ),
                 ]));
             }
+            if let Some(daily) = app.cache.daily_usage.get(&tool.name) {
+                lines.push(Line::from(vec![
+                    Span::styled("  7 days: ", Style::default().fg(theme.subtext0)),
+                    Span::styled(sparkline(daily), Style::default().fg(theme.teal)),
+                ]));
+            }
+            if let Some(outcomes) = app.cache.outcomes.get(&tool.name) {
+                if let Some(rate) = outcomes.success_rate() {
+                    let color = if outcomes.is_failing() {
+                        theme.red
+                    } else {
+                        theme.green
+                    };
+                    lines.push(Line::from(vec![
+                        Span::styled("  Success: ", Style::default().fg(theme.subtext0)),
+                        Span::styled(format!("{:.0}%", rate * 100.0), Style::default().fg(color)),
+                        Span::styled(
+                            format!(" of {} runs", outcomes.runs),
+                            Style::default().fg(theme.subtext0),
+                        ),
+                    ]));
+                }
+                if let (Some(p50), Some(p95)) = (outcomes.p50_ms, outcomes.p95_ms) {
+                    lines.push(Line::from(vec![
+                        Span::styled("  Duration: ", Style::default().fg(theme.subtext0)),
+                        Span::styled(
+                            format!(
+                                "p50 {} · p95 {}",
+                                format_duration_ms(p50),
+                                format_duration_ms(p95)
+                            ),
+                            Style::default().fg(theme.text),
+                        ),
+                    ]));
+                }
+            }
+            if !top_subcommands.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "  Top subcommands:",
+                    Style::default().fg(theme.subtext0),
+                )));
+                for (subcommand, count) in &top_subcommands {
+                    lines.push(Line::from(vec![
+                        Span::styled(
+                            format!("    {:<14}", subcommand),
+                            Style::default().fg(theme.text),
+                        ),
+                        Span::styled(format!("{}", count), Style::default().fg(theme.teal)),
+                    ]));
+                }
+            }
             lines.push(Line::from(""));
         }
 
-        // GitHub info (already fetched above)
+        // Repository info (already fetched above)
         if let Some(gh) = app.cache.github_cache.get(&tool.name) {
             lines.push(Line::from(Span::styled(
-                "GitHub:",
+                format!("{}:", gh.forge.label()),
                 Style::default()
                     .fg(theme.subtext0)
                     .add_modifier(Modifier::BOLD),
@@ -641,12 +746,53 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
                 Span::styled("  ★ Stars: ", Style::default().fg(theme.yellow)),
                 Span::styled(format_stars(gh.stars), Style::default().fg(theme.yellow)),
             ]));
+            if let (Some(first), Some(last)) = (star_history.first(), star_history.last())
+                && star_history.len() > 1
+            {
+                lines.push(Line::from(vec![
+                    Span::styled("  30 days: ", Style::default().fg(theme.subtext0)),
+                    Span::styled(
+                        star_sparkline(&star_history),
+                        Style::default().fg(theme.yellow),
+                    ),
+                    Span::styled(
+                        format!(" {:+}", last - first),
+                        Style::default().fg(theme.subtext0),
+                    ),
+                ]));
+            }
             if let Some(lang) = &gh.language {
                 lines.push(Line::from(vec![
                     Span::styled("  Language: ", Style::default().fg(theme.subtext0)),
                     Span::styled(lang.clone(), Style::default().fg(theme.peach)),
                 ]));
             }
+            if let Some(license) = &gh.license {
+                lines.push(Line::from(vec![
+                    Span::styled("  License: ", Style::default().fg(theme.subtext0)),
+                    Span::styled(license.clone(), Style::default().fg(theme.text)),
+                ]));
+            }
+            if let Some(tag) = &gh.latest_release {
+                lines.push(Line::from(vec![
+                    Span::styled("  Release: ", Style::default().fg(theme.subtext0)),
+                    Span::styled(tag.clone(), Style::default().fg(theme.green)),
+                ]));
+            }
+            if gh.archived {
+                lines.push(Line::from(Span::styled(
+                    "  Archived (read-only)",
+                    Style::default().fg(theme.red),
+                )));
+            } else if let Some(pushed) = &gh.pushed_at {
+                lines.push(Line::from(vec![
+                    Span::styled("  Last push: ", Style::default().fg(theme.subtext0)),
+                    Span::styled(
+                        format_friendly_datetime(pushed),
+                        Style::default().fg(theme.text),
+                    ),
+                ]));
+            }
             lines.push(Line::from(vec![
                 Span::styled("  Repo: ", Style::default().fg(theme.subtext0)),
                 Span::styled(
@@ -724,6 +870,95 @@ fn render_details(frame: &mut Frame, app: &mut App, db: &Database, theme: &Theme
     frame.render_widget(details, area);
 }
 
+/// Release notes of the selected tool's pending update, scrolled with J/K
+fn render_release_notes(frame: &mut Frame, app: &mut App, theme: &Theme, area: Rect) {
+    let name = app
+        .selected_tool()
+        .map(|t| t.name.clone())
+        .unwrap_or_default();
+    let notes = app.release_notes.get(&name);
+
+    let mut lines: Vec<Line> = Vec::new();
+    match notes {
+        _ if app.get_update(&name).is_none() => lines.push(Line::from(Span::styled(
+            "No update pending",
+            Style::default().fg(theme.subtext0),
+        ))),
+        None => lines.push(Line::from(Span::styled(
+            "No release notes found",
+            Style::default().fg(theme.subtext0),
+        ))),
+        Some(notes) => {
+            if notes.releases.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "No releases found between these versions",
+                    Style::default().fg(theme.subtext0),
+                )));
+            }
+            for release in &notes.releases {
+                let mut header = vec![Span::styled(
+                    release.tag_name.clone(),
+                    Style::default().fg(theme.blue).bold(),
+                )];
+                if let Some(date) = &release.published_at {
+                    header.push(Span::styled(
+                        format!("  {}", date.get(..10).unwrap_or(date)),
+                        Style::default().fg(theme.subtext0),
+                    ));
+                }
+                lines.push(Line::from(header));
+                if let Some(title) = release
+                    .name
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|n| !n.is_empty() && *n != release.tag_name)
+                {
+                    lines.push(Line::from(Span::styled(
+                        title.to_string(),
+                        Style::default().fg(theme.text).bold(),
+                    )));
+                }
+                for line in release.body.as_deref().unwrap_or_default().trim().lines() {
+                    lines.push(Line::from(Span::styled(
+                        line.trim_end().to_string(),
+                        Style::default().fg(theme.text),
+                    )));
+                }
+                lines.push(Line::from(""));
+            }
+            if let Some(link) = &notes.link {
+                lines.push(Line::from(vec![
+                    Span::styled("More: ", Style::default().fg(theme.subtext0)),
+                    Span::styled(link.clone(), Style::default().fg(theme.blue)),
+                ]));
+            }
+        }
+    }
+
+    // Keep at least the last line in view
+    let max_offset = lines.len().saturating_sub(1) as u16;
+    let offset = app.release_notes_offset(&name).min(max_offset);
+    if app.release_notes_scroll.0 == name {
+        app.release_notes_scroll.1 = offset;
+    }
+
+    let title = match app.get_update(&name) {
+        Some(update) => format!(" Release Notes {} → {} ", update.current, update.latest),
+        None => " Release Notes ".to_string(),
+    };
+    let paragraph = Paragraph::new(Text::from(lines))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.surface1))
+                .title(Span::styled(title, Style::default().fg(theme.text))),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((offset, 0));
+
+    frame.render_widget(paragraph, area);
+}
+
 /// Format star count (e.g., 1234 -> "1.2K")
 fn format_stars(stars: i64) -> String {
     if stars >= 1000 {
@@ -1456,6 +1691,13 @@ fn render_help_overlay(frame: &mut Frame, theme: &Theme, area: Rect) {
             Span::styled("  u        ", Style::default().fg(theme.yellow)),
             Span::styled("Update tool(s)", Style::default().fg(theme.text)),
         ]),
+        Line::from(vec![
+            Span::styled("  J/K      ", Style::default().fg(theme.yellow)),
+            Span::styled(
+                "Scroll release notes (Updates tab)",
+                Style::default().fg(theme.text),
+            ),
+        ]),
         Line::from(vec![
             Span::styled("  Enter    ", Style::default().fg(theme.yellow)),
             Span::styled("Show details popup", Style::default().fg(theme.text)),
@@ -1474,7 +1716,7 @@ fn render_help_overlay(frame: &mut Frame, theme: &Theme, area: Rect) {
         Line::from(vec![
             Span::styled("  s        ", Style::default().fg(theme.yellow)),
             Span::styled(
-                "Cycle sort (name/usage/recent)",
+                "Cycle sort (name/usage/recent/size)",
                 Style::default().fg(theme.text),
             ),
         ]),
@@ -1613,6 +1855,10 @@ fn render_config_ai_section(
             AiProvider::Gemini => "Gemini",
             AiProvider::Codex => "Codex",
             AiProvider::Opencode => "Opencode",
+            AiProvider::Anthropic => "Anthropic API",
+            AiProvider::OpenAi => "OpenAI-compatible API",
+            AiProvider::Ollama => "Ollama (local)",
+            AiProvider::Local => "Local server (llama.cpp, LM Studio)",
         };
         let selected = i == state.ai_selected;
         let focused = ai_focused && selected;
@@ -1997,11 +2243,9 @@ fn render_loading_overlay(frame: &mut Frame, app: &App, theme: &Theme, area: Rec
 
     // Build progress bar
     let bar_width = 30;
-    let filled = if progress.total_steps > 0 {
-        (progress.current_step * bar_width) / progress.total_steps
-    } else {
-        0
-    };
+    let filled = (progress.current_step * bar_width)
+        .checked_div(progress.total_steps)
+        .unwrap_or(0);
     let empty = bar_width - filled;
     let progress_bar = format!(
         "[{}{}] {}/{}",
@@ -654,7 +654,7 @@ pub fn get_migration_candidates(
 }
 
 /// Check if a version string is a stable release (not alpha, beta, rc, dev, etc.)
-fn is_stable_version(v: &str) -> bool {
+pub(crate) fn is_stable_version(v: &str) -> bool {
     // A stable version only contains digits, dots, and sometimes underscores
     // Pre-release versions contain letters like: 1.0a1, 1.0b2, 1.0rc1, 1.0.dev1, 1.0-alpha
     let lower = v.to_lowercase();